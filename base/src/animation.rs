@@ -0,0 +1,373 @@
+//a Imports
+use geo_nd::{quat, vector};
+
+use crate::{Quat, SkeletonPose, Transformation};
+
+//a Interpolation, AnimationProperty
+//tp Interpolation
+/// The interpolation used between keyframes of an [AnimationChannel]
+///
+/// This matches the interpolation modes of GLTF animation samplers
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Interpolation {
+    /// The value of the keyframe at or before the time is used
+    Step,
+    /// Values are linearly interpolated between keyframes; rotations
+    /// are spherically interpolated
+    #[default]
+    Linear,
+    /// Values are interpolated with a cubic Hermite spline, with each
+    /// keyframe having an in-tangent, a value, and an out-tangent
+    CubicSpline,
+}
+
+//tp AnimationProperty
+/// The property of a target that an [AnimationChannel] animates
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationProperty {
+    /// The translation of a [Transformation] (3xf32 per value)
+    #[default]
+    Translation,
+    /// The rotation of a [Transformation] (a [Quat], 4xf32 per value)
+    Rotation,
+    /// The scale of a [Transformation] (3xf32 per value)
+    Scale,
+    /// The morph target weights of a mesh (one f32 per morph target
+    /// per value)
+    Weights,
+}
+
+//a AnimationChannel
+//tp AnimationChannel
+/// A set of keyframes that animate a single property of a target
+///
+/// The target is an index whose meaning is determined by the client;
+/// usually it is a bone index in a [crate::Skeleton], or an index
+/// into an array of [Transformation]s
+///
+/// The keyframe values are held as an array of f32, with
+/// 'ele_size' f32 for each value; for cubic spline interpolation
+/// each keyframe has three such values (in-tangent, value,
+/// out-tangent) in that order
+#[derive(Debug, Clone)]
+pub struct AnimationChannel {
+    /// The target of the channel
+    target: usize,
+    /// The property of the target that is animated
+    property: AnimationProperty,
+    /// The interpolation to use between keyframes
+    interpolation: Interpolation,
+    /// Keyframe times in seconds, in increasing order
+    times: Vec<f32>,
+    /// Keyframe values
+    values: Vec<f32>,
+    /// Number of f32 in each value
+    ele_size: usize,
+}
+
+//ip AnimationChannel
+impl AnimationChannel {
+    //fp new
+    /// Create a new [AnimationChannel]
+    ///
+    /// The number of values must be a whole multiple of the number of
+    /// keyframe times (three times that for cubic spline
+    /// interpolation), and for translation, rotation and scale it must
+    /// match the size of the property
+    pub fn new(
+        target: usize,
+        property: AnimationProperty,
+        interpolation: Interpolation,
+        times: Vec<f32>,
+        values: Vec<f32>,
+    ) -> Self {
+        let values_per_key = {
+            if interpolation == Interpolation::CubicSpline {
+                3
+            } else {
+                1
+            }
+        };
+        let num_values = times.len() * values_per_key;
+        assert!(
+            num_values > 0 && values.len().is_multiple_of(num_values),
+            "Animation channel must have a whole number of values per keyframe"
+        );
+        let ele_size = values.len() / num_values;
+        match property {
+            AnimationProperty::Translation | AnimationProperty::Scale => {
+                assert_eq!(
+                    ele_size, 3,
+                    "Translation and scale channels must have Vec3 values"
+                )
+            }
+            AnimationProperty::Rotation => {
+                assert_eq!(ele_size, 4, "Rotation channels must have Quat values")
+            }
+            AnimationProperty::Weights => (),
+        }
+        Self {
+            target,
+            property,
+            interpolation,
+            times,
+            values,
+            ele_size,
+        }
+    }
+
+    //ap target
+    /// Get the target of the channel
+    pub fn target(&self) -> usize {
+        self.target
+    }
+
+    //ap property
+    /// Get the property of the target that the channel animates
+    pub fn property(&self) -> AnimationProperty {
+        self.property
+    }
+
+    //ap interpolation
+    /// Get the interpolation used by the channel
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    //ap ele_size
+    /// Get the number of f32 in each value of the channel
+    pub fn ele_size(&self) -> usize {
+        self.ele_size
+    }
+
+    //ap duration
+    /// Get the time of the last keyframe of the channel
+    pub fn duration(&self) -> f32 {
+        *self.times.last().unwrap()
+    }
+
+    //mi value
+    /// Borrow the value (not tangents) of keyframe 'k'
+    fn value(&self, k: usize) -> &[f32] {
+        let e = self.ele_size;
+        let n = {
+            if self.interpolation == Interpolation::CubicSpline {
+                3 * k + 1
+            } else {
+                k
+            }
+        };
+        &self.values[n * e..(n + 1) * e]
+    }
+
+    //mp sample_into
+    /// Evaluate the channel at time 't', writing 'ele_size' values
+    /// into 'out'
+    ///
+    /// Times before the first keyframe use the first keyframe value;
+    /// times after the last keyframe use the last keyframe value
+    pub fn sample_into(&self, t: f32, out: &mut [f32]) {
+        let e = self.ele_size;
+        let out = &mut out[0..e];
+        let n = self.times.len();
+        if n == 1 || t <= self.times[0] {
+            out.copy_from_slice(self.value(0));
+            return;
+        }
+        if t >= self.times[n - 1] {
+            out.copy_from_slice(self.value(n - 1));
+            return;
+        }
+        let k = self.times.partition_point(|kt| *kt <= t) - 1;
+        let dt = self.times[k + 1] - self.times[k];
+        let u = (t - self.times[k]) / dt;
+        match self.interpolation {
+            Interpolation::Step => {
+                out.copy_from_slice(self.value(k));
+            }
+            Interpolation::Linear if self.property == AnimationProperty::Rotation => {
+                let q0 = quat_of_slice(self.value(k));
+                let q1 = quat_of_slice(self.value(k + 1));
+                out.copy_from_slice(&slerp(u, &q0, &q1));
+            }
+            Interpolation::Linear => {
+                let v0 = self.value(k);
+                let v1 = self.value(k + 1);
+                for i in 0..e {
+                    out[i] = v0[i] * (1.0 - u) + v1[i] * u;
+                }
+            }
+            Interpolation::CubicSpline => {
+                let v0 = self.value(k);
+                let v1 = self.value(k + 1);
+                let b0 = &self.values[(3 * k + 2) * e..(3 * k + 3) * e];
+                let a1 = &self.values[(3 * k + 3) * e..(3 * k + 4) * e];
+                let u2 = u * u;
+                let u3 = u2 * u;
+                let h00 = 2.0 * u3 - 3.0 * u2 + 1.0;
+                let h10 = (u3 - 2.0 * u2 + u) * dt;
+                let h01 = -2.0 * u3 + 3.0 * u2;
+                let h11 = (u3 - u2) * dt;
+                for i in 0..e {
+                    out[i] = h00 * v0[i] + h10 * b0[i] + h01 * v1[i] + h11 * a1[i];
+                }
+                if self.property == AnimationProperty::Rotation {
+                    let q = quat::normalize(quat_of_slice(out));
+                    out.copy_from_slice(&q);
+                }
+            }
+        }
+    }
+
+    //mp apply
+    /// Apply the channel at time 't' to a [Transformation]
+    ///
+    /// Only the property animated by the channel is changed; a
+    /// weights channel does not affect the [Transformation]
+    pub fn apply(&self, t: f32, transformation: &mut Transformation) {
+        let mut v = [0.; 4];
+        match self.property {
+            AnimationProperty::Translation => {
+                self.sample_into(t, &mut v);
+                transformation.set_translation([v[0], v[1], v[2]]);
+            }
+            AnimationProperty::Rotation => {
+                self.sample_into(t, &mut v);
+                transformation.set_rotation(v);
+            }
+            AnimationProperty::Scale => {
+                self.sample_into(t, &mut v);
+                transformation.set_scale([v[0], v[1], v[2]]);
+            }
+            AnimationProperty::Weights => (),
+        }
+    }
+}
+
+//fi quat_of_slice
+fn quat_of_slice(s: &[f32]) -> Quat {
+    [s[0], s[1], s[2], s[3]]
+}
+
+//fi slerp
+/// Spherical linear interpolation of two unit quaternions, taking the
+/// shortest path
+fn slerp(u: f32, q0: &Quat, q1: &Quat) -> Quat {
+    let mut q1 = *q1;
+    let mut d = vector::dot(q0, &q1);
+    if d < 0. {
+        q1 = vector::scale(q1, -1.);
+        d = -d;
+    }
+    if d > 0.9995 {
+        return quat::nlerp(u, q0, &q1);
+    }
+    let theta = d.acos();
+    let s = theta.sin();
+    let s0 = ((1.0 - u) * theta).sin() / s;
+    let s1 = (u * theta).sin() / s;
+    vector::add(vector::scale(*q0, s0), &q1, s1)
+}
+
+//a AnimationClip
+//tp AnimationClip
+/// A named set of [AnimationChannel]s that are played together, such
+/// as a 'walk' cycle for a character
+#[derive(Debug, Clone, Default)]
+pub struct AnimationClip {
+    /// The name of the clip
+    name: String,
+    /// The duration of the clip - the time of the last keyframe of
+    /// all the channels
+    duration: f32,
+    /// The channels of the clip
+    channels: Vec<AnimationChannel>,
+}
+
+//ip AnimationClip
+impl AnimationClip {
+    //fp new
+    /// Create a new [AnimationClip] with no channels
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    //mp add_channel
+    /// Add a channel to the clip
+    pub fn add_channel(&mut self, channel: AnimationChannel) {
+        self.duration = self.duration.max(channel.duration());
+        self.channels.push(channel);
+    }
+
+    //ap name
+    /// Get the name of the clip
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    //ap duration
+    /// Get the duration of the clip in seconds
+    pub fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    //ap channels
+    /// Get the channels of the clip
+    pub fn channels(&self) -> &[AnimationChannel] {
+        &self.channels
+    }
+
+    //mp apply_to_transformations
+    /// Apply the clip at time 't' to an array of [Transformation]s,
+    /// with the channel targets being indices into the array
+    ///
+    /// Channels with targets beyond the array, and weights channels,
+    /// are ignored
+    pub fn apply_to_transformations(&self, t: f32, transformations: &mut [Transformation]) {
+        for c in &self.channels {
+            if let Some(transformation) = transformations.get_mut(c.target) {
+                c.apply(t, transformation);
+            }
+        }
+    }
+
+    //mp apply_to_morph_weights
+    /// Apply the weights channels of the clip with a particular
+    /// target at time 't' to a set of morph target weights
+    ///
+    /// Channels with more values than there are weights are ignored
+    pub fn apply_to_morph_weights(&self, t: f32, target: usize, weights: &mut [f32]) {
+        for c in &self.channels {
+            if c.property == AnimationProperty::Weights
+                && c.target == target
+                && c.ele_size <= weights.len()
+            {
+                c.sample_into(t, weights);
+            }
+        }
+    }
+
+    //mp apply_to_pose
+    /// Apply the clip at time 't' to a [SkeletonPose], with the
+    /// channel targets being bone indices in its [crate::Skeleton]
+    ///
+    /// The [SkeletonPose] must then be updated to generate the bone
+    /// matrices
+    pub fn apply_to_pose(&self, t: f32, pose: &mut SkeletonPose) {
+        for c in &self.channels {
+            if c.property == AnimationProperty::Weights {
+                continue;
+            }
+            if let Some(bone_pose) = pose.bone_pose_mut(c.target) {
+                let mut transformation = *bone_pose.transformation();
+                c.apply(t, &mut transformation);
+                bone_pose.set_transformation(transformation);
+            }
+        }
+    }
+
+    //zz All done
+}
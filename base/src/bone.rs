@@ -39,6 +39,14 @@ pub struct Bone {
     /// hierarchy root; updated when any transformation is changed in
     /// the hierarchy at this bone or above
     pub(crate) mtb: Mat4,
+    /// The mesh-to-bone mapping Matrix of the bind pose of a skinned
+    /// mesh, if it is given explicitly (such as by the inverse bind
+    /// matrices of a Gltf skin) rather than being the rest pose
+    pub(crate) bind_mtb: Option<Mat4>,
+    /// For a root bone, the parent-to-mesh mapping Matrix of the
+    /// (unanimated) parent of the bone, such as a transformed
+    /// armature; this is the identity for other bones
+    pub(crate) root_ptm: Mat4,
     ///  Index into matrix array to put this bones animated mtm
     pub matrix_index: usize,
 }
@@ -55,6 +63,8 @@ impl Bone {
             matrix_index,
             ptb,
             mtb,
+            bind_mtb: None,
+            root_ptm: matrix::identity4(),
         }
     }
 
@@ -73,12 +83,14 @@ impl Bone {
 
     //mp derive_matrices
     /// Derive matrices for the bone given a parent mesh-to-bone [Mat4]
+    ///
+    /// A root bone is placed in mesh space by its root_ptm matrix
     pub fn derive_matrices(&mut self, is_root: bool, parent_mtb: &Mat4) -> &Mat4 {
         self.ptb = self.transformation.mat4_inverse();
         if is_root {
-            self.mtb = self.ptb;
+            self.mtb = matrix::multiply4(&matrix::inverse4(&self.root_ptm), &self.ptb);
         } else {
-            self.mtb = matrix::multiply4(&self.ptb, parent_mtb);
+            self.mtb = matrix::multiply4(parent_mtb, &self.ptb);
         }
         &self.mtb
     }
@@ -89,6 +101,33 @@ impl Bone {
         &self.mtb
     }
 
+    //mp set_root_ptm
+    /// Set the parent-to-mesh Matrix for the bone if it is a root
+    /// bone, for a bone whose parent is not part of the skeleton
+    pub fn set_root_ptm(&mut self, root_ptm: Mat4) {
+        self.root_ptm = root_ptm;
+    }
+
+    //mp borrow_root_ptm
+    /// Borrow the parent-to-mesh Matrix used if the bone is a root bone
+    pub fn borrow_root_ptm(&self) -> &Mat4 {
+        &self.root_ptm
+    }
+
+    //mp set_bind_mtb
+    /// Set the mesh-to-bone Matrix of the bind pose of a skinned mesh;
+    /// if None then the bind pose is the rest pose
+    pub fn set_bind_mtb(&mut self, bind_mtb: Option<Mat4>) {
+        self.bind_mtb = bind_mtb;
+    }
+
+    //mp borrow_bind_mtb
+    /// Borrow the mesh-to-bone Matrix of the bind pose of a skinned
+    /// mesh - the rest pose mtb Matrix if no bind pose has been set
+    pub fn borrow_bind_mtb(&self) -> &Mat4 {
+        self.bind_mtb.as_ref().unwrap_or(&self.mtb)
+    }
+
     //mp borrow_ptb
     /// Borrow the ptb Matrix (for test mainly)
     pub fn borrow_ptb(&self) -> &Mat4 {
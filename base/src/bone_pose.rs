@@ -32,7 +32,7 @@ impl<'a> BonePose<'a> {
     /// Create a new pose of a bone
     pub fn new(bone: &'a Bone) -> Self {
         let transformation = *bone.borrow_transformation();
-        let pbtp = [0.; 16];
+        let pbtp = transformation.mat4();
         let animated_btm = [0.; 16];
         let animated_mtm = [0.; 16];
         Self {
@@ -48,6 +48,13 @@ impl<'a> BonePose<'a> {
     /// Reset the pose transformation to that of the bone in the skeleton
     pub fn transformation_reset(&mut self) {
         self.transformation = *self.bone.borrow_transformation();
+        self.pbtp = self.transformation.mat4();
+    }
+
+    //ap transformation
+    /// Get the current pose transformation of the posed bone
+    pub fn transformation(&self) -> &Transformation {
+        &self.transformation
     }
 
     //mp set_transformation
@@ -62,7 +69,9 @@ impl<'a> BonePose<'a> {
     /// animated-posed-bone-to-mesh matrix
     ///
     /// If there is no parent (is_root true) then the animated
-    /// bone-to-mesh is just the posed-bone-to-parent transformation
+    /// bone-to-mesh is the posed-bone-to-parent transformation
+    /// followed by the (unanimated) root parent-to-mesh matrix of the
+    /// bone
     ///
     /// If there is a parent then its pose transformation must be
     /// preapplied to this; when this animated_btm is applied to a
@@ -74,13 +83,16 @@ impl<'a> BonePose<'a> {
     /// *bone*s mtb matrix to get a vector in this local bone space,
     /// to which the animated_btm can be applied to get a model space
     /// vector. Hence multiplying animated_btm and bone.mtb together.
+    ///
+    /// If the bone has a bind pose that differs from its rest pose
+    /// then the mtb of the bind pose is used instead
     pub fn derive_animation(&mut self, is_root: bool, parent_animated_pbtm: &Mat4) -> &Mat4 {
         if is_root {
-            self.animated_btm = self.pbtp;
+            self.animated_btm = matrix::multiply4(&self.pbtp, self.bone.borrow_root_ptm());
         } else {
-            self.animated_btm = matrix::multiply4(parent_animated_pbtm, &self.pbtp);
+            self.animated_btm = matrix::multiply4(&self.pbtp, parent_animated_pbtm);
         }
-        self.animated_mtm = matrix::multiply4(&self.animated_btm, &self.bone.mtb);
+        self.animated_mtm = matrix::multiply4(self.bone.borrow_bind_mtb(), &self.animated_btm);
         &self.animated_btm
     }
 
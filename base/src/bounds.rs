@@ -0,0 +1,162 @@
+//a Imports
+use geo_nd::vector;
+
+use crate::{Mat4, Vec3};
+
+//a Bounds
+//tp Bounds
+/// Bounding volumes of a set of points - an axis-aligned box and a
+/// bounding sphere
+///
+/// The box is the tighter bound for objects that are aligned with the
+/// axes; the sphere is simpler to use for framing or culling, and is
+/// unchanged (except for scaling) by rotation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds {
+    /// Minimum coordinates of the box
+    min: Vec3,
+    /// Maximum coordinates of the box
+    max: Vec3,
+    /// Center of the sphere
+    center: Vec3,
+    /// Radius of the sphere
+    radius: f32,
+}
+
+//ip Bounds
+impl Bounds {
+    //fp of_box
+    /// Create bounds for an axis-aligned box; the sphere is that
+    /// through the corners of the box
+    pub fn of_box(min: Vec3, max: Vec3) -> Self {
+        let center = vector::scale(vector::add(min, &max, 1.), 0.5);
+        let radius = vector::distance(&center, &max);
+        Self {
+            min,
+            max,
+            center,
+            radius,
+        }
+    }
+
+    //fp of_points
+    /// Create bounds for a set of points, or None if there are none
+    ///
+    /// The sphere is centered on the center of the box, and passes
+    /// through the furthest point from that
+    pub fn of_points<I: IntoIterator<Item = Vec3>>(points: I) -> Option<Self> {
+        let points: Vec<Vec3> = points.into_iter().collect();
+        let first = *points.first()?;
+        let mut min = first;
+        let mut max = first;
+        for p in &points {
+            for i in 0..3 {
+                min[i] = min[i].min(p[i]);
+                max[i] = max[i].max(p[i]);
+            }
+        }
+        let center = vector::scale(vector::add(min, &max, 1.), 0.5);
+        let radius = points
+            .iter()
+            .map(|p| vector::distance(&center, p))
+            .fold(0., f32::max);
+        Some(Self {
+            min,
+            max,
+            center,
+            radius,
+        })
+    }
+
+    //ap min
+    /// Get the minimum coordinates of the box
+    pub fn min(&self) -> Vec3 {
+        self.min
+    }
+
+    //ap max
+    /// Get the maximum coordinates of the box
+    pub fn max(&self) -> Vec3 {
+        self.max
+    }
+
+    //ap center
+    /// Get the center of the sphere
+    pub fn center(&self) -> Vec3 {
+        self.center
+    }
+
+    //ap radius
+    /// Get the radius of the sphere
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    //mp union
+    /// Get the bounds of both this and another
+    pub fn union(&self, other: &Self) -> Self {
+        let mut min = self.min;
+        let mut max = self.max;
+        for i in 0..3 {
+            min[i] = min[i].min(other.min[i]);
+            max[i] = max[i].max(other.max[i]);
+        }
+        let d = vector::distance(&self.center, &other.center);
+        let (center, radius) = {
+            if d + other.radius <= self.radius {
+                (self.center, self.radius)
+            } else if d + self.radius <= other.radius {
+                (other.center, other.radius)
+            } else {
+                // The sphere touching the far sides of both spheres
+                let radius = (d + self.radius + other.radius) * 0.5;
+                let t = (radius - self.radius) / d;
+                let offset = vector::sub(other.center, &self.center, 1.);
+                (vector::add(self.center, &offset, t), radius)
+            }
+        };
+        Self {
+            min,
+            max,
+            center,
+            radius,
+        }
+    }
+
+    //mp transformed
+    /// Get the bounds after transformation by a matrix
+    ///
+    /// The box is that containing the transformed corners of this
+    /// box; the sphere center is transformed, and its radius scaled
+    /// by the largest scaling of the matrix
+    pub fn transformed(&self, m: &Mat4) -> Self {
+        let transform = |p: Vec3| {
+            let mut r = [m[12], m[13], m[14]];
+            for (c, pc) in p.iter().enumerate() {
+                for (row, rr) in r.iter_mut().enumerate() {
+                    *rr += m[4 * c + row] * pc;
+                }
+            }
+            r
+        };
+        let corners = (0..8).map(|i| {
+            [
+                if i & 1 == 0 { self.min[0] } else { self.max[0] },
+                if i & 2 == 0 { self.min[1] } else { self.max[1] },
+                if i & 4 == 0 { self.min[2] } else { self.max[2] },
+            ]
+        });
+        let box_bounds = Self::of_points(corners.map(transform)).unwrap();
+        let scale = (0..3)
+            .map(|c| vector::length(&[m[4 * c], m[4 * c + 1], m[4 * c + 2]]))
+            .fold(0., f32::max);
+        Self {
+            min: box_bounds.min,
+            max: box_bounds.max,
+            center: transform(self.center),
+            radius: self.radius * scale,
+        }
+    }
+
+    //zz All done
+}
@@ -106,6 +106,13 @@ impl<'a, R: Renderable> BufferDataAccessor<'a, R> {
         self.vertex_desc().ele_type()
     }
 
+    //ap is_normalized
+    /// Return true if integer elements of the field are normalized
+    #[inline]
+    pub fn is_normalized(&self) -> bool {
+        self.vertex_desc().is_normalized()
+    }
+
     //ap count
     /// Get the count of the number of elements in the field
     #[inline]
@@ -119,6 +126,44 @@ impl<'a, R: Renderable> BufferDataAccessor<'a, R> {
         self.vertex_desc().byte_length()
     }
 
+    //ap num_elements
+    /// Get the number of complete elements of the field within the
+    /// [BufferDescriptor]
+    pub fn num_elements(&self) -> usize {
+        let start = self.vertex_desc().byte_offset() as usize + self.byte_length() as usize;
+        let byte_length = self.desc.byte_length() as usize;
+        let stride = self.desc.stride() as usize;
+        if start > byte_length || stride == 0 {
+            0
+        } else {
+            (byte_length - start) / stride + 1
+        }
+    }
+
+    //mp read_f32s
+    /// Read all the elements of the field as f32, with 'count' values
+    /// for each element
+    ///
+    /// Integer elements are converted (and normalized if the field is
+    /// normalized)
+    pub fn read_f32s(&self) -> Vec<f32> {
+        let vertex_desc = self.vertex_desc();
+        let ele_type = vertex_desc.ele_type();
+        let ele_size = ele_type.byte_length() as usize;
+        let count = vertex_desc.count() as usize;
+        let stride = self.desc.stride() as usize;
+        let data = self.desc.as_ref();
+        let mut result = Vec::with_capacity(self.num_elements() * count);
+        for i in 0..self.num_elements() {
+            let start = i * stride + vertex_desc.byte_offset() as usize;
+            for n in 0..count {
+                let d = &data[start + n * ele_size..];
+                result.push(ele_type.read_f32(d, vertex_desc.is_normalized()));
+            }
+        }
+        result
+    }
+
     //zz All done
 }
 
@@ -0,0 +1,178 @@
+//a Imports
+use crate::{Mat4, Transformation};
+
+//a Projection
+//tp Projection
+/// The projection of a [Camera]
+///
+/// This matches the perspective and orthographic cameras of GLTF; the
+/// camera looks down its -Z axis, with +Y up and +X to the right
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Projection {
+    /// A perspective projection
+    Perspective {
+        /// Vertical field of view in radians
+        yfov: f32,
+        /// Aspect ratio (width / height) of the field of view; if None
+        /// then the aspect ratio of the viewport should be used
+        aspect_ratio: Option<f32>,
+        /// Distance to the near clipping plane
+        znear: f32,
+        /// Distance to the far clipping plane; if None then the
+        /// projection is infinite
+        zfar: Option<f32>,
+    },
+    /// An orthographic projection
+    Orthographic {
+        /// Half the width of the view
+        xmag: f32,
+        /// Half the height of the view
+        ymag: f32,
+        /// Distance to the near clipping plane
+        znear: f32,
+        /// Distance to the far clipping plane
+        zfar: f32,
+    },
+}
+
+//ip Default for Projection
+impl std::default::Default for Projection {
+    fn default() -> Self {
+        Self::Perspective {
+            yfov: std::f32::consts::FRAC_PI_4,
+            aspect_ratio: None,
+            znear: 0.1,
+            zfar: None,
+        }
+    }
+}
+
+//ip Projection
+impl Projection {
+    //mp mat4
+    /// Generate the projection matrix, using the aspect ratio of the
+    /// viewport if the projection does not specify one
+    pub fn mat4(&self, viewport_aspect_ratio: f32) -> Mat4 {
+        let mut m = [0.; 16];
+        match *self {
+            Self::Perspective {
+                yfov,
+                aspect_ratio,
+                znear,
+                zfar,
+            } => {
+                let aspect_ratio = aspect_ratio.unwrap_or(viewport_aspect_ratio);
+                let f = 1.0 / (yfov * 0.5).tan();
+                m[0] = f / aspect_ratio;
+                m[5] = f;
+                m[11] = -1.;
+                if let Some(zfar) = zfar {
+                    m[10] = (zfar + znear) / (znear - zfar);
+                    m[14] = 2. * zfar * znear / (znear - zfar);
+                } else {
+                    m[10] = -1.;
+                    m[14] = -2. * znear;
+                }
+            }
+            Self::Orthographic {
+                xmag,
+                ymag,
+                znear,
+                zfar,
+            } => {
+                m[0] = 1. / xmag;
+                m[5] = 1. / ymag;
+                m[10] = 2. / (znear - zfar);
+                m[14] = (zfar + znear) / (znear - zfar);
+                m[15] = 1.;
+            }
+        }
+        m
+    }
+}
+
+//a Camera
+//tp Camera
+/// A camera, with a [Projection] and a placement in world space
+///
+/// The transformation maps camera space to world space; hence the
+/// view matrix (world space to camera space) is its inverse
+#[derive(Debug, Clone, Default)]
+pub struct Camera {
+    /// Name of the camera
+    name: String,
+    /// The projection of the camera
+    projection: Projection,
+    /// The camera-to-world transformation
+    transformation: Transformation,
+}
+
+//ip Camera
+impl Camera {
+    //fp new
+    /// Create a new [Camera] with a projection, at the origin looking
+    /// down -Z
+    pub fn new(name: &str, projection: Projection) -> Self {
+        Self {
+            name: name.into(),
+            projection,
+            transformation: Transformation::new(),
+        }
+    }
+
+    //cp with_transformation
+    /// Set the camera-to-world transformation of the camera
+    pub fn with_transformation(mut self, transformation: Transformation) -> Self {
+        self.transformation = transformation;
+        self
+    }
+
+    //ap name
+    /// Get the name of the camera
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    //ap projection
+    /// Get the projection of the camera
+    pub fn projection(&self) -> &Projection {
+        &self.projection
+    }
+
+    //ap transformation
+    /// Get the camera-to-world transformation of the camera
+    pub fn transformation(&self) -> &Transformation {
+        &self.transformation
+    }
+
+    //mp set_transformation
+    /// Set the camera-to-world transformation of the camera
+    pub fn set_transformation(&mut self, transformation: Transformation) {
+        self.transformation = transformation;
+    }
+
+    //mp view_matrix
+    /// Generate the view matrix (world space to camera space)
+    pub fn view_matrix(&self) -> Mat4 {
+        self.transformation.mat4_inverse()
+    }
+
+    //mp projection_matrix
+    /// Generate the projection matrix, using the aspect ratio of the
+    /// viewport if the camera does not specify one
+    pub fn projection_matrix(&self, viewport_aspect_ratio: f32) -> Mat4 {
+        self.projection.mat4(viewport_aspect_ratio)
+    }
+
+    //mp view_projection_matrix
+    /// Generate the combined projection and view matrix, that maps
+    /// world space to clip space
+    pub fn view_projection_matrix(&self, viewport_aspect_ratio: f32) -> Mat4 {
+        geo_nd::matrix::multiply4(
+            &self.view_matrix(),
+            &self.projection_matrix(viewport_aspect_ratio),
+        )
+    }
+
+    //zz All done
+}
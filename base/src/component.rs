@@ -1,5 +1,7 @@
 //a Imports
-use crate::{Mesh, Transformation};
+use std::ops::Range;
+
+use crate::{Bounds, Mat4, Mesh, Renderable, Transformation, Vertices};
 
 //a Component
 //tp Component
@@ -13,6 +15,26 @@ pub struct Component {
     pub transformation: Option<Transformation>,
     /// The mesh associated with the component
     pub mesh: Mesh,
+    /// The bone (by matrix index) that the component is rigidly
+    /// attached to, if any
+    ///
+    /// Such a component (and its children) follows the animated pose
+    /// of the bone; its transformation places it at the rest pose of
+    /// the skeleton
+    pub bone: Option<usize>,
+    /// The bone matrices (by matrix index) of the skin of a skinned
+    /// mesh, if it has a skin of its own
+    ///
+    /// The joint indices of the vertices of the mesh index into this
+    /// range of the bone matrices; if None they index all of the bone
+    /// matrices
+    pub skin: Option<Range<usize>>,
+    /// The matrices of the instances of the mesh of the component,
+    /// each applied before the transformation; if empty then the
+    /// mesh is drawn once
+    ///
+    /// The children of the component are not instanced
+    pub instance_matrices: Vec<Mat4>,
 }
 
 //ip Component
@@ -23,6 +45,51 @@ impl Component {
         Self {
             transformation,
             mesh,
+            bone: None,
+            skin: None,
+            instance_matrices: Vec::new(),
+        }
+    }
+
+    //cp with_bone
+    /// Attach the [Component] rigidly to a bone (by matrix index)
+    pub fn with_bone(mut self, bone: usize) -> Self {
+        self.bone = Some(bone);
+        self
+    }
+
+    //cp with_skin
+    /// Skin the mesh of the [Component] with a range of the bone
+    /// matrices (by matrix index)
+    pub fn with_skin(mut self, skin: Range<usize>) -> Self {
+        self.skin = Some(skin);
+        self
+    }
+
+    //cp with_instances
+    /// Draw the mesh of the [Component] once for each instance matrix
+    pub fn with_instances(mut self, instance_matrices: Vec<Mat4>) -> Self {
+        self.instance_matrices = instance_matrices;
+        self
+    }
+
+    //mp calc_bounds
+    /// Calculate the bounds of the mesh of the [Component] after its
+    /// transformation (i.e. in the space of its parent), given the
+    /// vertices of the [crate::Object]
+    pub fn calc_bounds<R: Renderable>(&self, vertices: &[&Vertices<R>]) -> Option<Bounds> {
+        let mut bounds = self.mesh.calc_bounds(vertices)?;
+        if !self.instance_matrices.is_empty() {
+            bounds = self
+                .instance_matrices
+                .iter()
+                .map(|m| bounds.transformed(m))
+                .reduce(|a, b| a.union(&b))
+                .unwrap();
+        }
+        match &self.transformation {
+            Some(t) => Some(bounds.transformed(&t.mat4())),
+            None => Some(bounds),
         }
     }
 }
@@ -84,6 +84,12 @@ where
         !self.children.is_empty()
     }
 
+    //ap children
+    /// Borrow the indices of the children of this node
+    pub fn children(&self) -> &[usize] {
+        &self.children
+    }
+
     //zz All done
 }
 
@@ -1,5 +1,7 @@
 //a Imports
-use crate::{Instantiable, Mat4, Renderable, SkeletonPose, Transformation};
+use geo_nd::matrix;
+
+use crate::{Bounds, Instantiable, Mat4, Renderable, SkeletonPose, Transformation};
 
 //a Instance
 //tp Instance
@@ -29,6 +31,9 @@ where
     pub bone_poses: Vec<SkeletonPose<'a>>,
     /// Transformation matrices for the bones
     pub bone_matrices: Vec<Mat4>,
+    /// Morph target weights for each of the vertices of the
+    /// [Instantiable] (empty for vertices without morph targets)
+    pub morph_weights: Vec<Vec<f32>>,
 }
 
 impl<'a, R> Instance<'a, R>
@@ -42,21 +47,72 @@ where
     /// the [Instantiable] to be posed, and respective matrices for
     /// drawing the meshes within the [Instantiable]
     ///
+    /// If the [Instantiable] has a [crate::Skeleton] then a
+    /// [SkeletonPose] is created for it, in its rest pose; the bone
+    /// matrices start as those of the rest pose (and any bone
+    /// matrices beyond those of the poses are the identity)
+    ///
+    /// The morph target weights start as the defaults of the [Instantiable]
+    ///
     /// It should contain appropriate Materials too
     pub fn new(instantiable: &'a Instantiable<R>, num_bone_matrices: usize) -> Self {
         let transformation = Transformation::new();
         let trans_mat = [0.; 16];
-        let bone_poses = Vec::new();
+        let mut bone_poses = Vec::new();
+        if let Some(skeleton) = instantiable.skeleton.as_ref() {
+            bone_poses.push(SkeletonPose::new(skeleton));
+        }
         let mut bone_matrices = Vec::with_capacity(num_bone_matrices);
         for _ in 0..num_bone_matrices {
-            bone_matrices.push([0.; 16]);
+            bone_matrices.push(matrix::identity4());
         }
-        Self {
+        let mut instance = Self {
             instantiable,
             transformation,
             trans_mat,
             bone_poses,
             bone_matrices,
+            morph_weights: instantiable.morph_weights.clone(),
+        };
+        instance.copy_bone_matrices();
+        instance
+    }
+
+    //ap world_bounds
+    /// Get the bounds of the instance in world space - the bounds of
+    /// the [Instantiable] after the instance transformation
+    pub fn world_bounds(&self) -> Option<Bounds> {
+        self.instantiable
+            .bounds
+            .map(|b| b.transformed(&self.transformation.mat4()))
+    }
+
+    //mp update_bone_matrices
+    /// Update the [SkeletonPose]s for an animation tick, and copy
+    /// their matrices into the bone matrices of the instance ready
+    /// for drawing
+    ///
+    /// The poses should have been posed (e.g. with
+    /// [crate::AnimationClip::apply_to_pose]) prior to this
+    pub fn update_bone_matrices(&mut self, tick: usize) {
+        for pose in self.bone_poses.iter_mut() {
+            pose.update(tick);
+        }
+        self.copy_bone_matrices();
+    }
+
+    //mi copy_bone_matrices
+    /// Copy the matrices of the [SkeletonPose]s into the bone matrices
+    fn copy_bone_matrices(&mut self) {
+        let mut n = 0;
+        for pose in self.bone_poses.iter() {
+            for m in pose.bone_matrices() {
+                if n >= self.bone_matrices.len() {
+                    return;
+                }
+                self.bone_matrices[n] = *m;
+                n += 1;
+            }
         }
     }
 }
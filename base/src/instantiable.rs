@@ -2,7 +2,9 @@
 use crate::hierarchy;
 use hierarchy::Hierarchy;
 
-use crate::{Component, Instance, Material, RenderRecipe, Renderable, Skeleton, Texture, Vertices};
+use crate::{
+    Bounds, Component, Instance, Material, RenderRecipe, Renderable, Skeleton, Texture, Vertices,
+};
 
 //a Instantiable
 //tp Instantiable
@@ -36,6 +38,11 @@ where
     pub materials: Vec<R::Material>,
     /// Render recipe
     pub render_recipe: RenderRecipe,
+    /// Bounds of the object in object space, if it has any positions
+    pub bounds: Option<Bounds>,
+    /// Default morph target weights for each of the vertices (empty
+    /// for vertices without morph targets)
+    pub morph_weights: Vec<Vec<f32>>,
     /// Number of bone matrices required for all the bone sets in this structure
     pub num_bone_matrices: usize,
 }
@@ -61,8 +68,13 @@ where
         mut components: Hierarchy<Component>,
     ) -> Self {
         components.find_roots();
-        let render_recipe = RenderRecipe::from_component_hierarchy(&components);
-        let num_bone_matrices = 0;
+        let mut render_recipe = RenderRecipe::from_component_hierarchy(&components);
+        let bounds = render_recipe.calc_bounds(&vertices);
+        let num_bone_matrices = skeleton.as_ref().map_or(0, |s| s.max_index);
+        let morph_weights = vertices
+            .iter()
+            .map(|v| v.morph_weights().to_vec())
+            .collect();
         let vertices = vertices
             .into_iter()
             .map(|v| v.borrow_client().clone())
@@ -77,13 +89,16 @@ where
             textures,
             materials,
             render_recipe,
+            bounds,
+            morph_weights,
             num_bone_matrices,
         }
     }
 
     //mp instantiate
     /// Create an `Instance` from this instantiable - must be used with accompanying mesh data in the appropriate form for the client
-    /// Must still add bone_poses one per bone set
+    ///
+    /// If there is a skeleton then the instance has a pose for it
     pub fn instantiate(&self) -> Instance<R> {
         Instance::new(self, self.num_bone_matrices)
     }
@@ -28,7 +28,8 @@ include 3D modeling tools, games, and 3D user interfaces.
 
 The object model is derived from the Khronos glTF 3D
 model/scene description (<https://github.com/KhronosGroup/glTF>),
-without explicit support for animation or cameras.
+including keyframe animation (see [AnimationClip]) and cameras (see
+[Camera]).
 
 ## Overview of the model
 
@@ -220,6 +221,18 @@ the mesh vertex coordinate.
 
 A [Skeleton] is similar to a `skin` in GLTF.
 
+An [AnimationClip] is a set of [AnimationChannel]s, each of which
+animates the translation, rotation, scale (or morph weights) of a
+target with keyframes and an [Interpolation]; a clip can be evaluated
+at a time to set the posed [Transformation]s of a [SkeletonPose], or
+to update an array of [Transformation]s directly.
+
+[Vertices] may also have [MorphTarget]s (blend shapes), which displace
+their positions, normals and tangents; each [Instance] has a set of
+morph target weights for each of the vertices of its [Instantiable],
+which a clip can also animate. A renderer can use a [MorphedAttr] to
+blend the displaced attributes for the weights.
+
 /// Each bone has a transformation with respect to its parent that is
 /// a translation (its origin relative to its parent origin), scale
 /// (in each direction, although a common scale for each coordinates
@@ -536,8 +549,8 @@ Make only part of an instantiable be drawn (have a Vec of RenderRecipes, one per
 
 mod types;
 pub use types::BufferElementType;
-pub use types::MaterialAspect;
 pub use types::ShortIndex;
+pub use types::{AlphaMode, MaterialAspect};
 pub use types::{Mat3, Mat4, Quat, Vec3, Vec4};
 pub use types::{PrimitiveType, VertexAttr, VertexDesc};
 
@@ -551,6 +564,9 @@ pub mod hierarchy;
 mod transformation;
 pub use transformation::Transformation;
 
+mod bounds;
+pub use bounds::Bounds;
+
 mod bone;
 mod bone_pose;
 pub use bone::Bone;
@@ -560,6 +576,13 @@ mod skeleton;
 mod skeleton_pose;
 pub use skeleton::Skeleton;
 pub use skeleton_pose::SkeletonPose;
+mod animation;
+pub use animation::{AnimationChannel, AnimationClip, AnimationProperty, Interpolation};
+
+mod camera;
+mod light;
+pub use camera::{Camera, Projection};
+pub use light::{Light, LightKind};
 
 mod buffer_data;
 mod buffer_data_accessor;
@@ -579,7 +602,7 @@ pub use traits::{
 };
 
 mod texture;
-pub use texture::Texture;
+pub use texture::{Texture, TextureFilter, TextureSampler, TextureWrap};
 
 mod material;
 pub use material::BaseData as MaterialBaseData;
@@ -587,6 +610,8 @@ pub use material::{BaseMaterial, PbrMaterial};
 
 mod vertices;
 pub use vertices::Vertices;
+mod morph;
+pub use morph::{MorphTarget, MorphedAttr};
 mod mesh;
 mod primitive;
 pub use mesh::Mesh;
@@ -0,0 +1,128 @@
+//a Imports
+use geo_nd::quat;
+
+use crate::{Transformation, Vec3};
+
+//a LightKind
+//tp LightKind
+/// The kind of a [Light]
+///
+/// This matches the punctual lights of GLTF (KHR_lights_punctual)
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum LightKind {
+    /// A light infinitely far away, shining along its direction
+    #[default]
+    Directional,
+    /// A light at a position, shining in all directions
+    Point,
+    /// A light at a position, shining in a cone about its direction
+    Spot {
+        /// Angle from the direction at which the light starts to fall off
+        inner_cone_angle: f32,
+        /// Angle from the direction at which the light is fully off
+        outer_cone_angle: f32,
+    },
+}
+
+//a Light
+//tp Light
+/// A punctual light, with a world-space position and direction
+///
+/// A light is created at the origin shining down its -Z axis; it is
+/// placed in the world with [Light::placed]
+#[derive(Debug, Clone, Default)]
+pub struct Light {
+    /// Name of the light
+    name: String,
+    /// The kind of the light
+    kind: LightKind,
+    /// Linear RGB color of the light
+    color: Vec3,
+    /// Intensity of the light - lux for directional lights, candela
+    /// for point and spot lights
+    intensity: f32,
+    /// Distance beyond which the light has no effect; None for infinite
+    range: Option<f32>,
+    /// World-space position of the light
+    position: Vec3,
+    /// World-space direction that the light shines in (unit vector)
+    direction: Vec3,
+}
+
+//ip Light
+impl Light {
+    //fp new
+    /// Create a new [Light] at the origin, shining down -Z
+    pub fn new(name: &str, kind: LightKind, color: Vec3, intensity: f32) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+            color,
+            intensity,
+            range: None,
+            position: [0.; 3],
+            direction: [0., 0., -1.],
+        }
+    }
+
+    //cp with_range
+    /// Set the range of the light
+    pub fn with_range(mut self, range: Option<f32>) -> Self {
+        self.range = range;
+        self
+    }
+
+    //cp placed
+    /// Place the light in world space with a light-to-world
+    /// transformation; the position is the translation, and the
+    /// direction is the -Z axis rotated by the transformation
+    pub fn placed(mut self, transformation: &Transformation) -> Self {
+        self.position = transformation.translation();
+        self.direction = quat::apply3(&transformation.rotation(), &[0., 0., -1.]);
+        self
+    }
+
+    //ap name
+    /// Get the name of the light
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    //ap kind
+    /// Get the kind of the light
+    pub fn kind(&self) -> LightKind {
+        self.kind
+    }
+
+    //ap color
+    /// Get the linear RGB color of the light
+    pub fn color(&self) -> Vec3 {
+        self.color
+    }
+
+    //ap intensity
+    /// Get the intensity of the light
+    pub fn intensity(&self) -> f32 {
+        self.intensity
+    }
+
+    //ap range
+    /// Get the range of the light, if it is not infinite
+    pub fn range(&self) -> Option<f32> {
+        self.range
+    }
+
+    //ap position
+    /// Get the world-space position of the light
+    pub fn position(&self) -> Vec3 {
+        self.position
+    }
+
+    //ap direction
+    /// Get the world-space direction the light shines in
+    pub fn direction(&self) -> Vec3 {
+        self.direction
+    }
+
+    //zz All done
+}
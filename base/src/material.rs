@@ -7,7 +7,7 @@ This provides for abstract Materials which can be used by any 3D model
 !*/
 
 //a Imports
-use crate::{Material, MaterialAspect, ShortIndex};
+use crate::{AlphaMode, Mat3, Material, MaterialAspect, ShortIndex};
 
 //a BaseData
 //tp BaseData
@@ -40,6 +40,10 @@ pub struct BaseData {
     /// The alpha data for the texture (alpha mode, alpha cutoff, etc)
     ///
     /// effectively extensions
+    ///
+    /// Bits 0 and 1 are the [AlphaMode]; bit 2 is set if the material
+    /// is double-sided; bit 3 is set if the material is unlit; the top
+    /// 16 bits are the alpha cutoff in 65536ths
     pub alpha_etc: u32,
 }
 
@@ -91,6 +95,65 @@ impl BaseData {
         (metallic, roughness)
     }
 
+    //mp set_alpha_mode
+    /// Set the alpha mode of the material
+    pub fn set_alpha_mode(&mut self, alpha_mode: AlphaMode) {
+        self.alpha_etc = (self.alpha_etc & !3) | (alpha_mode as u32);
+    }
+
+    //ap alpha_mode
+    /// Get the alpha mode of the material
+    pub fn alpha_mode(&self) -> AlphaMode {
+        AlphaMode::of_u32(self.alpha_etc & 3)
+    }
+
+    //mp set_alpha_cutoff
+    /// Set the alpha cutoff of the material, used if the alpha mode is Mask
+    pub fn set_alpha_cutoff(&mut self, alpha_cutoff: f32) {
+        let alpha_cutoff = (alpha_cutoff.clamp(0., 1.) * 65536.0).min(65535.0) as u32;
+        self.alpha_etc = (self.alpha_etc & 0xffff) | (alpha_cutoff << 16);
+    }
+
+    //ap alpha_cutoff
+    /// Get the alpha cutoff of the material
+    pub fn alpha_cutoff(&self) -> f32 {
+        ((self.alpha_etc >> 16) as f32) / 65536.0
+    }
+
+    //mp set_double_sided
+    /// Set whether the material is double-sided (back faces should
+    /// not be culled, and are lit with a reversed normal)
+    pub fn set_double_sided(&mut self, double_sided: bool) {
+        if double_sided {
+            self.alpha_etc |= 4;
+        } else {
+            self.alpha_etc &= !4;
+        }
+    }
+
+    //ap double_sided
+    /// Return true if the material is double-sided
+    pub fn double_sided(&self) -> bool {
+        (self.alpha_etc & 4) != 0
+    }
+
+    //mp set_unlit
+    /// Set whether the material is unlit (rendered with its color
+    /// without any lighting)
+    pub fn set_unlit(&mut self, unlit: bool) {
+        if unlit {
+            self.alpha_etc |= 8;
+        } else {
+            self.alpha_etc &= !8;
+        }
+    }
+
+    //ap unlit
+    /// Return true if the material is unlit
+    pub fn unlit(&self) -> bool {
+        (self.alpha_etc & 8) != 0
+    }
+
     //ap rgba_tuple
     /// Return a tuple of R, G, B, A of the color
     pub fn rgba_tuple(&self) -> (u8, u8, u8, u8) {
@@ -104,14 +167,22 @@ impl BaseData {
     //zz All done
 }
 
+//ip From<u32> for BaseData
+/// Create [BaseData] from an RGBA color; the alpha mode is Blend if
+/// the color is not fully opaque, and the alpha cutoff is 0.5
 impl From<u32> for BaseData {
     fn from(rgba: u32) -> Self {
-        Self {
+        let mut base_data = Self {
             rgba,
             metallic_roughness: 0,
             emissive_rgb: 0,
             alpha_etc: 0,
+        };
+        if (rgba >> 24) < 255 {
+            base_data.set_alpha_mode(AlphaMode::Blend);
         }
+        base_data.set_alpha_cutoff(0.5);
+        base_data
     }
 }
 
@@ -150,14 +221,47 @@ impl Material for BaseMaterial {
 //a PbrMaterial
 //tp PbrMaterial
 /// A physically-based rendered material with full set of textures
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct PbrMaterial {
     base_data: BaseData,
-    base_texture: ShortIndex,
-    normal_texture: ShortIndex,
-    mr_texture: ShortIndex,
-    occlusion_texture: ShortIndex,
-    emission_texture: ShortIndex,
+    /// Texture for each aspect, in the order of [MaterialAspect]
+    textures: [ShortIndex; MaterialAspect::NUM],
+    /// Texture coordinate set used by each texture, in the order of
+    /// [MaterialAspect]
+    tex_coords: [u8; MaterialAspect::NUM],
+    /// Scale of the X and Y of the normal texture
+    normal_scale: f32,
+    /// Strength of the occlusion texture; 0 is no occlusion
+    occlusion_strength: f32,
+    /// Transformation of the texture coordinates for each texture,
+    /// in the order of [MaterialAspect]
+    uv_transforms: [Mat3; MaterialAspect::NUM],
+    /// Multiplier for the emissive color
+    emissive_strength: f32,
+    /// Intensity and roughness of the clearcoat layer
+    clearcoat: (f32, f32),
+    /// Fraction of light transmitted through the surface
+    transmission: f32,
+    /// Index of refraction
+    ior: f32,
+}
+
+//ip Default for PbrMaterial
+impl Default for PbrMaterial {
+    fn default() -> Self {
+        Self {
+            base_data: BaseData::default(),
+            textures: [ShortIndex::none(); MaterialAspect::NUM],
+            tex_coords: [0; MaterialAspect::NUM],
+            normal_scale: 1.0,
+            occlusion_strength: 1.0,
+            uv_transforms: [[1., 0., 0., 0., 1., 0., 0., 0., 1.]; MaterialAspect::NUM],
+            emissive_strength: 1.0,
+            clearcoat: (0., 0.),
+            transmission: 0.,
+            ior: 1.5,
+        }
+    }
 }
 
 //ip PbrMaterial
@@ -180,6 +284,46 @@ impl PbrMaterial {
         self.base_data.set_rgba((r, g, b, a));
     }
 
+    //mp set_alpha_mode
+    /// Set the alpha mode and alpha cutoff
+    pub fn set_alpha_mode(&mut self, alpha_mode: AlphaMode, alpha_cutoff: f32) {
+        self.base_data.set_alpha_mode(alpha_mode);
+        self.base_data.set_alpha_cutoff(alpha_cutoff);
+    }
+
+    //mp set_double_sided
+    /// Set whether the material is double-sided
+    pub fn set_double_sided(&mut self, double_sided: bool) {
+        self.base_data.set_double_sided(double_sided);
+    }
+
+    //mp set_normal_scale
+    /// Set the scale of the X and Y of the normal texture
+    pub fn set_normal_scale(&mut self, normal_scale: f32) {
+        self.normal_scale = normal_scale;
+    }
+
+    //mp set_occlusion_strength
+    /// Set the strength of the occlusion texture
+    pub fn set_occlusion_strength(&mut self, occlusion_strength: f32) {
+        self.occlusion_strength = occlusion_strength;
+    }
+
+    //mp set_tex_coord
+    /// Set the texture coordinate set (0 for TexCoords0, etc) used by
+    /// the texture of an aspect
+    pub fn set_tex_coord(&mut self, aspect: MaterialAspect, tex_coord: usize) {
+        self.tex_coords[aspect as usize] = tex_coord.min(255) as u8;
+    }
+
+    //mp set_uv_transform
+    /// Set the transformation (a column-major 3x3 matrix applied to
+    /// (u, v, 1)) of the texture coordinates used by the texture of
+    /// an aspect
+    pub fn set_uv_transform(&mut self, aspect: MaterialAspect, uv_transform: Mat3) {
+        self.uv_transforms[aspect as usize] = uv_transform;
+    }
+
     //mp set_emissive_rgb
     /// Set the emission RGB
     pub fn set_emissive_rgb(&mut self, (r, g, b): (u8, u8, u8)) {
@@ -198,29 +342,40 @@ impl PbrMaterial {
         self.base_data = base_data.clone();
     }
 
+    //mp set_unlit
+    /// Set whether the material is unlit
+    pub fn set_unlit(&mut self, unlit: bool) {
+        self.base_data.set_unlit(unlit);
+    }
+
+    //mp set_emissive_strength
+    /// Set the multiplier for the emissive color
+    pub fn set_emissive_strength(&mut self, emissive_strength: f32) {
+        self.emissive_strength = emissive_strength;
+    }
+
+    //mp set_clearcoat
+    /// Set the intensity and roughness of the clearcoat layer
+    pub fn set_clearcoat(&mut self, clearcoat: f32, roughness: f32) {
+        self.clearcoat = (clearcoat, roughness);
+    }
+
+    //mp set_transmission
+    /// Set the fraction of light transmitted through the surface
+    pub fn set_transmission(&mut self, transmission: f32) {
+        self.transmission = transmission;
+    }
+
+    //mp set_ior
+    /// Set the index of refraction
+    pub fn set_ior(&mut self, ior: f32) {
+        self.ior = ior;
+    }
+
     //mp set_texture
-    /// Set a texture (currently just base texture) to an index in the Textures of an object
+    /// Set the texture of an aspect to an index in the Textures of an object
     pub fn set_texture(&mut self, aspect: MaterialAspect, index: ShortIndex) {
-        use MaterialAspect::*;
-        #[allow(unreachable_patterns)]
-        match aspect {
-            Color => {
-                self.base_texture = index;
-            }
-            Normal => {
-                self.normal_texture = index;
-            }
-            MetallicRoughness => {
-                self.mr_texture = index;
-            }
-            Occlusion => {
-                self.occlusion_texture = index;
-            }
-            Emission => {
-                self.emission_texture = index;
-            }
-            _ => (),
-        }
+        self.textures[aspect as usize] = index;
     }
 }
 
@@ -231,15 +386,38 @@ impl Material for PbrMaterial {
     }
 
     fn texture(&self, aspect: MaterialAspect) -> ShortIndex {
-        use MaterialAspect::*;
-        #[allow(unreachable_patterns)]
-        match aspect {
-            Color => self.base_texture,
-            Normal => self.normal_texture,
-            MetallicRoughness => self.mr_texture,
-            Occlusion => self.occlusion_texture,
-            Emission => self.emission_texture,
-            _ => ShortIndex::none(),
-        }
+        self.textures[aspect as usize]
+    }
+
+    fn tex_coord(&self, aspect: MaterialAspect) -> usize {
+        self.tex_coords[aspect as usize] as usize
+    }
+
+    fn normal_scale(&self) -> f32 {
+        self.normal_scale
+    }
+
+    fn occlusion_strength(&self) -> f32 {
+        self.occlusion_strength
+    }
+
+    fn uv_transform(&self, aspect: MaterialAspect) -> Mat3 {
+        self.uv_transforms[aspect as usize]
+    }
+
+    fn emissive_strength(&self) -> f32 {
+        self.emissive_strength
+    }
+
+    fn clearcoat(&self) -> (f32, f32) {
+        self.clearcoat
+    }
+
+    fn transmission(&self) -> f32 {
+        self.transmission
+    }
+
+    fn ior(&self) -> f32 {
+        self.ior
     }
 }
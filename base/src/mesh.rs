@@ -1,5 +1,5 @@
 //a Imports
-use crate::Primitive;
+use crate::{Bounds, Primitive, Renderable, Vertices};
 
 //a Mesh
 //tp Mesh
@@ -22,5 +22,15 @@ impl Mesh {
         self.primitives.push(primitive);
     }
 
+    //mp calc_bounds
+    /// Calculate the bounds of all the primitives of the [Mesh], given
+    /// the vertices of the [crate::Object]
+    pub fn calc_bounds<R: Renderable>(&self, vertices: &[&Vertices<R>]) -> Option<Bounds> {
+        self.primitives
+            .iter()
+            .filter_map(|p| p.calc_bounds(vertices))
+            .reduce(|a, b| a.union(&b))
+    }
+
     //zz All done
 }
@@ -0,0 +1,189 @@
+//a Imports
+use crate::{BufferDataAccessor, Renderable, VertexAttr, Vertices};
+
+//a MorphTarget
+//tp MorphTarget
+/// A morph target (or blend shape) of a set of [Vertices]
+///
+/// A morph target provides displacements for some of the attributes
+/// of the vertices (position, normal and tangent); when drawn, each
+/// attribute is its value plus the sum of the displacements of the
+/// targets scaled by the weights of the targets
+///
+/// The displacements of an attribute are provided for the same
+/// number of vertices as the attribute itself
+#[derive(Debug)]
+pub struct MorphTarget<'vertices, R: Renderable> {
+    /// The displacements, and the attribute that each displaces
+    attrs: Vec<(VertexAttr, &'vertices BufferDataAccessor<'vertices, R>)>,
+}
+
+//ip Default for MorphTarget
+impl<'vertices, R: Renderable> Default for MorphTarget<'vertices, R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//ip MorphTarget
+impl<'vertices, R: Renderable> MorphTarget<'vertices, R> {
+    //fp new
+    /// Create a new [MorphTarget] that displaces no attributes
+    pub fn new() -> Self {
+        Self { attrs: Vec::new() }
+    }
+
+    //mp add_attr
+    /// Add the displacements for a particular [VertexAttr], replacing
+    /// any that the target already has
+    pub fn add_attr(
+        &mut self,
+        vertex_attr: VertexAttr,
+        accessor: &'vertices BufferDataAccessor<'vertices, R>,
+    ) {
+        if let Some(p) = self.attrs.iter().position(|(va, _)| *va == vertex_attr) {
+            self.attrs[p].1 = accessor;
+        } else {
+            self.attrs.push((vertex_attr, accessor));
+        }
+    }
+
+    //mp borrow_attr
+    /// Borrow the displacements for an attribute, if the target has them
+    pub fn borrow_attr(
+        &self,
+        vertex_attr: VertexAttr,
+    ) -> Option<&BufferDataAccessor<'vertices, R>> {
+        self.attrs
+            .iter()
+            .find(|(va, _)| *va == vertex_attr)
+            .map(|(_, a)| *a)
+    }
+
+    //mp iter_attrs
+    /// Iterate through the displaced attributes and their displacements
+    pub fn iter_attrs(
+        &self,
+    ) -> std::slice::Iter<'_, (VertexAttr, &'vertices BufferDataAccessor<'vertices, R>)> {
+        self.attrs.iter()
+    }
+
+    //zz All done
+}
+
+//a MorphedAttr
+//tp MorphedAttr
+/// A copy of an attribute of a set of [Vertices], and its
+/// displacements for each of the morph targets of the vertices, from
+/// which the attribute can be blended for a set of weights
+///
+/// This permits a renderer to apply the morph targets on the CPU,
+/// uploading the blended attribute before drawing
+#[derive(Debug, Clone)]
+pub struct MorphedAttr {
+    /// The attribute
+    vertex_attr: VertexAttr,
+    /// Number of f32 for each vertex
+    count: usize,
+    /// The values of the attribute, 'count' f32 per vertex
+    base: Vec<f32>,
+    /// The displacements of the attribute for each morph target, with
+    /// the same layout as 'base'; empty if the target does not
+    /// displace the attribute
+    displacements: Vec<Vec<f32>>,
+}
+
+//ip MorphedAttr
+impl MorphedAttr {
+    //fp of_vertices
+    /// Create a [MorphedAttr] for an attribute of a set of
+    /// [Vertices], if the vertices have the attribute and at least
+    /// one of their morph targets displaces it
+    ///
+    /// Displacements with fewer elements than the attribute (such as
+    /// the three elements of a tangent displacement) only displace the
+    /// first elements of the attribute
+    pub fn of_vertices<R: Renderable>(
+        vertices: &Vertices<R>,
+        vertex_attr: VertexAttr,
+    ) -> Option<Self> {
+        let targets = vertices.morph_targets();
+        if !targets.iter().any(|t| t.borrow_attr(vertex_attr).is_some()) {
+            return None;
+        }
+        let accessor = vertices.borrow_attr(vertex_attr)?;
+        let count = accessor.count() as usize;
+        let base = accessor.read_f32s();
+        let displacements = targets
+            .iter()
+            .map(|t| {
+                let Some(d) = t.borrow_attr(vertex_attr) else {
+                    return vec![];
+                };
+                let d_count = d.count() as usize;
+                let d = d.read_f32s();
+                let mut displacement = vec![0.; base.len()];
+                for (v, dv) in displacement
+                    .chunks_exact_mut(count)
+                    .zip(d.chunks_exact(d_count))
+                {
+                    for (v, dv) in v.iter_mut().zip(dv) {
+                        *v = *dv;
+                    }
+                }
+                displacement
+            })
+            .collect();
+        Some(Self {
+            vertex_attr,
+            count,
+            base,
+            displacements,
+        })
+    }
+
+    //ap vertex_attr
+    /// Get the attribute that is morphed
+    pub fn vertex_attr(&self) -> VertexAttr {
+        self.vertex_attr
+    }
+
+    //ap count
+    /// Get the number of f32 for each vertex
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    //ap base
+    /// Get the values of the attribute without any morphing
+    pub fn base(&self) -> &[f32] {
+        &self.base
+    }
+
+    //ap num_targets
+    /// Get the number of morph targets
+    pub fn num_targets(&self) -> usize {
+        self.displacements.len()
+    }
+
+    //mp blend
+    /// Blend the attribute for a set of morph target weights, into
+    /// 'out'
+    ///
+    /// Weights for targets beyond those of the vertices are ignored;
+    /// targets without weights are not applied
+    pub fn blend(&self, weights: &[f32], out: &mut Vec<f32>) {
+        out.clear();
+        out.extend_from_slice(&self.base);
+        for (w, d) in weights.iter().zip(self.displacements.iter()) {
+            if *w == 0. {
+                continue;
+            }
+            for (o, d) in out.iter_mut().zip(d.iter()) {
+                *o += w * d;
+            }
+        }
+    }
+
+    //zz All done
+}
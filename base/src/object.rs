@@ -1,9 +1,11 @@
 //a Imports
+use std::ops::Range;
+
 use crate::hierarchy;
 use crate::Renderable;
 use crate::{
-    Component, Instantiable, Material, Mesh, ShortIndex, Skeleton, Texture, Transformation,
-    Vertices,
+    Bounds, Component, Instantiable, Mat4, Material, Mesh, RenderRecipe, ShortIndex, Skeleton,
+    Texture, Transformation, Vertices,
 };
 use hierarchy::Hierarchy;
 
@@ -140,6 +142,73 @@ where
         child
     }
 
+    //fp add_bone_component
+    /// Add a component to the hierarchy that is rigidly attached to a
+    /// bone (by matrix index) of the skeleton, such as a sword held
+    /// in a hand
+    ///
+    /// The transformation places the component at the rest pose of
+    /// the skeleton; when drawn the component follows the animated
+    /// pose of the bone
+    pub fn add_bone_component(
+        &mut self,
+        parent: Option<usize>,
+        bone: usize,
+        transformation: Option<Transformation>,
+        mesh: Mesh,
+    ) -> usize {
+        let node = Component::new(transformation, mesh).with_bone(bone);
+        let child = self.components.add_node(node);
+        if let Some(parent) = parent {
+            self.components.relate(parent, child);
+        }
+        child
+    }
+
+    //fp add_skinned_component
+    /// Add a component to the hierarchy whose mesh is skinned with a
+    /// range of the bone matrices (by matrix index) of the skeleton,
+    /// such as one of a number of characters each with its own skin
+    ///
+    /// The joint indices of the vertices of the mesh index into the
+    /// range
+    pub fn add_skinned_component(
+        &mut self,
+        parent: Option<usize>,
+        skin: Range<usize>,
+        transformation: Option<Transformation>,
+        mesh: Mesh,
+    ) -> usize {
+        let node = Component::new(transformation, mesh).with_skin(skin);
+        let child = self.components.add_node(node);
+        if let Some(parent) = parent {
+            self.components.relate(parent, child);
+        }
+        child
+    }
+
+    //fp add_instanced_component
+    /// Add a component to the hierarchy whose mesh is drawn once for
+    /// each of a set of instance matrices, such as the trees of a
+    /// forest
+    ///
+    /// Each instance matrix is applied before the transformation of
+    /// the component
+    pub fn add_instanced_component(
+        &mut self,
+        parent: Option<usize>,
+        transformation: Option<Transformation>,
+        instance_matrices: Vec<Mat4>,
+        mesh: Mesh,
+    ) -> usize {
+        let node = Component::new(transformation, mesh).with_instances(instance_matrices);
+        let child = self.components.add_node(node);
+        if let Some(parent) = parent {
+            self.components.relate(parent, child);
+        }
+        child
+    }
+
     //fp relate
     /// Add a relation between two components
     pub fn relate(&mut self, parent: usize, child: usize) {
@@ -156,6 +225,13 @@ where
         self.components.find_roots();
     }
 
+    //mp bounds
+    /// Calculate the bounds of the object in object space, from all
+    /// its components; this requires the object to have been analyzed
+    pub fn bounds(&self) -> Option<Bounds> {
+        RenderRecipe::from_component_hierarchy(&self.components).calc_bounds(&self.vertices)
+    }
+
     //dp into_instantiable
     /// Deconstruct the object into an [Instantiable] for the
     /// renderable. This should be invoked after analysis and clients
@@ -1,5 +1,5 @@
 //a Imports
-use crate::{PrimitiveType, ShortIndex};
+use crate::{Bounds, PrimitiveType, Renderable, ShortIndex, Vertices};
 
 //a Primitive
 //tp Primitive
@@ -113,5 +113,19 @@ impl Primitive {
         self.byte_offset
     }
 
+    //mp calc_bounds
+    /// Calculate the bounds of the primitive, given the vertices of
+    /// the [crate::Object]
+    ///
+    /// If the [Vertices] have bounds set then those are used;
+    /// otherwise the positions used by the primitive are read
+    pub fn calc_bounds<R: Renderable>(&self, vertices: &[&Vertices<R>]) -> Option<Bounds> {
+        let vertices_index: Option<usize> = self.vertices_index.into();
+        let vertices = vertices.get(vertices_index?)?;
+        vertices
+            .bounds()
+            .or_else(|| vertices.calc_bounds(self.byte_offset, self.index_count))
+    }
+
     //zz All done
 }
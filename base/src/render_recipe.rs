@@ -1,11 +1,13 @@
 //a Imports
+use std::ops::Range;
+
 use crate::hierarchy;
 use hierarchy::{Hierarchy, NodeEnumOp};
 
 use geo_nd::matrix;
 
 use crate::Mat4;
-use crate::{Component, Primitive};
+use crate::{Bounds, Component, Primitive, Renderable, Vertices};
 
 //a RenderRecipe
 //tp RenderRecipe
@@ -14,6 +16,15 @@ use crate::{Component, Primitive};
 /// Created from a hierarchy of components, this is an array of
 /// transformation matrices, an array of [Primitive]s, and an array of
 /// pairs (matrix index, primitive index) of what needs to be drawn
+///
+/// Primitives of components that are attached to a bone also record
+/// the bone, so that they can follow the animated pose of the bone
+///
+/// Primitives of components that have a skin of their own record the
+/// range of the bone matrices that their joints index
+///
+/// Primitives of components that have instance matrices record the
+/// index of those matrices, and are drawn once per instance
 #[derive(Debug)]
 pub struct RenderRecipe {
     /// Matrices to use (the first is the identity matrix)
@@ -22,6 +33,19 @@ pub struct RenderRecipe {
     pub primitives: Vec<Primitive>,
     /// Draw requirements - matrix index for the associated primitive index
     pub matrix_for_primitives: Vec<usize>,
+    /// The bone matrix index for each primitive that is attached to a
+    /// bone of the skeleton
+    pub bone_for_primitives: Vec<Option<usize>>,
+    /// The range of bone matrices of the skin of each primitive that
+    /// has a skin of its own
+    pub skin_for_primitives: Vec<Option<Range<usize>>>,
+    /// The instance matrices of the instanced components
+    pub instance_matrices: Vec<Vec<Mat4>>,
+    /// The index into 'instance_matrices' for each primitive that is
+    /// instanced
+    pub instances_for_primitives: Vec<Option<usize>>,
+    /// Bounds of each primitive (in its own space), once calculated
+    pub primitive_bounds: Vec<Option<Bounds>>,
 }
 
 //ip Default RenderRecipe
@@ -39,10 +63,20 @@ impl RenderRecipe {
         let matrices = Vec::new();
         let primitives = Vec::new();
         let matrix_for_primitives = Vec::new();
+        let bone_for_primitives = Vec::new();
+        let skin_for_primitives = Vec::new();
+        let instance_matrices = Vec::new();
+        let instances_for_primitives = Vec::new();
+        let primitive_bounds = Vec::new();
         Self {
             matrices,
             primitives,
             matrix_for_primitives,
+            bone_for_primitives,
+            skin_for_primitives,
+            instance_matrices,
+            instances_for_primitives,
+            primitive_bounds,
         }
     }
 
@@ -50,20 +84,25 @@ impl RenderRecipe {
     /// Build a RenderRecipe from a [Hierarchy] of [Component]
     ///
     /// It requires the hierarchy to have had 'find_roots' executed prior
+    ///
+    /// A component attached to a bone attaches all of its children to
+    /// that bone too, unless they are attached to another bone
     pub fn from_component_hierarchy(components: &Hierarchy<Component>) -> Self {
         let mut recipe = Self::new();
 
         // Create matrices for all meshes in the component,
-        // and enumerate them as (mesh index, matrix index) in `meshes`
+        // and enumerate them as (mesh index, matrix index, bone) in `meshes`
         recipe.matrices.push(matrix::identity4());
         let mut meshes = Vec::new();
         for root in components.borrow_roots() {
             let mut trans_index = 0;
+            let mut bone = None;
             let mut mesh_stack = Vec::new();
             for op in components.iter_from(*root) {
                 match op {
                     NodeEnumOp::Push((n, comp), _has_children) => {
-                        mesh_stack.push(trans_index);
+                        mesh_stack.push((trans_index, bone));
+                        bone = comp.bone.or(bone);
                         if let Some(transformation) = comp.transformation {
                             let transformation = matrix::multiply4(
                                 &recipe.matrices[trans_index],
@@ -72,23 +111,122 @@ impl RenderRecipe {
                             trans_index = recipe.matrices.len();
                             recipe.matrices.push(transformation);
                         } // else keep same trans_index as its parent
-                        meshes.push((n, trans_index));
+                        meshes.push((n, trans_index, bone));
                     }
                     NodeEnumOp::Pop(_, _) => {
-                        trans_index = mesh_stack.pop().unwrap();
+                        (trans_index, bone) = mesh_stack.pop().unwrap();
                     }
                 }
             }
         }
 
         // Copy out the mesh primitives paired with the matrix index
-        for (n, trans_index) in meshes {
-            for p in &components.borrow_node(n).mesh.primitives {
+        for (n, trans_index, bone) in meshes {
+            let component = components.borrow_node(n);
+            let instances = {
+                if component.instance_matrices.is_empty() {
+                    None
+                } else {
+                    recipe
+                        .instance_matrices
+                        .push(component.instance_matrices.clone());
+                    Some(recipe.instance_matrices.len() - 1)
+                }
+            };
+            for p in &component.mesh.primitives {
                 recipe.primitives.push(p.clone());
                 recipe.matrix_for_primitives.push(trans_index);
+                recipe.bone_for_primitives.push(bone);
+                recipe.skin_for_primitives.push(component.skin.clone());
+                recipe.instances_for_primitives.push(instances);
+                recipe.primitive_bounds.push(None);
             }
         }
 
         recipe
     }
+
+    //mp primitive_matrix
+    /// Get the matrix to draw a primitive with, given the bone
+    /// matrices of an instance (see [crate::Instance::bone_matrices])
+    ///
+    /// For a primitive attached to a bone this is the animated matrix
+    /// of the bone applied to the rest pose matrix of the primitive;
+    /// if there is no such bone matrix then the primitive is drawn at
+    /// its rest pose
+    pub fn primitive_matrix(&self, primitive: usize, bone_matrices: &[Mat4]) -> Mat4 {
+        let m = &self.matrices[self.matrix_for_primitives[primitive]];
+        match self.bone_for_primitives[primitive].and_then(|b| bone_matrices.get(b)) {
+            Some(bone_matrix) => matrix::multiply4(m, bone_matrix),
+            None => *m,
+        }
+    }
+
+    //mp primitive_skin_matrices
+    /// Get the bone matrices that the joints of a skinned primitive
+    /// index, given the bone matrices of an instance (see
+    /// [crate::Instance::bone_matrices])
+    ///
+    /// This is the range of the bone matrices of the skin of the
+    /// primitive, if it has one, else all of the bone matrices
+    pub fn primitive_skin_matrices<'m>(
+        &self,
+        primitive: usize,
+        bone_matrices: &'m [Mat4],
+    ) -> &'m [Mat4] {
+        match &self.skin_for_primitives[primitive] {
+            Some(skin) => bone_matrices.get(skin.clone()).unwrap_or(&[]),
+            None => bone_matrices,
+        }
+    }
+
+    //mp primitive_instances
+    /// Get the instance matrices of a primitive, which are applied
+    /// before the matrix of the primitive; this is empty if the
+    /// primitive is drawn just once
+    pub fn primitive_instances(&self, primitive: usize) -> &[Mat4] {
+        match self.instances_for_primitives[primitive] {
+            Some(i) => &self.instance_matrices[i],
+            None => &[],
+        }
+    }
+
+    //mp calc_bounds
+    /// Calculate the bounds of the primitives given the vertices of
+    /// the object, and return the bounds of them all after their
+    /// matrices are applied (i.e. in object space)
+    pub fn calc_bounds<R: Renderable>(&mut self, vertices: &[&Vertices<R>]) -> Option<Bounds> {
+        self.primitive_bounds = self
+            .primitives
+            .iter()
+            .map(|p| p.calc_bounds(vertices))
+            .collect();
+        self.bounds()
+    }
+
+    //ap bounds
+    /// Get the bounds of all the primitives in object space, using
+    /// the bounds calculated by [RenderRecipe::calc_bounds]
+    ///
+    /// The bounds of an instanced primitive cover all of its instances
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.primitive_bounds
+            .iter()
+            .enumerate()
+            .filter_map(|(i, b)| b.map(|b| (i, b)))
+            .flat_map(|(i, b)| {
+                let m = &self.matrices[self.matrix_for_primitives[i]];
+                let instances = self.primitive_instances(i);
+                let bounds: Vec<Bounds> = if instances.is_empty() {
+                    vec![b.transformed(m)]
+                } else {
+                    instances
+                        .iter()
+                        .map(|im| b.transformed(&matrix::multiply4(im, m)))
+                        .collect()
+                };
+                bounds
+            })
+            .reduce(|a, b| a.union(&b))
+    }
 }
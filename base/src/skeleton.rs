@@ -59,6 +59,25 @@ impl Skeleton {
         self.skeleton.add_node(bone)
     }
 
+    //mp set_root_ptm
+    /// Set the parent-to-mesh matrix of a bone (by its bone reference
+    /// index) that is used if it is a root bone; this places the root
+    /// in mesh space, leaving its transformation local to its parent
+    /// so that it can be animated
+    pub fn set_root_ptm(&mut self, bone: usize, root_ptm: Mat4) {
+        let (_, bones) = self.skeleton.borrow_mut();
+        bones[bone].data.set_root_ptm(root_ptm);
+    }
+
+    //mp set_bind_mtb
+    /// Set the mesh-to-bone matrix of the bind pose of a bone (by its
+    /// bone reference index), for skinned meshes whose bind pose is
+    /// not the rest pose of the [Skeleton]
+    pub fn set_bind_mtb(&mut self, bone: usize, bind_mtb: Mat4) {
+        let (_, bones) = self.skeleton.borrow_mut();
+        bones[bone].data.set_bind_mtb(Some(bind_mtb));
+    }
+
     //mp relate
     /// Relate a parent bone to a child bone (by bone reference indices)
     pub fn relate(&mut self, parent: usize, child: usize) {
@@ -1,4 +1,5 @@
 //a Imports
+use geo_nd::matrix;
 use indent_display::{IndentedDisplay, IndentedOptions, Indenter};
 
 use crate::hierarchy;
@@ -28,7 +29,8 @@ impl<'a> SkeletonPose<'a> {
     //fp new
     /// Create a new [SkeletonPose] for a [Skeleton]
     ///
-    /// The [Skeleton] must have been resolved
+    /// The [Skeleton] must have been resolved; the bone matrices are
+    /// derived for its rest pose
     pub fn new(skeleton: &'a Skeleton) -> Self {
         let mut poses = Vec::new();
         for b in skeleton.skeleton.borrow_elements().iter() {
@@ -36,38 +38,57 @@ impl<'a> SkeletonPose<'a> {
         }
         let mut data = Vec::new();
         for _ in 0..skeleton.max_index {
-            data.push([0.; 16]);
+            data.push(matrix::identity4());
         }
         let last_updated = 0;
-        Self {
+        let mut pose = Self {
             skeleton,
             poses,
             data,
             last_updated,
-        }
+        };
+        pose.derive_bone_matrices();
+        pose
+    }
+
+    //ap bone_pose_mut
+    /// Borrow the [BonePose] of a bone (by its bone reference index)
+    /// mutably, if it exists
+    pub fn bone_pose_mut(&mut self, bone: usize) -> Option<&mut BonePose<'a>> {
+        self.poses.get_mut(bone)
+    }
+
+    //ap bone_matrices
+    /// Borrow the mesh-to-animated-model-space matrices, indexed by
+    /// the matrix index of each bone
+    ///
+    /// These are for the rest pose until the pose has been updated
+    pub fn bone_matrices(&self) -> &[Mat4] {
+        &self.data
     }
 
     //fp derive_animation
     /// Derive the animation for the current poses of the [SkeletonPose]
     ///
-    /// This traverses the hierarchy as required
+    /// This traverses the hierarchy as required, keeping a stack of
+    /// the animated bone-to-mesh matrices of the parents of each bone
     pub fn derive_animation(&mut self) {
-        let mut mat_depth = 0;
+        let mut stack: Vec<Mat4> = Vec::new();
         for (_, recipe) in &self.skeleton.roots {
             for op in recipe.borrow_ops() {
                 match op {
                     hierarchy::NodeEnumOp::Push(n, _) => {
-                        if mat_depth == 0 {
-                            self.data[mat_depth] =
-                                *self.poses[*n].derive_animation(true, &self.data[mat_depth]);
-                        } else {
-                            self.data[mat_depth + 1] =
-                                *self.poses[*n].derive_animation(false, &self.data[mat_depth]);
-                        }
-                        mat_depth += 1;
+                        let btm = {
+                            if let Some(parent) = stack.last() {
+                                *self.poses[*n].derive_animation(false, parent)
+                            } else {
+                                *self.poses[*n].derive_animation(true, &[0.; 16])
+                            }
+                        };
+                        stack.push(btm);
                     }
                     _ => {
-                        mat_depth -= 1;
+                        stack.pop();
                     }
                 }
             }
@@ -80,12 +101,19 @@ impl<'a> SkeletonPose<'a> {
     pub fn update(&mut self, tick: usize) {
         if tick != self.last_updated {
             self.last_updated = tick;
-            self.derive_animation();
-            let bones = self.skeleton.skeleton.borrow_elements();
-            for (i, bone) in bones.iter().enumerate().take(self.poses.len()) {
-                let matrix_index = bone.data.matrix_index;
-                self.data[matrix_index] = *self.poses[i].borrow_animated_mtm();
-            }
+            self.derive_bone_matrices();
+        }
+    }
+
+    //mi derive_bone_matrices
+    /// Derive the animation for the current poses, and copy the
+    /// animated matrices of the bones to their matrix indices
+    fn derive_bone_matrices(&mut self) {
+        self.derive_animation();
+        let bones = self.skeleton.skeleton.borrow_elements();
+        for (i, bone) in bones.iter().enumerate().take(self.poses.len()) {
+            let matrix_index = bone.data.matrix_index;
+            self.data[matrix_index] = *self.poses[i].borrow_animated_mtm();
         }
     }
 }
@@ -3,6 +3,81 @@ use std::cell::{Ref, RefCell};
 
 use crate::{BufferElementType, Renderable};
 
+//a TextureWrap, TextureFilter, TextureSampler
+//tp TextureWrap
+/// How texture coordinates outside the range 0 to 1 are mapped onto
+/// a [Texture]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TextureWrap {
+    /// The texture repeats
+    #[default]
+    Repeat,
+    /// The texture repeats, mirrored on every other repetition
+    MirroredRepeat,
+    /// The coordinates are clamped to the edge of the texture
+    ClampToEdge,
+}
+
+//tp TextureFilter
+/// The filtering used when sampling a [Texture]
+///
+/// The mipmap filters are only valid for minification; they require
+/// the client to generate mipmaps for the texture
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TextureFilter {
+    /// Use the nearest texel
+    Nearest,
+    /// Linearly interpolate the nearest texels
+    #[default]
+    Linear,
+    /// Use the nearest texel of the nearest mipmap
+    NearestMipmapNearest,
+    /// Linearly interpolate the texels of the nearest mipmap
+    LinearMipmapNearest,
+    /// Use the nearest texel of the two nearest mipmaps, and
+    /// interpolate between them
+    NearestMipmapLinear,
+    /// Linearly interpolate the texels of the two nearest mipmaps,
+    /// and interpolate between them
+    LinearMipmapLinear,
+}
+
+//ip TextureFilter
+impl TextureFilter {
+    //ap uses_mipmaps
+    /// Return true if the filter requires the texture to have mipmaps
+    pub fn uses_mipmaps(&self) -> bool {
+        !matches!(self, Self::Nearest | Self::Linear)
+    }
+
+    //ap without_mipmaps
+    /// Get the filter without its mipmap filtering, as required for
+    /// magnification
+    pub fn without_mipmaps(&self) -> Self {
+        match self {
+            Self::Nearest | Self::NearestMipmapNearest | Self::NearestMipmapLinear => Self::Nearest,
+            _ => Self::Linear,
+        }
+    }
+}
+
+//tp TextureSampler
+/// The wrapping and filtering to use when sampling a [Texture]
+///
+/// The default is to repeat and to filter linearly, without mipmaps
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TextureSampler {
+    /// Wrapping for the S (U) coordinate
+    pub wrap_s: TextureWrap,
+    /// Wrapping for the T (V) coordinate
+    pub wrap_t: TextureWrap,
+    /// Filter used for magnification; this should not be a mipmap
+    /// filter
+    pub mag_filter: TextureFilter,
+    /// Filter used for minification
+    pub min_filter: TextureFilter,
+}
+
 //a Texture
 //tp Texture
 /// A texture is managed by the library as a byte slice which has up
@@ -35,6 +110,8 @@ pub struct Texture<'texture, R: Renderable> {
     ///
     /// For most image textures this is Int8
     pub ele_type: BufferElementType,
+    /// The wrapping and filtering to use for the texture
+    pub sampler: TextureSampler,
     /// Client handle/value
     rc_client: RefCell<R::Texture>,
 }
@@ -47,8 +124,8 @@ where
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         writeln!(
             fmt,
-            "Texture {{dims:{:?}, {:?}*{}, client:{:?}}}",
-            self.dims, self.ele_type, self.elements_per_data, self.rc_client
+            "Texture {{dims:{:?}, {:?}*{}, {:?}, client:{:?}}}",
+            self.dims, self.ele_type, self.elements_per_data, self.sampler, self.rc_client
         )?;
         Ok(())
     }
@@ -77,15 +154,24 @@ impl<'texture, R: Renderable> Texture<'texture, R> {
         elements_per_data: u32,
     ) -> Self {
         let rc_client = Default::default();
+        let sampler = TextureSampler::default();
         Self {
             data,
             dims,
             ele_type,
             elements_per_data,
+            sampler,
             rc_client,
         }
     }
 
+    //cp with_sampler
+    /// Set the wrapping and filtering of the texture
+    pub fn with_sampler(mut self, sampler: TextureSampler) -> Self {
+        self.sampler = sampler;
+        self
+    }
+
     //ap dims
     /// Get the dimensions of the texture
     pub fn dims(&self) -> &(usize, usize, usize) {
@@ -104,6 +190,18 @@ impl<'texture, R: Renderable> Texture<'texture, R> {
         (self.elements_per_data, self.ele_type)
     }
 
+    //ap sampler
+    /// Get the wrapping and filtering of the texture
+    pub fn sampler(&self) -> &TextureSampler {
+        &self.sampler
+    }
+
+    //mp set_sampler
+    /// Set the wrapping and filtering of the texture
+    pub fn set_sampler(&mut self, sampler: TextureSampler) {
+        self.sampler = sampler;
+    }
+
     //mp create_client
     /// Create the client texture
     pub fn create_client(&self, renderer: &mut R) {
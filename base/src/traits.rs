@@ -1,6 +1,6 @@
 //a Imports
 use crate::{BufferData, BufferDataAccessor, BufferIndexAccessor, Texture, Vertices};
-use crate::{BufferDescriptor, MaterialAspect, MaterialBaseData, ShortIndex};
+use crate::{BufferDescriptor, Mat3, MaterialAspect, MaterialBaseData, ShortIndex};
 
 //a Client traits
 //tt BufferClient
@@ -191,4 +191,41 @@ pub trait Material: std::fmt::Debug {
     fn texture(&self, _aspect: MaterialAspect) -> ShortIndex {
         ShortIndex::none()
     }
+    /// Get the texture coordinate set (0 for TexCoords0, etc) used by
+    /// the texture for a specific aspect
+    fn tex_coord(&self, _aspect: MaterialAspect) -> usize {
+        0
+    }
+    /// Get the scale applied to the X and Y of the normal texture
+    fn normal_scale(&self) -> f32 {
+        1.0
+    }
+    /// Get the strength of the occlusion texture - 0 for no
+    /// occlusion, 1 for full occlusion
+    fn occlusion_strength(&self) -> f32 {
+        1.0
+    }
+    /// Get the transformation of the texture coordinates used by the
+    /// texture for a specific aspect, as a column-major 3x3 matrix to
+    /// apply to (u, v, 1)
+    fn uv_transform(&self, _aspect: MaterialAspect) -> Mat3 {
+        [1., 0., 0., 0., 1., 0., 0., 0., 1.]
+    }
+    /// Get the multiplier for the emissive color
+    fn emissive_strength(&self) -> f32 {
+        1.0
+    }
+    /// Get the intensity and roughness of the clearcoat layer; an
+    /// intensity of 0 is no clearcoat
+    fn clearcoat(&self) -> (f32, f32) {
+        (0., 0.)
+    }
+    /// Get the fraction of light transmitted through the surface
+    fn transmission(&self) -> f32 {
+        0.
+    }
+    /// Get the index of refraction
+    fn ior(&self) -> f32 {
+        1.5
+    }
 }
@@ -63,13 +63,13 @@ impl Transformation {
 
     //ap scale
     /// Get the scale
-    pub fn scale(&mut self) -> Vec3 {
+    pub fn scale(&self) -> Vec3 {
         self.scale
     }
 
     //ap translation
-    /// Set the translation of a transformation
-    pub fn translation(&mut self) -> Vec3 {
+    /// Get the translation of a transformation
+    pub fn translation(&self) -> Vec3 {
         self.translation
     }
 
@@ -106,13 +106,19 @@ impl Transformation {
     }
 
     //mp combine
-    /// Combine two transformations into this
+    /// Combine two transformations into this, such that 'other' is
+    /// applied first and then 'base'
     ///
-    /// To operate correctly the scales must be
+    /// To operate correctly the scale of 'base' must be uniform
     pub fn combine(&mut self, base: &Self, other: &Self) {
         self.rotation = quat::multiply(&base.rotation, &other.rotation);
-        self.translation = base.translation;
-        self.translation = vector::add(self.translation, &other.translation, 1.);
+        let translation = [
+            other.translation[0] * base.scale[0],
+            other.translation[1] * base.scale[1],
+            other.translation[2] * base.scale[2],
+        ];
+        let translation = quat::apply3(&base.rotation, &translation);
+        self.translation = vector::add(base.translation, &translation, 1.);
         for i in 0..3 {
             self.scale[i] = base.scale[i] * other.scale[i];
         }
@@ -165,9 +171,11 @@ impl Transformation {
             m[i + 4] *= sc;
             m[i + 8] *= sc;
         }
-        m[12] -= self.translation[0];
-        m[13] -= self.translation[1];
-        m[14] -= self.translation[2];
+        for i in 0..3 {
+            m[12 + i] = -(m[i] * self.translation[0]
+                + m[i + 4] * self.translation[1]
+                + m[i + 8] * self.translation[2]);
+        }
         m
     }
 
@@ -196,11 +204,13 @@ impl Transformation {
 
     //mp interpolate
     /// Set this transformation to be an interpolation between two others
+    ///
+    /// A 't' of 0 yields 'in0', and a 't' of 1 yields 'in1'
     pub fn interpolate(&mut self, t: f32, in0: &Self, in1: &Self) {
         let tn = 1.0 - t;
         for i in 0..3 {
-            self.translation[i] = t * in0.translation[i] + tn * in1.translation[i];
-            self.scale[i] = t * in0.scale[i] + tn * in1.scale[i];
+            self.translation[i] = tn * in0.translation[i] + t * in1.translation[i];
+            self.scale[i] = tn * in0.scale[i] + t * in1.scale[i];
         }
         self.rotation = quat::nlerp(t, &in0.rotation, &in1.rotation);
     }
@@ -104,6 +104,56 @@ impl BufferElementType {
             UInt32 => 4,
         }
     }
+
+    /// Return true if the element type is a floating point type
+    pub fn is_float(self) -> bool {
+        matches!(self, Self::Float32 | Self::Float16)
+    }
+
+    /// Read an element of this type from the start of little-endian
+    /// data as an f32
+    ///
+    /// If normalized then integer types are mapped to 0.0 to 1.0 (or
+    /// -1.0 to 1.0 if signed), otherwise they are converted to the
+    /// nearest float
+    pub fn read_f32(self, data: &[u8], normalized: bool) -> f32 {
+        use BufferElementType::*;
+        let (value, scale) = match self {
+            Float32 => return f32::from_le_bytes([data[0], data[1], data[2], data[3]]),
+            Float16 => return f16_bits_to_f32(u16::from_le_bytes([data[0], data[1]])),
+            SInt8 => (data[0] as i8 as f32, 127.0),
+            UInt8 => (data[0] as f32, 255.0),
+            SInt16 => (i16::from_le_bytes([data[0], data[1]]) as f32, 32767.0),
+            UInt16 => (u16::from_le_bytes([data[0], data[1]]) as f32, 65535.0),
+            SInt32 => (
+                i32::from_le_bytes([data[0], data[1], data[2], data[3]]) as f32,
+                2147483647.0,
+            ),
+            UInt32 => (
+                u32::from_le_bytes([data[0], data[1], data[2], data[3]]) as f32,
+                4294967295.0,
+            ),
+        };
+        if normalized {
+            (value / scale).max(-1.0)
+        } else {
+            value
+        }
+    }
+}
+
+//fi f16_bits_to_f32
+/// Convert the bits of an IEEE half-precision float to an f32
+fn f16_bits_to_f32(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exp = ((bits >> 10) & 0x1f) as i32;
+    let mant = (bits & 0x3ff) as f32;
+    match exp {
+        0 => sign * mant * 2.0_f32.powi(-24),
+        31 if mant == 0. => sign * f32::INFINITY,
+        31 => f32::NAN,
+        _ => sign * (1.0 + mant / 1024.0) * 2.0_f32.powi(exp - 15),
+    }
 }
 
 //tp VertexDesc
@@ -127,11 +177,19 @@ pub struct VertexDesc {
 
     /// Type of each element, e.g. Float32, SInt16, UInt8
     ele_type: BufferElementType,
+
+    /// True if integer elements are normalized to 0.0 to 1.0 (or -1.0
+    /// to 1.0 if signed) when read as floats
+    #[cfg_attr(feature = "serde", serde(default))]
+    normalized: bool,
 }
 
 //ip Display for VertexDesc
 impl std::fmt::Debug for VertexDesc {
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        if self.normalized {
+            write!(fmt, "Normalized")?;
+        }
         if self.dims[0] == 0 {
             write!(
                 fmt,
@@ -165,6 +223,7 @@ impl VertexDesc {
             byte_offset,
             dims: [0, 0],
             ele_type,
+            normalized: false,
         }
     }
 
@@ -177,6 +236,7 @@ impl VertexDesc {
             byte_offset,
             dims: [len, 0],
             ele_type,
+            normalized: false,
         }
     }
 
@@ -194,6 +254,7 @@ impl VertexDesc {
             byte_offset,
             dims,
             ele_type,
+            normalized: false,
         }
     }
 
@@ -211,6 +272,7 @@ impl VertexDesc {
             byte_offset,
             dims,
             ele_type,
+            normalized: false,
         }
     }
 
@@ -242,6 +304,20 @@ impl VertexDesc {
         self.ele_type
     }
 
+    //ap is_normalized
+    /// Return true if integer elements of the field are normalized
+    #[inline]
+    pub fn is_normalized(&self) -> bool {
+        self.normalized
+    }
+
+    //mp set_normalized
+    /// Set whether integer elements of the field are normalized
+    /// (ignored for float element types)
+    pub fn set_normalized(&mut self, normalized: bool) {
+        self.normalized = normalized && !self.ele_type.is_float();
+    }
+
     //ap count
     /// Get the count of the number of elements in the field
     #[inline]
@@ -289,6 +365,15 @@ pub enum VertexAttr {
     TexCoords1,
     /// Texture coordinates (2 x f32)
     TexCoords2,
+    /// Texture coordinates (2 x f32)
+    TexCoords3,
+    /// A second color at the point (4xf32)
+    Color1,
+    /// A second set of joints (n x int), for more than four
+    /// influences per vertex
+    Joints1,
+    /// Weights to apply to each bone\[joints1\[i\]\]
+    Weights1,
 }
 
 //tp PrimitiveType
@@ -329,6 +414,64 @@ pub enum MaterialAspect {
     Occlusion,
     /// Emission (as f32)
     Emission,
+    /// Clearcoat intensity (as f32, from the red channel)
+    Clearcoat,
+    /// Clearcoat roughness (as f32, from the green channel)
+    ClearcoatRoughness,
+    /// Normal of the clearcoat layer
+    ClearcoatNormal,
+    /// Transmission (as f32, from the red channel)
+    Transmission,
+}
+
+//ip MaterialAspect
+impl MaterialAspect {
+    /// The number of material aspects
+    pub const NUM: usize = 9;
+
+    /// All of the material aspects, in order
+    pub const ALL: [Self; Self::NUM] = [
+        Self::Color,
+        Self::Normal,
+        Self::MetallicRoughness,
+        Self::Occlusion,
+        Self::Emission,
+        Self::Clearcoat,
+        Self::ClearcoatRoughness,
+        Self::ClearcoatNormal,
+        Self::Transmission,
+    ];
+}
+
+//tp AlphaMode
+/// How the alpha of a material is used when rendering
+///
+/// This is set to match the GLTF
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum AlphaMode {
+    /// Alpha is ignored; the material is fully opaque
+    #[default]
+    Opaque,
+    /// The material is opaque where alpha is at least the alpha
+    /// cutoff, and fully transparent elsewhere
+    Mask,
+    /// Alpha is used to blend with the background
+    Blend,
+}
+
+//ip AlphaMode
+impl AlphaMode {
+    //fp of_u32
+    /// Get the [AlphaMode] from its numeric value, as packed in a
+    /// material; values beyond Blend are Opaque
+    pub fn of_u32(n: u32) -> Self {
+        match n {
+            1 => Self::Mask,
+            2 => Self::Blend,
+            _ => Self::Opaque,
+        }
+    }
 }
 
 //tp ShortIndex
@@ -1,8 +1,8 @@
 //a Imports
 use std::cell::{Ref, RefCell};
 
-use crate::{BufferDataAccessor, BufferIndexAccessor};
-use crate::{Renderable, VertexAttr};
+use crate::{Bounds, MorphTarget, MorphedAttr, Renderable, VertexAttr};
+use crate::{BufferDataAccessor, BufferElementType, BufferIndexAccessor};
 
 //a Vertices
 //tp Vertices
@@ -39,6 +39,16 @@ pub struct Vertices<'vertices, R: Renderable> {
     /// Attributes of the vertices, which must include position, sorted by VertexAttr
     attrs: Vec<&'vertices BufferDataAccessor<'vertices, R>>,
 
+    /// Bounds of all the positions, if known (e.g. from the min and
+    /// max of a Gltf accessor)
+    bounds: Option<Bounds>,
+
+    /// Morph targets of the vertices, if any
+    morph_targets: Vec<MorphTarget<'vertices, R>>,
+
+    /// Default weights of the morph targets
+    morph_weights: Vec<f32>,
+
     /// Client handle for this set of Vertices, updated when 'create_client' is invoked
     rc_client: RefCell<R::Vertices>,
 }
@@ -71,10 +81,26 @@ impl<'vertices, R: Renderable> Vertices<'vertices, R> {
         Self {
             indices,
             attrs,
+            bounds: None,
+            morph_targets: Vec::new(),
+            morph_weights: Vec::new(),
             rc_client,
         }
     }
 
+    //mp set_bounds
+    /// Set the bounds of all the positions of the vertices, if they
+    /// are known without reading the data
+    pub fn set_bounds(&mut self, bounds: Option<Bounds>) {
+        self.bounds = bounds;
+    }
+
+    //ap bounds
+    /// Get the bounds of all the positions of the vertices, if set
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.bounds
+    }
+
     //mp add_attr
     /// Add a [BufferAccessor] for a particular [VertexAttr]
     ///
@@ -93,6 +119,48 @@ impl<'vertices, R: Renderable> Vertices<'vertices, R> {
         }
     }
 
+    //mp add_morph_target
+    /// Add a [MorphTarget] to the vertices, with a default weight of 0
+    pub fn add_morph_target(&mut self, morph_target: MorphTarget<'vertices, R>) {
+        self.morph_targets.push(morph_target);
+        self.morph_weights.push(0.);
+    }
+
+    //ap morph_targets
+    /// Get the morph targets of the vertices
+    pub fn morph_targets(&self) -> &[MorphTarget<'vertices, R>] {
+        &self.morph_targets
+    }
+
+    //mp set_morph_weights
+    /// Set the default weights of the morph targets; weights beyond
+    /// the number of morph targets are ignored
+    pub fn set_morph_weights(&mut self, weights: &[f32]) {
+        for (w, sw) in self.morph_weights.iter_mut().zip(weights.iter()) {
+            *w = *sw;
+        }
+    }
+
+    //ap morph_weights
+    /// Get the default weights of the morph targets, one per target
+    pub fn morph_weights(&self) -> &[f32] {
+        &self.morph_weights
+    }
+
+    //mp morphed_attrs
+    /// Get a [MorphedAttr] for each of the position, normal and
+    /// tangent attributes that the morph targets displace
+    pub fn morphed_attrs(&self) -> Vec<MorphedAttr> {
+        [
+            VertexAttr::Position,
+            VertexAttr::Normal,
+            VertexAttr::Tangent,
+        ]
+        .into_iter()
+        .filter_map(|va| MorphedAttr::of_vertices(self, va))
+        .collect()
+    }
+
     //mp borrow_indices
     /// Borrow the indices [BufferAccessor]
     pub fn borrow_indices<'a>(&'a self) -> Option<&'a BufferIndexAccessor<'vertices, R>> {
@@ -117,6 +185,51 @@ impl<'vertices, R: Renderable> Vertices<'vertices, R> {
         self.attrs.iter()
     }
 
+    //mp calc_bounds
+    /// Calculate the bounds of the positions used by 'count' indices
+    /// starting at a byte offset into the indices; if there are no
+    /// indices then this is the bounds of 'count' vertices starting
+    /// at vertex 'byte_offset'
+    ///
+    /// Returns None if there are no positions used
+    pub fn calc_bounds(&self, byte_offset: u32, count: u32) -> Option<Bounds> {
+        let position = self.borrow_attr(VertexAttr::Position)?;
+        let vertex_desc = position.vertex_desc();
+        if vertex_desc.count() < 3 {
+            return None;
+        }
+        let data = position.desc().as_ref();
+        let stride = position.desc().stride() as usize;
+        let ele_size = vertex_desc.ele_type().byte_length() as usize;
+        let read_position = |i: usize| {
+            let start = i * stride + vertex_desc.byte_offset() as usize;
+            let d = data.get(start..start + 3 * ele_size)?;
+            let mut p = [0.; 3];
+            for (n, pn) in p.iter_mut().enumerate() {
+                *pn = vertex_desc
+                    .ele_type()
+                    .read_f32(&d[n * ele_size..], vertex_desc.is_normalized());
+            }
+            Some(p)
+        };
+        let (byte_offset, count) = (byte_offset as usize, count as usize);
+        if let Some(indices) = self.indices {
+            let index_data = indices.as_ref();
+            let index_size = indices.ele_type().byte_length() as usize;
+            let index_data = index_data.get(byte_offset..byte_offset + count * index_size)?;
+            Bounds::of_points(index_data.chunks_exact(index_size).filter_map(|b| {
+                let i = match indices.ele_type() {
+                    BufferElementType::UInt8 => b[0] as usize,
+                    BufferElementType::UInt16 => u16::from_le_bytes([b[0], b[1]]) as usize,
+                    _ => u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize,
+                };
+                read_position(i)
+            }))
+        } else {
+            Bounds::of_points((byte_offset..byte_offset + count).filter_map(read_position))
+        }
+    }
+
     //mp create_client
     /// Create the render buffer required by the BufferAccessor
     pub fn create_client(&self, renderer: &mut R) {
@@ -0,0 +1,106 @@
+use mod3d_base::{AnimationChannel, AnimationClip, AnimationProperty, Interpolation};
+use mod3d_base::{Skeleton, SkeletonPose, Transformation};
+
+fn assert_near(a: &[f32], b: &[f32]) {
+    for (x, y) in a.iter().zip(b.iter()) {
+        assert!((x - y).abs() < 1e-5, "{a:?} != {b:?}");
+    }
+}
+
+#[test]
+fn test_step_linear() {
+    let times = vec![0., 1., 3.];
+    let values = vec![0., 0., 0., 2., 4., 6., 4., 8., 12.];
+    let step = AnimationChannel::new(
+        0,
+        AnimationProperty::Translation,
+        Interpolation::Step,
+        times.clone(),
+        values.clone(),
+    );
+    let linear = AnimationChannel::new(
+        0,
+        AnimationProperty::Translation,
+        Interpolation::Linear,
+        times,
+        values,
+    );
+    let mut v = [0.; 3];
+    step.sample_into(0.5, &mut v);
+    assert_near(&v, &[0., 0., 0.]);
+    step.sample_into(2.0, &mut v);
+    assert_near(&v, &[2., 4., 6.]);
+    linear.sample_into(0.5, &mut v);
+    assert_near(&v, &[1., 2., 3.]);
+    linear.sample_into(2.0, &mut v);
+    assert_near(&v, &[3., 6., 9.]);
+    linear.sample_into(-1.0, &mut v);
+    assert_near(&v, &[0., 0., 0.]);
+    linear.sample_into(10.0, &mut v);
+    assert_near(&v, &[4., 8., 12.]);
+}
+
+#[test]
+fn test_rotation_slerp() {
+    let s = std::f32::consts::FRAC_1_SQRT_2;
+    // Identity to 90 degrees about Z
+    let channel = AnimationChannel::new(
+        0,
+        AnimationProperty::Rotation,
+        Interpolation::Linear,
+        vec![0., 1.],
+        vec![0., 0., 0., 1., 0., 0., s, s],
+    );
+    let mut q = [0.; 4];
+    channel.sample_into(0.5, &mut q);
+    let a = std::f32::consts::PI / 8.0;
+    assert_near(&q, &[0., 0., a.sin(), a.cos()]);
+}
+
+#[test]
+fn test_cubic_spline() {
+    // Scalar weights with zero tangents; at the midpoint the Hermite
+    // spline is the average of the values
+    let channel = AnimationChannel::new(
+        0,
+        AnimationProperty::Weights,
+        Interpolation::CubicSpline,
+        vec![0., 2.],
+        vec![0., 1., 0., 0., 3., 0.],
+    );
+    assert_eq!(channel.ele_size(), 1);
+    let mut w = [0.];
+    channel.sample_into(1.0, &mut w);
+    assert_near(&w, &[2.]);
+    channel.sample_into(0.5, &mut w);
+    assert_near(&w, &[1. + 2. * (3. * 0.0625 - 2. * 0.015625)]);
+}
+
+#[test]
+fn test_clip() {
+    let mut clip = AnimationClip::new("move");
+    clip.add_channel(AnimationChannel::new(
+        1,
+        AnimationProperty::Scale,
+        Interpolation::Linear,
+        vec![0., 2.],
+        vec![1., 1., 1., 3., 3., 3.],
+    ));
+    assert_eq!(clip.duration(), 2.);
+    let mut transformations = [Transformation::new(); 2];
+    clip.apply_to_transformations(1.0, &mut transformations);
+    assert_near(&transformations[0].scale(), &[1., 1., 1.]);
+    assert_near(&transformations[1].scale(), &[2., 2., 2.]);
+
+    let mut skeleton = Skeleton::new();
+    let b0 = skeleton.add_bone(Transformation::new(), 0);
+    let b1 = skeleton.add_bone(Transformation::new().with_translation([1., 0., 0.]), 1);
+    skeleton.relate(b0, b1);
+    skeleton.resolve();
+    skeleton.derive_matrices();
+    let mut pose = SkeletonPose::new(&skeleton);
+    clip.apply_to_pose(1.0, &mut pose);
+    let t = *pose.bone_pose_mut(b1).unwrap().transformation();
+    assert_near(&t.scale(), &[2., 2., 2.]);
+    assert_near(&t.translation(), &[1., 0., 0.]);
+}
@@ -40,3 +40,227 @@ fn test_1() {
         &[1., 0., 0., 0., 0., 1., 0., 0., 0., 0., 1., 0., 0., -1., -0.5, 1.]
     );
 }
+
+#[test]
+fn test_pose() {
+    let mut skeleton = build_bone_set();
+    skeleton.derive_matrices();
+    let mut pose = mod3d_base::SkeletonPose::new(&skeleton);
+    pose.update(1);
+    let identity = [
+        1., 0., 0., 0., 0., 1., 0., 0., 0., 0., 1., 0., 0., 0., 0., 1.,
+    ];
+    for m in pose.bone_matrices() {
+        assert_eq!(m, &identity, "Rest pose must give identity matrices");
+    }
+
+    // Move bone 2 (and hence its children) by 1 in X; the matrix
+    // indices are in hierarchy order, so bone 2 and its children use
+    // matrices 2, 3 and 4
+    let bone_pose = pose.bone_pose_mut(2).unwrap();
+    let t = *bone_pose.transformation();
+    bone_pose.set_transformation(t.with_translation([1., 1., 0.]));
+    pose.update(2);
+    let moved = [
+        1., 0., 0., 0., 0., 1., 0., 0., 0., 0., 1., 0., 1., 0., 0., 1.,
+    ];
+    for (i, m) in pose.bone_matrices().iter().enumerate() {
+        let expected = if (2..5).contains(&i) {
+            &moved
+        } else {
+            &identity
+        };
+        assert_eq!(m, expected, "Bone matrix {i}");
+    }
+}
+
+#[test]
+fn bone_component() {
+    use mod3d_base::example_client::Renderable;
+    use mod3d_base::{BaseMaterial, Object, RenderRecipe};
+    let mut triangle = mod3d_base::ExampleVertices::new();
+    mod3d_base::example_objects::triangle::new::<Renderable>(&mut triangle, 0.5);
+    let material = BaseMaterial::of_rgba(0xff0000ff);
+    let mut obj: Object<BaseMaterial, Renderable> = Object::new();
+    let v_id = obj.add_vertices(triangle.borrow_vertices(0.into()));
+    let m_id = obj.add_material(&material);
+    obj.add_component(
+        None,
+        Some(Transformation::new().with_translation([1., 0., 0.])),
+        mod3d_base::example_objects::triangle::mesh(v_id, m_id),
+    );
+    let hand = obj.add_bone_component(
+        None,
+        1,
+        Some(Transformation::new().with_translation([0., 2., 0.])),
+        mod3d_base::example_objects::triangle::mesh(v_id, m_id),
+    );
+    obj.add_component(
+        Some(hand),
+        Some(Transformation::new().with_translation([0., 0., 1.])),
+        mod3d_base::example_objects::triangle::mesh(v_id, m_id),
+    );
+    obj.analyze();
+
+    let recipe = RenderRecipe::from_component_hierarchy(&obj.components);
+    assert_eq!(recipe.primitives.len(), 3);
+    let bone_matrices = [
+        Transformation::new().mat4(),
+        Transformation::new().with_translation([5., 0., 0.]).mat4(),
+    ];
+    let mut rest = vec![];
+    let mut posed = vec![];
+    for i in 0..3 {
+        let m = recipe.primitive_matrix(i, &[]);
+        rest.push([m[12], m[13], m[14]]);
+        let m = recipe.primitive_matrix(i, &bone_matrices);
+        posed.push(([m[12], m[13], m[14]], recipe.bone_for_primitives[i]));
+    }
+    rest.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    posed.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(rest, vec![[0., 2., 0.], [0., 2., 1.], [1., 0., 0.]]);
+    assert_eq!(
+        posed,
+        vec![
+            ([1., 0., 0.], None),
+            ([5., 2., 0.], Some(1)),
+            ([5., 2., 1.], Some(1)),
+        ],
+        "Children of a bone component follow the bone"
+    );
+}
+
+#[test]
+fn test_pose_rotated_chain() {
+    use geo_nd::quat;
+    let quarter = quat::of_axis_angle(&[0., 0., 1.], std::f32::consts::FRAC_PI_2);
+    let mut skeleton = Skeleton::new();
+    let b0 = skeleton.add_bone(
+        Transformation::new()
+            .with_translation([2., 0., 0.])
+            .with_rotation(quarter),
+        0,
+    );
+    let b1 = skeleton.add_bone(Transformation::new().with_translation([1., 0., 0.]), 1);
+    skeleton.relate(b0, b1);
+    skeleton.resolve();
+    skeleton.derive_matrices();
+
+    // At rest bone 1 is at (2,1,0); rotating bone 0 by another
+    // quarter turn moves it (and the mesh around it) to (1,0,0)
+    let mut pose = mod3d_base::SkeletonPose::new(&skeleton);
+    let bone_pose = pose.bone_pose_mut(b0).unwrap();
+    let t = *bone_pose.transformation();
+    bone_pose.set_transformation(t.with_rotation(quat::multiply(&quarter, &quarter)));
+    pose.update(1);
+    let m = pose.bone_matrices()[1];
+    let p = [
+        2. * m[0] + m[4] + m[12],
+        2. * m[1] + m[5] + m[13],
+        2. * m[2] + m[6] + m[14],
+    ];
+    for (a, e) in p.iter().zip([1., 0., 0.]) {
+        assert!((a - e).abs() < 1E-5, "Posed point {p:?}");
+    }
+}
+
+#[test]
+fn bone_component_of_instance() {
+    use geo_nd::quat;
+    use mod3d_base::example_client::Renderable;
+    use mod3d_base::{BaseMaterial, Object};
+    let mut triangle = mod3d_base::ExampleVertices::new();
+    mod3d_base::example_objects::triangle::new::<Renderable>(&mut triangle, 0.5);
+    let material = BaseMaterial::of_rgba(0xff0000ff);
+
+    // The rest pose of the bone is at (0,2,0) rotated a quarter turn
+    // about Z; its bind pose is at the origin
+    let quarter = quat::of_axis_angle(&[0., 0., 1.], std::f32::consts::FRAC_PI_2);
+    let mut skeleton = Skeleton::new();
+    let b0 = skeleton.add_bone(
+        Transformation::new()
+            .with_translation([0., 2., 0.])
+            .with_rotation(quarter),
+        0,
+    );
+    skeleton.set_bind_mtb(b0, Transformation::new().mat4());
+    skeleton.resolve();
+    skeleton.derive_matrices();
+
+    let mut obj: Object<BaseMaterial, Renderable> = Object::new();
+    obj.skeleton = Some(skeleton);
+    let v_id = obj.add_vertices(triangle.borrow_vertices(0.into()));
+    let m_id = obj.add_material(&material);
+    obj.add_bone_component(
+        None,
+        0,
+        Some(Transformation::new().with_translation([1., 0., 0.])),
+        mod3d_base::example_objects::triangle::mesh(v_id, m_id),
+    );
+    obj.analyze();
+    let mut renderer = Renderable::default();
+    let instantiable = obj.into_instantiable(&mut renderer);
+
+    // Without updating the bone matrices the instance is drawn at
+    // the rest pose: (1,0,0) in the bind pose, rotated and moved
+    let instance = instantiable.instantiate();
+    let m = instantiable
+        .render_recipe
+        .primitive_matrix(0, &instance.bone_matrices);
+    for (a, e) in m[12..15].iter().zip([0., 3., 0.]) {
+        assert!((a - e).abs() < 1E-5, "Rest pose matrix {m:?}");
+    }
+}
+
+#[test]
+fn skinned_components() {
+    use mod3d_base::example_client::Renderable;
+    use mod3d_base::{BaseMaterial, Object};
+    let mut triangle = mod3d_base::ExampleVertices::new();
+    mod3d_base::example_objects::triangle::new::<Renderable>(&mut triangle, 0.5);
+    let material = BaseMaterial::of_rgba(0xff0000ff);
+
+    // Two skins of two bones each, side by side
+    let mut skeleton = Skeleton::new();
+    for i in 0..4 {
+        skeleton.add_bone(
+            Transformation::new().with_translation([i as f32, 0., 0.]),
+            i,
+        );
+    }
+    skeleton.resolve();
+    skeleton.derive_matrices();
+
+    let mut obj: Object<BaseMaterial, Renderable> = Object::new();
+    obj.skeleton = Some(skeleton);
+    let v_id = obj.add_vertices(triangle.borrow_vertices(0.into()));
+    let m_id = obj.add_material(&material);
+    obj.add_skinned_component(
+        None,
+        2..4,
+        None,
+        mod3d_base::example_objects::triangle::mesh(v_id, m_id),
+    );
+    obj.add_component(
+        None,
+        None,
+        mod3d_base::example_objects::triangle::mesh(v_id, m_id),
+    );
+    obj.analyze();
+    let mut renderer = Renderable::default();
+    let instantiable = obj.into_instantiable(&mut renderer);
+    let recipe = &instantiable.render_recipe;
+
+    // The joints of the skinned primitive index just its own skin
+    let bone_matrices: Vec<_> = (0..4)
+        .map(|i| {
+            Transformation::new()
+                .with_translation([i as f32, 0., 0.])
+                .mat4()
+        })
+        .collect();
+    let skinned = recipe.primitive_skin_matrices(0, &bone_matrices);
+    assert_eq!(skinned, &bone_matrices[2..4]);
+    let unskinned = recipe.primitive_skin_matrices(1, &bone_matrices);
+    assert_eq!(unskinned, &bone_matrices[..]);
+}
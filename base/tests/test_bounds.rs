@@ -0,0 +1,128 @@
+use mod3d_base::example_client::Renderable;
+use mod3d_base::{BaseMaterial, Bounds, Object, Transformation};
+
+#[test]
+fn bounds_union_transform() {
+    let a = Bounds::of_box([0., 0., 0.], [2., 0., 0.]);
+    assert_eq!(a.center(), [1., 0., 0.]);
+    assert_eq!(a.radius(), 1.);
+
+    let b = Bounds::of_points([[4., 0., 0.], [6., 0., 0.]]).unwrap();
+    let ab = a.union(&b);
+    assert_eq!(ab.min(), [0., 0., 0.]);
+    assert_eq!(ab.max(), [6., 0., 0.]);
+    assert_eq!(ab.center(), [3., 0., 0.]);
+    assert_eq!(ab.radius(), 3.);
+    assert_eq!(ab.union(&a), ab, "Contained bounds do not change a union");
+    assert!(Bounds::of_points([]).is_none());
+
+    let m = Transformation::new()
+        .with_translation([0., 1., 0.])
+        .with_scale([2., 2., 2.])
+        .mat4();
+    let t = a.transformed(&m);
+    assert_eq!(t.min(), [0., 1., 0.]);
+    assert_eq!(t.max(), [4., 1., 0.]);
+    assert_eq!(t.center(), [2., 1., 0.]);
+    assert_eq!(t.radius(), 2.);
+}
+
+#[test]
+fn object_bounds() {
+    let mut triangle = mod3d_base::ExampleVertices::new();
+    mod3d_base::example_objects::triangle::new::<Renderable>(&mut triangle, 0.5);
+    let material = BaseMaterial::of_rgba(0xff0000ff);
+    let mut obj: Object<BaseMaterial, Renderable> = Object::new();
+    let v_id = obj.add_vertices(triangle.borrow_vertices(0.into()));
+    let m_id = obj.add_material(&material);
+    let parent = obj.add_component(
+        None,
+        Some(Transformation::new().with_translation([1., 2., 3.])),
+        mod3d_base::example_objects::triangle::mesh(v_id, m_id),
+    );
+    obj.add_component(
+        Some(parent),
+        Some(Transformation::new().with_translation([0., 0., 1.])),
+        mod3d_base::example_objects::triangle::mesh(v_id, m_id),
+    );
+    obj.analyze();
+
+    let mesh_bounds = obj
+        .components
+        .borrow_node(0)
+        .mesh
+        .calc_bounds(&obj.vertices);
+    assert_eq!(mesh_bounds.unwrap().min(), [-0.5, -0.5, 0.]);
+    assert_eq!(mesh_bounds.unwrap().max(), [0.5, 0.5, 0.]);
+    let component_bounds = obj.components.borrow_node(1).calc_bounds(&obj.vertices);
+    assert_eq!(component_bounds.unwrap().min(), [-0.5, -0.5, 1.]);
+
+    let bounds = obj.bounds().unwrap();
+    assert_eq!(bounds.min(), [0.5, 1.5, 3.]);
+    assert_eq!(bounds.max(), [1.5, 2.5, 4.]);
+
+    let mut renderer = Renderable::default();
+    let instantiable = obj.into_instantiable(&mut renderer);
+    assert_eq!(instantiable.bounds, Some(bounds));
+    assert_eq!(instantiable.render_recipe.primitive_bounds.len(), 2);
+
+    let mut instance = instantiable.instantiate();
+    instance.transformation.set_translation([10., 0., 0.]);
+    let world_bounds = instance.world_bounds().unwrap();
+    assert_eq!(world_bounds.min(), [10.5, 1.5, 3.]);
+    assert_eq!(world_bounds.radius(), bounds.radius());
+}
+
+#[test]
+fn instanced_bounds() {
+    let mut triangle = mod3d_base::ExampleVertices::new();
+    mod3d_base::example_objects::triangle::new::<Renderable>(&mut triangle, 0.5);
+    let material = BaseMaterial::of_rgba(0xff0000ff);
+    let mut obj: Object<BaseMaterial, Renderable> = Object::new();
+    let v_id = obj.add_vertices(triangle.borrow_vertices(0.into()));
+    let m_id = obj.add_material(&material);
+    obj.add_instanced_component(
+        None,
+        Some(Transformation::new().with_translation([0., 0., 1.])),
+        vec![
+            Transformation::new().mat4(),
+            Transformation::new().with_translation([4., 0., 0.]).mat4(),
+        ],
+        mod3d_base::example_objects::triangle::mesh(v_id, m_id),
+    );
+    obj.analyze();
+
+    let bounds = obj.bounds().unwrap();
+    assert_eq!(bounds.min(), [-0.5, -0.5, 1.]);
+    assert_eq!(bounds.max(), [4.5, 0.5, 1.]);
+
+    let mut renderer = Renderable::default();
+    let instantiable = obj.into_instantiable(&mut renderer);
+    assert_eq!(instantiable.bounds, Some(bounds));
+    let recipe = &instantiable.render_recipe;
+    assert_eq!(recipe.primitive_instances(0).len(), 2);
+    assert_eq!(recipe.bounds(), Some(bounds));
+
+    // The instance matrices apply before that of the node, so a node
+    // rotated a quarter turn about Z moves the instance at X=4 to Y=4
+    let quarter = geo_nd::quat::of_axis_angle(&[0., 0., 1.], std::f32::consts::FRAC_PI_2);
+    let mut obj: Object<BaseMaterial, Renderable> = Object::new();
+    let v_id = obj.add_vertices(triangle.borrow_vertices(0.into()));
+    let m_id = obj.add_material(&material);
+    obj.add_instanced_component(
+        None,
+        Some(Transformation::new().with_rotation(quarter)),
+        vec![
+            Transformation::new().mat4(),
+            Transformation::new().with_translation([4., 0., 0.]).mat4(),
+        ],
+        mod3d_base::example_objects::triangle::mesh(v_id, m_id),
+    );
+    obj.analyze();
+    let instantiable = obj.into_instantiable(&mut renderer);
+    let bounds = instantiable.render_recipe.bounds().unwrap();
+    let approx =
+        |a: [f32; 3], b: [f32; 3]| a.iter().zip(b.iter()).all(|(a, b)| (a - b).abs() < 1e-5);
+    assert!(approx(bounds.min(), [-0.5, -0.5, 0.]), "{bounds:?}");
+    assert!(approx(bounds.max(), [0.5, 4.5, 0.]), "{bounds:?}");
+}
@@ -0,0 +1,59 @@
+use mod3d_base::Transformation;
+
+fn assert_near(a: &[f32], b: &[f32]) {
+    for (x, y) in a.iter().zip(b.iter()) {
+        assert!((x - y).abs() < 1e-5, "{a:?} != {b:?}");
+    }
+}
+
+#[test]
+fn test_interpolate() {
+    let quarter = geo_nd::quat::of_axis_angle(&[0., 0., 1.], std::f32::consts::FRAC_PI_2);
+    let in0 = Transformation::new().with_translation([1., 2., 3.]);
+    let in1 = Transformation::new()
+        .with_translation([5., 6., 7.])
+        .with_scale([3., 3., 3.])
+        .with_rotation(quarter);
+    let mut t = Transformation::new();
+
+    // Translation, scale and rotation all start at 'in0' and end at 'in1'
+    t.interpolate(0., &in0, &in1);
+    assert_near(&t.translation(), &in0.translation());
+    assert_near(&t.scale(), &in0.scale());
+    assert_near(&t.rotation(), &in0.rotation());
+    t.interpolate(1., &in0, &in1);
+    assert_near(&t.translation(), &in1.translation());
+    assert_near(&t.scale(), &in1.scale());
+    assert_near(&t.rotation(), &in1.rotation());
+
+    t.interpolate(0.25, &in0, &in1);
+    assert_near(&t.translation(), &[2., 3., 4.]);
+    assert_near(&t.scale(), &[1.5, 1.5, 1.5]);
+}
+
+#[test]
+fn test_combine() {
+    use geo_nd::quat;
+    let quarter_z = quat::of_axis_angle(&[0., 0., 1.], std::f32::consts::FRAC_PI_2);
+    let quarter_x = quat::of_axis_angle(&[1., 0., 0.], std::f32::consts::FRAC_PI_2);
+    let parent = Transformation::new()
+        .with_translation([1., 2., 3.])
+        .with_scale([2., 2., 2.])
+        .with_rotation(quarter_z);
+    let child = Transformation::new()
+        .with_translation([1., 0., 0.])
+        .with_scale([3., 1., 0.5])
+        .with_rotation(quarter_x);
+    let mut t = Transformation::new();
+    t.combine(&parent, &child);
+
+    // The child is applied first and then the parent
+    let expected = geo_nd::matrix::multiply4(&child.mat4(), &parent.mat4());
+    assert_near(&t.mat4(), &expected);
+
+    // The child's origin is (1,0,0) scaled by 2 and rotated to
+    // (0,2,0) in the parent, then moved to (1,4,3)
+    assert_near(&t.translation(), &[1., 4., 3.]);
+    assert_near(&t.scale(), &[6., 2., 1.]);
+    assert_near(&t.rotation(), &quat::multiply(&quarter_z, &quarter_x));
+}
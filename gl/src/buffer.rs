@@ -27,6 +27,8 @@ where
     pub elements_per_data: u32,
     /// The type of each element
     pub ele_type: BufferElementType,
+    /// True if integer elements are normalized when read as floats
+    pub normalized: bool,
     /// Offset from start of buffer to first byte of data
     pub byte_offset: u32,
     /// Stride of data in the buffer - 0 for elements_per_data*sizeof(ele_type)
@@ -51,11 +53,32 @@ where
         bda.desc().data().create_client(render_context);
         self.elements_per_data = bda.count();
         self.ele_type = bda.ele_type();
+        self.normalized = bda.is_normalized();
         self.byte_offset = bda.desc().byte_offset();
         self.stride = bda.desc().stride();
         self.gl_buffer = bda.desc().data().borrow_client().clone();
     }
 
+    //fp of_floats
+    /// Create a [VertexBuffer] with its own dynamic OpenGL buffer
+    /// holding 'elements_per_data' f32 for each vertex, which may be
+    /// updated with update_floats
+    pub fn of_floats(render_context: &mut G, data: &[f32], elements_per_data: u32) -> Self {
+        let mut gl_buffer = <G as Gl>::Buffer::default();
+        render_context.init_buffer_of_floats(&mut gl_buffer, data);
+        Self {
+            gl_buffer,
+            elements_per_data,
+            ..Default::default()
+        }
+    }
+
+    //mp update_floats
+    /// Update the contents of a [VertexBuffer] created by of_floats
+    pub fn update_floats(&self, render_context: &mut G, data: &[f32]) {
+        render_context.buffer_update_floats(&self.gl_buffer, data);
+    }
+
     //fp bind_to_vao_attr
     /// Bind the buffer as a vertex attribute to the current VAO
     pub fn bind_to_vao_attr(
@@ -68,6 +91,7 @@ where
             attr_id,
             self.elements_per_data,
             self.ele_type,
+            self.normalized,
             self.byte_offset,
             self.stride,
         );
@@ -85,12 +109,14 @@ where
         let gl_buffer = <G as Gl>::Buffer::default();
         let elements_per_data = 0;
         let ele_type = BufferElementType::float32();
+        let normalized = false;
         let byte_offset = 0;
         let stride = 0;
         Self {
             gl_buffer,
             elements_per_data,
             ele_type,
+            normalized,
             byte_offset,
             stride,
         }
@@ -106,12 +132,14 @@ where
         let gl_buffer = self.gl_buffer.clone();
         let elements_per_data = self.elements_per_data;
         let ele_type = self.ele_type;
+        let normalized = self.normalized;
         let byte_offset = self.byte_offset;
         let stride = self.stride;
         Self {
             gl_buffer,
             elements_per_data,
             ele_type,
+            normalized,
             byte_offset,
             stride,
         }
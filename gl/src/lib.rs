@@ -21,8 +21,10 @@ mod traits;
 pub use traits::{Gl, GlBuffer, GlProgram, GlShader, GlShaderType, GlVao};
 
 //a Submodules
+mod light;
 mod material;
 mod texture;
+pub use light::ShaderLight;
 pub use material::Material;
 pub use texture::Texture;
 
@@ -36,7 +38,9 @@ mod vertices;
 pub use vertices::Vertices;
 
 mod shader_instantiable;
-pub use shader_instantiable::{ShaderInstantiable, ShaderMaterialBaseData};
+pub use shader_instantiable::{
+    ShaderInstantiable, ShaderMaterialBaseData, MAX_BONE_MATRICES, MAX_INSTANCES_PER_DRAW,
+};
 
 #[derive(Debug, Default, Clone)]
 pub struct Descriptor(());
@@ -0,0 +1,92 @@
+//a Imports
+use mod3d_base::{Light, LightKind};
+
+use crate::{Vec3, Vec4};
+
+//a ShaderLight
+//tp ShaderLight
+/// A light as it is laid out in the 'World' uniform buffer of the
+/// shaders (std140, 32 bytes)
+///
+/// The 'w' component of the position encodes the type of light:
+///
+/// * positive: a point light at position.xyz, with w the minimum squared
+///   distance used for fall-off
+///
+/// * negative: a directional light, with position.xyz the direction
+///   *towards* the light
+///
+/// * zero: an ambient light
+///
+/// The shaders do not support spot cones, so spot lights are treated
+/// as point lights
+#[derive(Debug, Default, Clone, Copy)]
+#[repr(C)]
+pub struct ShaderLight {
+    position: Vec4,
+    color: Vec4,
+}
+
+//ip ShaderLight
+impl ShaderLight {
+    /// Minimum squared distance used for the fall-off of point lights
+    const MIN_DISTANCE2: f32 = 0.1;
+
+    //fp new
+    /// Create a [ShaderLight] directly from its position and color
+    pub fn new(position: Vec4, color: Vec4) -> Self {
+        Self { position, color }
+    }
+
+    //fp of_light
+    /// Create a [ShaderLight] from a world-space [Light]
+    ///
+    /// The color is scaled by the intensity of the light
+    pub fn of_light(light: &Light) -> Self {
+        let c = light.color();
+        let i = light.intensity();
+        let color = [c[0] * i, c[1] * i, c[2] * i, 0.];
+        let position = {
+            match light.kind() {
+                LightKind::Directional => {
+                    let d = light.direction();
+                    [-d[0], -d[1], -d[2], -1.]
+                }
+                LightKind::Point | LightKind::Spot { .. } => {
+                    let p = light.position();
+                    [p[0], p[1], p[2], Self::MIN_DISTANCE2]
+                }
+            }
+        };
+        Self { position, color }
+    }
+
+    //fp ambient
+    /// Create an ambient [ShaderLight]
+    pub fn ambient(color: Vec3) -> Self {
+        Self {
+            position: [0.; 4],
+            color: [color[0], color[1], color[2], 0.],
+        }
+    }
+
+    //fp fill
+    /// Fill an array of [ShaderLight] (such as that of the 'World'
+    /// uniform buffer) with as many lights as fit, leaving room for a
+    /// final ambient light; any unused entries have no effect
+    pub fn fill(shader_lights: &mut [ShaderLight], lights: &[Light], ambient: Vec3) {
+        let Some(n) = shader_lights.len().checked_sub(1) else {
+            return;
+        };
+        let mut shader_lights = shader_lights.iter_mut();
+        for (sl, l) in (&mut shader_lights).zip(lights.iter().take(n)) {
+            *sl = Self::of_light(l);
+        }
+        if let Some(sl) = shader_lights.next() {
+            *sl = Self::ambient(ambient);
+        }
+        for sl in shader_lights {
+            *sl = Self::default();
+        }
+    }
+}
@@ -1,5 +1,5 @@
 //a Imports
-use mod3d_base::ShortIndex;
+use mod3d_base::{MaterialAspect, ShortIndex};
 
 use crate::{Gl, ShaderMaterialBaseData, TextureId};
 
@@ -10,7 +10,8 @@ use crate::{Gl, ShaderMaterialBaseData, TextureId};
 pub struct Material {
     // <G: Gl> {
     base_data: ShaderMaterialBaseData,
-    textures: [(TextureId, ShortIndex); 8],
+    textures: [(TextureId, ShortIndex); MaterialAspect::NUM],
+    uv_transforms: [f32; MaterialAspect::NUM * 8],
 }
 
 // impl<G: Gl> Material<G> {
@@ -28,25 +29,24 @@ impl Material {
         M: mod3d_base::Material,
     {
         let base_data = ShaderMaterialBaseData::of_material(material);
-        let mut textures = [(TextureId::None, ShortIndex::none()); 8];
+        let mut textures = [(TextureId::None, ShortIndex::none()); MaterialAspect::NUM];
+        let mut uv_transforms = [0.; MaterialAspect::NUM * 8];
         let mut i = 0;
-        for aspect in [
-            mod3d_base::MaterialAspect::Color,
-            mod3d_base::MaterialAspect::Normal,
-            mod3d_base::MaterialAspect::MetallicRoughness,
-            mod3d_base::MaterialAspect::Occlusion,
-            mod3d_base::MaterialAspect::Emission,
-        ] {
+        for (n, aspect) in MaterialAspect::ALL.into_iter().enumerate() {
             let ti = material.texture(aspect);
             if ti.is_some() {
                 textures[i] = (TextureId::of_material_aspect(aspect), ti);
                 i += 1;
             };
+            let m = material.uv_transform(aspect);
+            uv_transforms[n * 8..n * 8 + 8]
+                .copy_from_slice(&[m[0], m[3], m[6], 0., m[1], m[4], m[7], 0.]);
         }
         eprintln!("Textures {textures:?}");
         Ok(Self {
             base_data,
             textures,
+            uv_transforms,
         })
     }
     pub fn base_data(&self) -> &ShaderMaterialBaseData {
@@ -55,6 +55,15 @@ impl Material {
     pub fn textures(&self) -> &[(TextureId, ShortIndex)] {
         &self.textures
     }
+    /// Get the transforms of the texture coordinates for each texture,
+    /// as two vec4 per texture in the order of [MaterialAspect]
+    ///
+    /// The vec4 are the first two rows of the transform, so that the
+    /// transformed coordinates are the dot product of each with (u,
+    /// v, 1, 0)
+    pub fn uv_transforms(&self) -> &[f32] {
+        &self.uv_transforms
+    }
 }
 
 //ip Display for Material
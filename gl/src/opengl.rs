@@ -16,6 +16,22 @@ mod texture;
 mod vao;
 use vao::Vao;
 
+//a Functions
+//fi gl_primitive_type
+/// Get the GL primitive type for a [mod3d_base::PrimitiveType]
+fn gl_primitive_type(primitive_type: mod3d_base::PrimitiveType) -> u32 {
+    use mod3d_base::PrimitiveType::*;
+    match primitive_type {
+        Points => gl::POINTS,
+        Lines => gl::LINES,
+        LineLoop => gl::LINE_LOOP,
+        LineStrip => gl::LINE_STRIP,
+        Triangles => gl::TRIANGLES,
+        TriangleFan => gl::TRIANGLE_FAN,
+        TriangleStrip => gl::TRIANGLE_STRIP,
+    }
+}
+
 //a Model3DOpenGL
 //tp Model3DOpenGL
 #[derive(Debug)]
@@ -107,6 +123,16 @@ impl Gl for Model3DOpenGL {
         buffer.of_indices(view);
     }
 
+    //mp init_buffer_of_floats
+    fn init_buffer_of_floats(&mut self, buffer: &mut <Self as Gl>::Buffer, data: &[f32]) {
+        buffer.of_floats(data);
+    }
+
+    //mp buffer_update_floats
+    fn buffer_update_floats(&mut self, buffer: &<Self as Gl>::Buffer, data: &[f32]) {
+        buffer.update_floats(data);
+    }
+
     //mp vao_create_from_indices
     fn vao_create_from_indices(&mut self, indices: &crate::IndexBuffer<Self>) -> Result<Vao, ()> {
         Vao::create_from_indices(self, indices)
@@ -119,10 +145,11 @@ impl Gl for Model3DOpenGL {
         attr_id: &<Program as GlProgram>::GlAttrId,
         count: u32,
         ele_type: BufferElementType,
+        normalized: bool,
         byte_offset: u32,
         stride: u32,
     ) {
-        buffer.bind_to_vao_attr(*attr_id, count, ele_type, byte_offset, stride);
+        buffer.bind_to_vao_attr(*attr_id, count, ele_type, normalized, byte_offset, stride);
     }
 
     //mp program_set_uniform_mat4
@@ -134,6 +161,34 @@ impl Gl for Model3DOpenGL {
         }
     }
 
+    //fp program_set_uniform_mat4_array
+    fn program_set_uniform_mat4_array(
+        &mut self,
+        program: &Self::Program,
+        id: crate::UniformId,
+        mat4s: &[Mat4],
+    ) {
+        if let Some(u) = program.uniform(id) {
+            unsafe {
+                gl::UniformMatrix4fv(
+                    u,
+                    mat4s.len() as i32,
+                    gl::FALSE,
+                    mat4s.as_ptr() as *const f32,
+                );
+            }
+        }
+    }
+
+    //fp program_set_uniform_f32
+    fn program_set_uniform_f32(&mut self, program: &Self::Program, id: crate::UniformId, v: f32) {
+        if let Some(u) = program.uniform(id) {
+            unsafe {
+                gl::Uniform1f(u, v);
+            }
+        }
+    }
+
     //fp program_set_uniform_floats_4
     fn program_set_uniform_floats_4(
         &mut self,
@@ -186,17 +241,7 @@ impl Gl for Model3DOpenGL {
     fn draw_primitive(&mut self, vaos: &[Vao], primitive: &mod3d_base::Primitive) {
         // (if p.vertices_index different to last)
         // (if p.material_index ...
-        use mod3d_base::PrimitiveType::*;
-        let gl_type = match primitive.primitive_type() {
-            Points => gl::POINTS,
-            Lines => gl::LINES,
-            LineLoop => gl::LINE_LOOP,
-            LineStrip => gl::LINE_STRIP,
-            Triangles => gl::TRIANGLES,
-            TriangleFan => gl::TRIANGLE_FAN,
-            TriangleStrip => gl::TRIANGLE_STRIP,
-        };
-
+        let gl_type = gl_primitive_type(primitive.primitive_type());
         let opt_vertices_index: Option<usize> = primitive.vertices_index().into();
         if let Some(vertices_index) = opt_vertices_index {
             let index_type = vaos[vertices_index].bind_vao();
@@ -219,6 +264,38 @@ impl Gl for Model3DOpenGL {
         }
     }
 
+    //fp draw_primitive_instanced
+    fn draw_primitive_instanced(
+        &mut self,
+        vaos: &[Vao],
+        primitive: &mod3d_base::Primitive,
+        instance_count: usize,
+    ) {
+        let gl_type = gl_primitive_type(primitive.primitive_type());
+        let opt_vertices_index: Option<usize> = primitive.vertices_index().into();
+        if let Some(vertices_index) = opt_vertices_index {
+            let index_type = vaos[vertices_index].bind_vao();
+            unsafe {
+                gl::DrawElementsInstanced(
+                    gl_type,
+                    primitive.index_count() as i32,
+                    index_type,
+                    primitive.byte_offset() as *const std::ffi::c_void,
+                    instance_count as i32,
+                );
+            }
+        } else {
+            unsafe {
+                gl::DrawArraysInstanced(
+                    gl_type,
+                    primitive.byte_offset() as i32,
+                    primitive.index_count() as i32,
+                    instance_count as i32,
+                );
+            }
+        }
+    }
+
     //mp bind_vao
     fn bind_vao(&mut self, vao: Option<&Self::Vao>) {
         if let Some(vao) = vao {
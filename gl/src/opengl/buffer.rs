@@ -75,6 +75,42 @@ impl Buffer {
         self.gl = Rc::new(gl);
     }
 
+    //mp of_floats
+    /// Create an OpenGL ARRAY_BUFFER buffer using DYNAMIC_DRAW - this
+    /// copies the data in to OpenGL, and it may be updated with
+    /// update_floats
+    pub fn of_floats(&mut self, data: &[f32]) {
+        assert!(self.is_none());
+        let mut gl: gl::types::GLuint = 0;
+        unsafe {
+            gl::GenBuffers(1, (&mut gl) as *mut gl::types::GLuint);
+            gl::BindBuffer(gl::ARRAY_BUFFER, gl);
+            gl::BufferData(
+                gl::ARRAY_BUFFER,
+                std::mem::size_of_val(data) as gl::types::GLsizeiptr,
+                data.as_ptr() as *const gl::types::GLvoid,
+                gl::DYNAMIC_DRAW,
+            );
+            gl::BindBuffer(gl::ARRAY_BUFFER, 0); // unbind to protect
+        }
+        self.gl = Rc::new(gl);
+    }
+
+    //mp update_floats
+    /// Update the contents of an ARRAY_BUFFER created by of_floats
+    pub fn update_floats(&self, data: &[f32]) {
+        unsafe {
+            gl::BindBuffer(gl::ARRAY_BUFFER, self.gl_buffer());
+            gl::BufferSubData(
+                gl::ARRAY_BUFFER,
+                0,
+                std::mem::size_of_val(data) as gl::types::GLsizeiptr,
+                data.as_ptr() as *const gl::types::GLvoid,
+            );
+            gl::BindBuffer(gl::ARRAY_BUFFER, 0); // unbind to protect
+        }
+    }
+
     //mp of_indices
     /// Create the OpenGL ELEMENT_ARRAY_BUFFER buffer using STATIC_DRAW - this copies the data in to OpenGL
     pub fn of_indices(&mut self, view: &mod3d_base::BufferIndexAccessor<Model3DOpenGL>) {
@@ -110,6 +146,7 @@ impl Buffer {
         attr_id: gl::types::GLuint,
         count: u32,
         ele_type: mod3d_base::BufferElementType,
+        normalized: bool,
         byte_offset: u32,
         stride: u32,
     ) {
@@ -119,11 +156,11 @@ impl Buffer {
                 Float32 => gl::FLOAT,
                 Float16 => gl::HALF_FLOAT,
                 SInt8 => gl::BYTE,
-                UInt8 => gl::BYTE,
+                UInt8 => gl::UNSIGNED_BYTE,
                 SInt16 => gl::SHORT,
-                UInt16 => gl::SHORT,
+                UInt16 => gl::UNSIGNED_SHORT,
                 SInt32 => gl::INT,
-                UInt32 => gl::INT,
+                UInt32 => gl::UNSIGNED_INT,
             }
         };
         unsafe {
@@ -135,7 +172,7 @@ impl Buffer {
                 attr_id,
                 count as i32, // size
                 ele_type,
-                gl::FALSE,     // normalized
+                if normalized { gl::TRUE } else { gl::FALSE },
                 stride as i32, // stride
                 byte_offset as usize as *const std::ffi::c_void,
             );
@@ -5,6 +5,32 @@ use mod3d_base::TextureClient;
 
 use crate::Model3DOpenGL;
 
+//a Sampler mapping
+//fi gl_wrap
+/// Map a [mod3d_base::TextureWrap] to an OpenGL wrap mode
+fn gl_wrap(wrap: mod3d_base::TextureWrap) -> gl::types::GLenum {
+    use mod3d_base::TextureWrap::*;
+    match wrap {
+        Repeat => gl::REPEAT,
+        MirroredRepeat => gl::MIRRORED_REPEAT,
+        ClampToEdge => gl::CLAMP_TO_EDGE,
+    }
+}
+
+//fi gl_filter
+/// Map a [mod3d_base::TextureFilter] to an OpenGL filter
+fn gl_filter(filter: mod3d_base::TextureFilter) -> gl::types::GLenum {
+    use mod3d_base::TextureFilter::*;
+    match filter {
+        Nearest => gl::NEAREST,
+        Linear => gl::LINEAR,
+        NearestMipmapNearest => gl::NEAREST_MIPMAP_NEAREST,
+        LinearMipmapNearest => gl::LINEAR_MIPMAP_NEAREST,
+        NearestMipmapLinear => gl::NEAREST_MIPMAP_LINEAR,
+        LinearMipmapLinear => gl::LINEAR_MIPMAP_LINEAR,
+    }
+}
+
 //a Texture
 //tp Texture
 /// A simple structure provides a reference-counted OpenGl texture;
@@ -89,10 +115,18 @@ impl Texture {
                 texture.data().as_ptr() as *const gl::types::GLvoid,
             );
 
-            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, gl::REPEAT as i32);
-            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, gl::REPEAT as i32);
-            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::LINEAR as i32);
-            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::LINEAR as i32);
+            let sampler = texture.sampler();
+            let wrap_s = gl_wrap(sampler.wrap_s) as i32;
+            let wrap_t = gl_wrap(sampler.wrap_t) as i32;
+            let min_filter = gl_filter(sampler.min_filter) as i32;
+            let mag_filter = gl_filter(sampler.mag_filter.without_mipmaps()) as i32;
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, wrap_s);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, wrap_t);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, min_filter);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, mag_filter);
+            if sampler.min_filter.uses_mipmaps() {
+                gl::GenerateMipmap(gl::TEXTURE_2D);
+            }
             gl::BindTexture(gl::TEXTURE_2D, 0);
             eprintln!("Created texture {gl:?}");
         }
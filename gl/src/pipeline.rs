@@ -28,11 +28,16 @@ mod deserialize {
                 "Position" => Position,
                 "Normal" => Normal,
                 "Color" => Color,
+                "Color1" => Color1,
                 "Tangent" => Tangent,
                 "Joints" => Joints,
+                "Joints1" => Joints1,
                 "Weights" => Weights,
+                "Weights1" => Weights1,
                 "TexCoords0" => TexCoords0,
                 "TexCoords1" => TexCoords1,
+                "TexCoords2" => TexCoords2,
+                "TexCoords3" => TexCoords3,
                 _ => {
                     return Err(serde::de::Error::custom(format!(
                         "Unknown attribute name {k}"
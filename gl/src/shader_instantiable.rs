@@ -1,6 +1,28 @@
 //a Imports
 use crate::{Gl, GlProgram, UniformId, Vertices};
 
+//a Constants
+/// The maximum number of instances drawn by one instanced draw call;
+/// the InstanceMatrices uniform array of a shader must be at least
+/// this large
+///
+/// Primitives with more instances are drawn with more than one call
+///
+/// The instance and bone matrices are plain uniforms, and OpenGL ES 3
+/// and WebGL2 only guarantee 256 vec4 vertex uniforms; together
+/// these limits use 224 of them (4 per matrix), leaving space for
+/// the model and mesh matrices and the bone scale
+pub const MAX_INSTANCES_PER_DRAW: usize = 16;
+
+/// The maximum number of bone matrices of the skin of a primitive
+/// (all the bone matrices of an instantiable for a primitive without
+/// a skin of its own); the BoneMatrices uniform array of a skinned
+/// shader must be at least this large
+///
+/// This is limited by the vertex uniforms of OpenGL ES 3 and WebGL2
+/// (see [MAX_INSTANCES_PER_DRAW])
+pub const MAX_BONE_MATRICES: usize = 40;
+
 //a Shader structure
 //tp ShaderMaterialBaseData
 /// Change to u8s
@@ -12,15 +34,41 @@ use crate::{Gl, GlProgram, UniformId, Vertices};
 /// emissive_color should be [u8; 3]
 /// occlusion factor is from:
 ///    A scalar parameter controlling the amount of occlusion applied. A value of `0.0` means no occlusion. A value of `1.0` means full occlusion. This value affects the final occlusion value as: `1.0 + strength * (<sampled occlusion texture value> - 1.0)`.
-
+///
+/// This is provided to shaders as five vec4:
+///
+/// * base color RGBA
+///
+/// * metallic, roughness, occlusion strength, normal scale
+///
+/// * emissive RGB (scaled by the emissive strength), alpha cutoff
+///
+/// * alpha mode (0 opaque, 1 mask, 2 blend), double-sided (0 or 1),
+///   texture coordinate sets, unlit (0 or 1)
+///
+/// * clearcoat, clearcoat roughness, transmission, index of refraction
+///
+/// The texture coordinate sets are an integer with two bits for each
+/// texture, in the order of [mod3d_base::MaterialAspect] (least
+/// significant first)
 #[derive(Default, Debug)]
 #[repr(C, packed)]
 pub struct ShaderMaterialBaseData {
     base_color: [f32; 4],
     metallic: f32,
     roughness: f32,
-    occlusion_factor: f32,
-    emissive_factor: f32,
+    occlusion_strength: f32,
+    normal_scale: f32,
+    emissive_color: [f32; 3],
+    alpha_cutoff: f32,
+    alpha_mode: f32,
+    double_sided: f32,
+    tex_coords: f32,
+    unlit: f32,
+    clearcoat: f32,
+    clearcoat_roughness: f32,
+    transmission: f32,
+    ior: f32,
 }
 
 //ip ShaderMaterialBaseData
@@ -38,14 +86,30 @@ impl ShaderMaterialBaseData {
             a as f32 / 255.0,
         ];
         let (metallic, roughness) = base_data.metallic_roughness();
-        let occlusion_factor = 0.;
-        let emissive_factor = 0.;
+        let emissive_strength = material.emissive_strength();
+        let emissive_color = [0, 8, 16]
+            .map(|s| ((base_data.emissive_rgb >> s) & 0xff) as f32 / 255.0 * emissive_strength);
+        let mut tex_coords = 0;
+        for (i, aspect) in mod3d_base::MaterialAspect::ALL.into_iter().enumerate() {
+            tex_coords |= material.tex_coord(aspect).min(3) << (2 * i);
+        }
+        let (clearcoat, clearcoat_roughness) = material.clearcoat();
         Self {
             base_color,
             metallic,
             roughness,
-            occlusion_factor,
-            emissive_factor,
+            occlusion_strength: material.occlusion_strength(),
+            normal_scale: material.normal_scale(),
+            emissive_color,
+            alpha_cutoff: base_data.alpha_cutoff(),
+            alpha_mode: base_data.alpha_mode() as u8 as f32,
+            double_sided: if base_data.double_sided() { 1. } else { 0. },
+            tex_coords: tex_coords as f32,
+            unlit: if base_data.unlit() { 1. } else { 0. },
+            clearcoat,
+            clearcoat_roughness,
+            transmission: material.transmission(),
+            ior: material.ior(),
         }
     }
     pub fn as_slice(&self) -> &[f32] {
@@ -82,6 +146,9 @@ where
     instantiable: &'a mod3d_base::Instantiable<G>,
     // vaos is 1-to-1 with instantiable::vertices, specific to this shader (class)
     vaos: Vec<G::Vao>,
+    // skinned is 1-to-1 with vaos, and is true if the VAO has both
+    // Joints and Weights attributes bound for the shader
+    skinned: Vec<bool>,
     // The program NEED NOT be borrowed, if the program's uniforms
     // required for the draw are recorded during 'new_vao'
     program: &'a G::Program,
@@ -93,13 +160,18 @@ where
     G: Gl,
 {
     //fi new_vao
+    /// Create a VAO for the vertices, binding the attributes that the
+    /// program requires; also return true if the VAO is skinned (both
+    /// Joints and Weights are bound)
     fn new_vao(
         context: &mut G,
         program: &G::Program,
         vertices: &Vertices<G>,
-    ) -> Result<G::Vao, ()> {
+    ) -> Result<(G::Vao, bool), ()> {
         let (indices, position, attrs) = vertices.borrow();
         let gl_vao = context.vao_create_from_indices(indices)?;
+        let mut has_joints = false;
+        let mut has_weights = false;
         for (index, vertex_attr) in program.attributes() {
             if *vertex_attr == mod3d_base::VertexAttr::Position {
                 position.bind_to_vao_attr(context, index);
@@ -107,66 +179,96 @@ where
                 for (va, buffer) in attrs {
                     if *vertex_attr == *va {
                         buffer.bind_to_vao_attr(context, index);
+                        has_joints |= *va == mod3d_base::VertexAttr::Joints;
+                        has_weights |= *va == mod3d_base::VertexAttr::Weights;
                         // crate::opengl_utils::check_errors().unwrap();
                     }
                 }
             }
         }
         context.bind_vao(None);
-        Ok(gl_vao)
+        Ok((gl_vao, has_joints && has_weights))
     }
 
     //fp new
     /// Create a new [ShaderInstantiable]
+    ///
+    /// This fails if a skinned primitive of the instantiable has more
+    /// than [MAX_BONE_MATRICES] bone matrices in its skin
     pub fn new(
         context: &mut G,
         program: &'a G::Program,
         instantiable: &'a mod3d_base::Instantiable<G>,
     ) -> Result<Self, ()> {
         let mut vaos = Vec::new();
+        let mut skinned = Vec::new();
         for v in &instantiable.vertices {
-            vaos.push(Self::new_vao(context, program, v)?);
+            let (vao, is_skinned) = Self::new_vao(context, program, v)?;
+            vaos.push(vao);
+            skinned.push(is_skinned);
+        }
+        let recipe = &instantiable.render_recipe;
+        for (i, p) in recipe.primitives.iter().enumerate() {
+            let opt_vertices_index: Option<usize> = p.vertices_index().into();
+            if !opt_vertices_index.is_some_and(|vi| skinned[vi]) {
+                continue;
+            }
+            let num_skin_matrices = match &recipe.skin_for_primitives[i] {
+                Some(skin) => skin.len(),
+                None => instantiable.num_bone_matrices,
+            };
+            if num_skin_matrices > MAX_BONE_MATRICES {
+                return Err(());
+            }
         }
         Ok(Self {
             instantiable,
             vaos,
+            skinned,
             program,
         })
     }
 
     //fp gl_draw
     /// Draw this [ShaderInstantiable] given an [mod3d_base::Instance] data
+    ///
+    /// Vertices with morph targets are blended on the CPU for the
+    /// morph weights of the instance, once before any primitive is
+    /// drawn, and only if the weights differ from those last blended
+    ///
+    /// Instanced primitives are drawn with instanced draw calls, with
+    /// their instance matrices in the InstanceMatrices uniform; other
+    /// primitives are drawn with just an identity instance matrix
     pub fn gl_draw(&self, context: &mut G, instance: &mod3d_base::Instance<G>) {
-        // shader camera matrix (already set?)
-        /*
-            // for bone_set_pose in instance.bone_set_poses {
-            //  bone_set_pose.update(tick)
-            // }
-                //for (t,m,b) in self.meshes:
-                //if b>=0:
-                //bma = self.bone_set_poses[b]
-                //program.set_uniform_if("uBonesMatrices",
-                //lambda u:GL.glUniformMatrix4fv(u, bma.max_index, False, bma.data))
-                //program.set_uniform_if("uBonesScale",
-                //lambda u: GL.glUniform1f(u, 1.0) )
-                //pass
-            //else:
-                //program.set_uniform_if("uBonesScale",
-                //lambda u: GL.glUniform1f(u, 0.0) )
-                //pass
-                # Provide mesh matrix and material uniforms
-                program.set_uniform_if("uMeshMatrix",
-                                       lambda u: GL.glUniformMatrix4fv(u, 1, False, t.mat4()) )
-
-        instance bone matrices
-        instance model matrix
-        for (i, p) in render_recipe.primitives.iter().enumerate() {
-         */
+        // The instance bone matrices must have been updated for the
+        // current poses (with Instance::update_bone_matrices)
+        let has_bones = !instance.bone_matrices.is_empty();
+        // The bone matrices of the skin last set in the uniform
+        let mut skin_set: Option<&[mod3d_base::Mat4]> = None;
         context.program_set_uniform_mat4(
             self.program,
             UniformId::ModelMatrix,
             &instance.transformation.mat4(),
         );
+        let identity = [mod3d_base::Transformation::new().mat4()];
+        context.program_set_uniform_mat4_array(
+            self.program,
+            UniformId::InstanceMatrices,
+            &identity,
+        );
+        // Blend the morph targets of each of the vertices once for the
+        // instance, rather than for each primitive that uses them
+        for (vertices, weights) in self
+            .instantiable
+            .vertices
+            .iter()
+            .zip(instance.morph_weights.iter())
+        {
+            if vertices.is_morphed() {
+                vertices.apply_morph_weights(context, weights);
+            }
+        }
+        let mut instances_set = false;
         for (i, p) in self
             .instantiable
             .render_recipe
@@ -182,6 +284,11 @@ where
                     UniformId::Material,
                     mat.base_data().as_slice(),
                 );
+                context.program_set_uniform_floats_4(
+                    self.program,
+                    UniformId::UvTransforms,
+                    mat.uv_transforms(),
+                );
                 for (texture_id, ti) in mat.textures() {
                     if !ti.is_none() {
                         let gl_texture = &self.instantiable.textures[ti.as_usize()];
@@ -190,16 +297,62 @@ where
                 }
             }
 
+            // Only skin primitives whose vertices have joints and weights
+            let opt_vertices_index: Option<usize> = p.vertices_index().into();
+            let is_skinned = has_bones && opt_vertices_index.is_some_and(|vi| self.skinned[vi]);
+            if is_skinned {
+                let skin = self
+                    .instantiable
+                    .render_recipe
+                    .primitive_skin_matrices(i, &instance.bone_matrices);
+                let skin = &skin[0..skin.len().min(MAX_BONE_MATRICES)];
+                if skin_set.is_none_or(|s| !std::ptr::eq(s, skin)) {
+                    context.program_set_uniform_mat4_array(
+                        self.program,
+                        UniformId::BoneMatrices,
+                        skin,
+                    );
+                    skin_set = Some(skin);
+                }
+            }
+            context.program_set_uniform_f32(
+                self.program,
+                UniformId::BoneScale,
+                if is_skinned { 1.0 } else { 0.0 },
+            );
+
             // set MeshMatrix (if different to last)
             // Optimization using mesh uniform buffer
             // Bind a mat4-sized range of the matrices arrays to the Matrix uniform binding point
-            let m = self.instantiable.render_recipe.matrix_for_primitives[i];
-            context.program_set_uniform_mat4(
-                self.program,
-                UniformId::MeshMatrix,
-                &self.instantiable.render_recipe.matrices[m],
-            );
-            context.draw_primitive(&self.vaos, p);
+            //
+            // Primitives attached to a bone follow its animated pose
+            let m = self
+                .instantiable
+                .render_recipe
+                .primitive_matrix(i, &instance.bone_matrices);
+            context.program_set_uniform_mat4(self.program, UniformId::MeshMatrix, &m);
+            let instances = self.instantiable.render_recipe.primitive_instances(i);
+            if instances.is_empty() {
+                if instances_set {
+                    context.program_set_uniform_mat4_array(
+                        self.program,
+                        UniformId::InstanceMatrices,
+                        &identity,
+                    );
+                    instances_set = false;
+                }
+                context.draw_primitive(&self.vaos, p);
+            } else {
+                for chunk in instances.chunks(MAX_INSTANCES_PER_DRAW) {
+                    context.program_set_uniform_mat4_array(
+                        self.program,
+                        UniformId::InstanceMatrices,
+                        chunk,
+                    );
+                    context.draw_primitive_instanced(&self.vaos, p, chunk.len());
+                }
+                instances_set = true;
+            }
         }
     }
 
@@ -97,6 +97,16 @@ pub trait Gl:
         view: &mod3d_base::BufferIndexAccessor<Self>,
     );
 
+    //mp init_buffer_of_floats
+    /// Create an OpenGL ARRAY_BUFFER buffer using DYNAMIC_DRAW - this
+    /// copies the data in to OpenGL, and it may be updated with
+    /// buffer_update_floats
+    fn init_buffer_of_floats(&mut self, buffer: &mut <Self as Gl>::Buffer, data: &[f32]);
+
+    //mp buffer_update_floats
+    /// Update the contents of a buffer created by init_buffer_of_floats
+    fn buffer_update_floats(&mut self, buffer: &<Self as Gl>::Buffer, data: &[f32]);
+
     //mp uniform_buffer_create
     /// Create a uniform buffer (a GlBuffer in the GPU bound to GlUniformBuffer)
     ///
@@ -138,12 +148,17 @@ pub trait Gl:
     /// With the currently bound VAO add this view of the specified
     /// buffer as an attribute of the program, if the program has that
     /// attribute
+    ///
+    /// If normalized then integer elements are normalized to 0.0 to 1.0
+    /// (or -1.0 to 1.0 if signed) when the shader reads them as floats
+    #[allow(clippy::too_many_arguments)]
     fn buffer_bind_to_vao_attr(
         &mut self,
         buffer: &<Self as Gl>::Buffer,
         attr_id: &<<Self as Gl>::Program as GlProgram>::GlAttrId,
         count: u32,
         ele_type: mod3d_base::BufferElementType,
+        normalized: bool,
         byte_offset: u32,
         stride: u32,
     );
@@ -156,6 +171,18 @@ pub trait Gl:
         mat4: &Mat4,
     );
 
+    //mp program_set_uniform_mat4_array
+    /// Set a uniform that is an array of Mat4 (such as bone matrices)
+    fn program_set_uniform_mat4_array(
+        &mut self,
+        program: &Self::Program,
+        id: crate::UniformId,
+        mat4s: &[Mat4],
+    );
+
+    //mp program_set_uniform_f32
+    fn program_set_uniform_f32(&mut self, program: &Self::Program, id: crate::UniformId, v: f32);
+
     //mp program_set_uniform_floats_4
     fn program_set_uniform_floats_4(
         &mut self,
@@ -189,6 +216,16 @@ pub trait Gl:
     /// Draw the specified primitive using its VAO index into the vaos slice
     fn draw_primitive(&mut self, vaos: &[Self::Vao], primitive: &mod3d_base::Primitive);
 
+    //fp draw_primitive_instanced
+    /// Draw the specified primitive using its VAO index into the vaos
+    /// slice, 'instance_count' times with a single instanced draw call
+    fn draw_primitive_instanced(
+        &mut self,
+        vaos: &[Self::Vao],
+        primitive: &mod3d_base::Primitive,
+        instance_count: usize,
+    );
+
     //fp bind_vao
     fn bind_vao(&mut self, vao: Option<&Self::Vao>);
 }
@@ -15,6 +15,14 @@ pub enum UniformId {
     /// The Material data uniform - once per model, and it may have
     /// many forms, but it must start with ShaderMaterialBaseData
     Material,
+    /// The texture coordinate transforms of the material - once per
+    /// model, as an array of vec4, two for each material aspect (see
+    /// [crate::Material::uv_transforms])
+    UvTransforms,
+    /// The instance matrices of an instanced primitive - once per
+    /// draw, as an array of mat4 indexed by the instance (see
+    /// [crate::MAX_INSTANCES_PER_DRAW])
+    InstanceMatrices,
     /// Texure uniform - dependent on the program.
     Texture(TextureId),
     /// User uniform - dependent on the program.
@@ -34,6 +42,8 @@ impl std::str::FromStr for UniformId {
             "BoneScale" => BoneScale,
             "BoneMatrices" => BoneMatrices,
             "Material" => Material,
+            "UvTransforms" => UvTransforms,
+            "InstanceMatrices" => InstanceMatrices,
             _ => Err(format!("Cannot interpret {s} as a UniformID"))?,
         };
         Ok(v)
@@ -59,6 +69,14 @@ pub enum TextureId {
     Emission,
     /// The metallic-roughness texture (as per Gltf)
     MetallicRoughness,
+    /// The clearcoat intensity texture (KHR_materials_clearcoat)
+    Clearcoat,
+    /// The clearcoat roughness texture (KHR_materials_clearcoat)
+    ClearcoatRoughness,
+    /// The clearcoat normal texture (KHR_materials_clearcoat)
+    ClearcoatNormal,
+    /// The transmission texture (KHR_materials_transmission)
+    Transmission,
     /// User 0
     User0,
 }
@@ -73,6 +91,10 @@ impl std::str::FromStr for TextureId {
             "Occlusion" => Occlusion,
             "Emission" => Emission,
             "MetallicRoughness" => MetallicRoughness,
+            "Clearcoat" => Clearcoat,
+            "ClearcoatRoughness" => ClearcoatRoughness,
+            "ClearcoatNormal" => ClearcoatNormal,
+            "Transmission" => Transmission,
             "User0" => User0,
             _ => Err(format!("Cannot interpret {s} as a TextureId"))?,
         };
@@ -90,6 +112,10 @@ impl TextureId {
             MetallicRoughness => Self::MetallicRoughness,
             Occlusion => Self::Occlusion,
             Emission => Self::Emission,
+            Clearcoat => Self::Clearcoat,
+            ClearcoatRoughness => Self::ClearcoatRoughness,
+            ClearcoatNormal => Self::ClearcoatNormal,
+            Transmission => Self::Transmission,
             _ => Self::None,
         }
     }
@@ -1,4 +1,5 @@
 //a Imports
+use std::cell::RefCell;
 use std::rc::Rc;
 
 use crate::{Gl, IndexBuffer, VertexBuffer};
@@ -18,6 +19,12 @@ where
     indices: Rc<IndexBuffer<G>>,
     position: Rc<VertexBuffer<G>>,
     attrs: Rc<Vec<(mod3d_base::VertexAttr, VertexBuffer<G>)>>,
+    /// The attributes that are displaced by morph targets, with the
+    /// dynamic buffers that are used in place of the originals
+    morphs: Rc<Vec<(mod3d_base::MorphedAttr, VertexBuffer<G>)>>,
+    /// The morph target weights last blended into the dynamic buffers,
+    /// if they have been
+    morph_weights: Rc<RefCell<Option<Vec<f32>>>>,
 }
 
 //ip Clone for Vertices
@@ -29,10 +36,14 @@ where
         let indices = self.indices.clone();
         let position = self.position.clone();
         let attrs = self.attrs.clone();
+        let morphs = self.morphs.clone();
+        let morph_weights = self.morph_weights.clone();
         Self {
             indices,
             position,
             attrs,
+            morphs,
+            morph_weights,
         }
     }
 }
@@ -48,7 +59,10 @@ where
     /// Todo: remove unwrap() in position; make it do nothing if there is no position attr
     ///
     /// Todo: support indics being an option
-    pub fn create(vertices: &mod3d_base::Vertices<G>, _renderer: &mut G) -> Self {
+    ///
+    /// Attributes that are displaced by morph targets use dynamic
+    /// buffers that are filled by [Vertices::apply_morph_weights]
+    pub fn create(vertices: &mod3d_base::Vertices<G>, renderer: &mut G) -> Self {
         let indices = vertices
             .borrow_indices()
             .unwrap()
@@ -56,13 +70,12 @@ where
             .as_index_buffer()
             .clone()
             .into();
-        let position = vertices
+        let mut position = vertices
             .borrow_attr(mod3d_base::VertexAttr::Position)
             .unwrap()
             .borrow_client()
             .as_vertex_buffer()
-            .clone()
-            .into();
+            .clone();
         let mut attrs = Vec::new();
         for buffer in vertices.iter_attrs() {
             attrs.push((
@@ -70,13 +83,60 @@ where
                 buffer.borrow_client().as_vertex_buffer().clone(),
             ));
         }
+        let mut morphs = Vec::new();
+        for morphed_attr in vertices.morphed_attrs() {
+            let vertex_attr = morphed_attr.vertex_attr();
+            let buffer =
+                VertexBuffer::of_floats(renderer, morphed_attr.base(), morphed_attr.count() as u32);
+            if vertex_attr == mod3d_base::VertexAttr::Position {
+                position = buffer.clone();
+            }
+            for (va, vb) in attrs.iter_mut() {
+                if *va == vertex_attr {
+                    *vb = buffer.clone();
+                }
+            }
+            morphs.push((morphed_attr, buffer));
+        }
+        let position = position.into();
         let attrs = attrs.into();
+        let morphs = morphs.into();
+        let morph_weights = Rc::new(RefCell::new(None));
         Self {
             indices,
             position,
             attrs,
+            morphs,
+            morph_weights,
+        }
+    }
+
+    //ap is_morphed
+    /// Return true if any attributes are displaced by morph targets
+    pub fn is_morphed(&self) -> bool {
+        !self.morphs.is_empty()
+    }
+
+    //mp apply_morph_weights
+    /// Blend the attributes that are displaced by morph targets for a
+    /// set of weights, and update their buffers
+    ///
+    /// The buffers are shared by all the instances of the vertices, so
+    /// this must be invoked before each instance is drawn; nothing is
+    /// done if the weights are those last blended
+    pub fn apply_morph_weights(&self, renderer: &mut G, weights: &[f32]) {
+        let mut last_weights = self.morph_weights.borrow_mut();
+        if last_weights.as_deref() == Some(weights) {
+            return;
+        }
+        let mut data = Vec::new();
+        for (morphed_attr, buffer) in self.morphs.iter() {
+            morphed_attr.blend(weights, &mut data);
+            buffer.update_floats(renderer, &data);
         }
+        *last_weights = Some(weights.to_vec());
     }
+
     //fp borrow
     /// Borrow the indices, positions, and the array of other attributes
     pub fn borrow(
@@ -111,10 +171,14 @@ where
         let indices = IndexBuffer::default().into();
         let position = VertexBuffer::default().into();
         let attrs = Vec::new().into();
+        let morphs = Vec::new().into();
+        let morph_weights = Rc::new(RefCell::new(None));
         Self {
             indices,
             position,
             attrs,
+            morphs,
+            morph_weights,
         }
     }
 }
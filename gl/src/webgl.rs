@@ -18,6 +18,22 @@ mod texture;
 mod vao;
 use vao::Vao;
 
+//a Functions
+//fi gl_primitive_type
+/// Get the GL primitive type for a [mod3d_base::PrimitiveType]
+fn gl_primitive_type(primitive_type: mod3d_base::PrimitiveType) -> u32 {
+    use mod3d_base::PrimitiveType::*;
+    match primitive_type {
+        Points => WebGl2RenderingContext::POINTS,
+        Lines => WebGl2RenderingContext::LINES,
+        LineLoop => WebGl2RenderingContext::LINE_LOOP,
+        LineStrip => WebGl2RenderingContext::LINE_STRIP,
+        Triangles => WebGl2RenderingContext::TRIANGLES,
+        TriangleFan => WebGl2RenderingContext::TRIANGLE_FAN,
+        TriangleStrip => WebGl2RenderingContext::TRIANGLE_STRIP,
+    }
+}
+
 //a Model3DWebGL
 //tp Model3DWebGL
 #[derive(Debug)]
@@ -114,6 +130,16 @@ impl Gl for Model3DWebGL {
         buffer.of_indices(view, self);
     }
 
+    //mp init_buffer_of_floats
+    fn init_buffer_of_floats(&mut self, buffer: &mut <Self as Gl>::Buffer, data: &[f32]) {
+        buffer.of_floats(data, self);
+    }
+
+    //mp buffer_update_floats
+    fn buffer_update_floats(&mut self, buffer: &<Self as Gl>::Buffer, data: &[f32]) {
+        buffer.update_floats(data, self);
+    }
+
     //mp vao_create_from_indices
     fn vao_create_from_indices(&mut self, indices: &crate::IndexBuffer<Self>) -> Result<Vao, ()> {
         Vao::create_from_indices(self, indices)
@@ -126,10 +152,19 @@ impl Gl for Model3DWebGL {
         attr_id: &<Program as GlProgram>::GlAttrId,
         count: u32,
         ele_type: mod3d_base::BufferElementType,
+        normalized: bool,
         byte_offset: u32,
         stride: u32,
     ) {
-        buffer.bind_to_vao_attr(self, *attr_id, count, ele_type, byte_offset, stride);
+        buffer.bind_to_vao_attr(
+            self,
+            *attr_id,
+            count,
+            ele_type,
+            normalized,
+            byte_offset,
+            stride,
+        );
     }
 
     //mp program_set_uniform_mat4
@@ -141,6 +176,29 @@ impl Gl for Model3DWebGL {
         }
     }
 
+    //fp program_set_uniform_mat4_array
+    fn program_set_uniform_mat4_array(
+        &mut self,
+        program: &Self::Program,
+        id: crate::UniformId,
+        mat4s: &[Mat4],
+    ) {
+        console_log!("webgl: set uniform [mat4; {}] {id:?}", mat4s.len());
+        if let Some(u) = program.uniform(id) {
+            let floats = mat4s.as_flattened();
+            self.context
+                .uniform_matrix4fv_with_f32_array(Some(u), false, floats);
+        }
+    }
+
+    //fp program_set_uniform_f32
+    fn program_set_uniform_f32(&mut self, program: &Self::Program, id: crate::UniformId, v: f32) {
+        console_log!("webgl: set uniform [f32] {id:?} {v}");
+        if let Some(u) = program.uniform(id) {
+            self.context.uniform1f(Some(u), v);
+        }
+    }
+
     //fp program_set_uniform_floats_4
     fn program_set_uniform_floats_4(
         &mut self,
@@ -191,16 +249,7 @@ impl Gl for Model3DWebGL {
     //mp draw_primitive
     fn draw_primitive(&mut self, vaos: &[Vao], primitive: &mod3d_base::Primitive) {
         console_log!("webgl: draw_primitive {primitive:?}");
-        use mod3d_base::PrimitiveType::*;
-        let gl_type = match primitive.primitive_type() {
-            Points => WebGl2RenderingContext::POINTS,
-            Lines => WebGl2RenderingContext::LINES,
-            LineLoop => WebGl2RenderingContext::LINE_LOOP,
-            LineStrip => WebGl2RenderingContext::LINE_STRIP,
-            Triangles => WebGl2RenderingContext::TRIANGLES,
-            TriangleFan => WebGl2RenderingContext::TRIANGLE_FAN,
-            TriangleStrip => WebGl2RenderingContext::TRIANGLE_STRIP,
-        };
+        let gl_type = gl_primitive_type(primitive.primitive_type());
         let opt_vertices_index: Option<usize> = primitive.vertices_index().into();
         if let Some(vertices_index) = opt_vertices_index {
             let index_type = vaos[vertices_index].bind_vao(self);
@@ -219,6 +268,35 @@ impl Gl for Model3DWebGL {
         }
     }
 
+    //fp draw_primitive_instanced
+    fn draw_primitive_instanced(
+        &mut self,
+        vaos: &[Vao],
+        primitive: &mod3d_base::Primitive,
+        instance_count: usize,
+    ) {
+        console_log!("webgl: draw_primitive_instanced {primitive:?} {instance_count}");
+        let gl_type = gl_primitive_type(primitive.primitive_type());
+        let opt_vertices_index: Option<usize> = primitive.vertices_index().into();
+        if let Some(vertices_index) = opt_vertices_index {
+            let index_type = vaos[vertices_index].bind_vao(self);
+            self.draw_elements_instanced_with_i32(
+                gl_type,
+                primitive.index_count() as i32,
+                index_type,
+                primitive.byte_offset() as i32,
+                instance_count as i32,
+            );
+        } else {
+            self.draw_arrays_instanced(
+                gl_type,
+                primitive.byte_offset() as i32,
+                primitive.index_count() as i32,
+                instance_count as i32,
+            );
+        }
+    }
+
     //mp bind_vao
     fn bind_vao(&mut self, vao: Option<&Self::Vao>) {
         if let Some(vao) = vao {
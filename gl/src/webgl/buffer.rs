@@ -89,6 +89,51 @@ impl Buffer {
         );
     }
 
+    //mp of_floats
+    /// Create an OpenGL ARRAY_BUFFER buffer using DYNAMIC_DRAW - this
+    /// copies the data in to OpenGL, and it may be updated with
+    /// update_floats
+    pub fn of_floats(&mut self, data: &[f32], render_context: &Model3DWebGL) {
+        assert!(self.is_none());
+        let gl = render_context.create_buffer().unwrap();
+        render_context.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&gl));
+        unsafe {
+            let buf_view = js_sys::Float32Array::view(data);
+            render_context.buffer_data_with_array_buffer_view(
+                WebGl2RenderingContext::ARRAY_BUFFER,
+                &buf_view,
+                WebGl2RenderingContext::DYNAMIC_DRAW,
+            );
+        }
+        render_context.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, None);
+        *self.gl.borrow_mut() = Some(gl);
+        log_gl_buffer(
+            render_context,
+            self.gl.borrow().as_ref(),
+            &format!("Buffer:of_floats {self}"),
+            WebGl2RenderingContext::ARRAY_BUFFER,
+            0,
+            0,
+        );
+    }
+
+    //mp update_floats
+    /// Update the contents of an ARRAY_BUFFER created by of_floats
+    pub fn update_floats(&self, data: &[f32], render_context: &Model3DWebGL) {
+        let gl_buffer_ref = self.gl.borrow();
+        let gl_buffer = gl_buffer_ref.as_ref();
+        render_context.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, gl_buffer);
+        unsafe {
+            let buf_view = js_sys::Float32Array::view(data);
+            render_context.buffer_sub_data_with_i32_and_array_buffer_view(
+                WebGl2RenderingContext::ARRAY_BUFFER,
+                0,
+                &buf_view,
+            );
+        }
+        render_context.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, None);
+    }
+
     //mp of_indices
     /// Create the OpenGL ELEMENT_ARRAY_BUFFER buffer using STATIC_DRAW - this copies the data in to OpenGL
     pub fn of_indices(
@@ -137,12 +182,14 @@ impl Buffer {
 
     //fp bind_to_vao_attr
     /// Bind the buffer as a vertex attribute to the current VAO
+    #[allow(clippy::too_many_arguments)]
     pub fn bind_to_vao_attr(
         &self,
         render_context: &Model3DWebGL,
         attr_id: <Program as GlProgram>::GlAttrId,
         count: u32,
         ele_type: mod3d_base::BufferElementType,
+        normalized: bool,
         byte_offset: u32,
         stride: u32,
     ) {
@@ -151,11 +198,11 @@ impl Buffer {
             match ele_type {
                 Float32 => WebGl2RenderingContext::FLOAT,
                 Float16 => WebGl2RenderingContext::HALF_FLOAT,
-                UInt8 => WebGl2RenderingContext::BYTE,
+                UInt8 => WebGl2RenderingContext::UNSIGNED_BYTE,
                 SInt8 => WebGl2RenderingContext::BYTE,
-                UInt16 => WebGl2RenderingContext::SHORT,
+                UInt16 => WebGl2RenderingContext::UNSIGNED_SHORT,
                 SInt16 => WebGl2RenderingContext::SHORT,
-                UInt32 => WebGl2RenderingContext::INT,
+                UInt32 => WebGl2RenderingContext::UNSIGNED_INT,
                 SInt32 => WebGl2RenderingContext::INT,
             }
         };
@@ -167,7 +214,7 @@ impl Buffer {
         log_gl_buffer(
             render_context,
             self.gl.borrow().as_ref(),
-            &format!("Buffer:bind_to_vao_attr {attr_id}, #{count}, {ele_type:?}, {stride}, {byte_offset}"),
+            &format!("Buffer:bind_to_vao_attr {attr_id}, #{count}, {ele_type:?}, {normalized}, {stride}, {byte_offset}"),
             0,
             0,
             0, // Do not output the buffer data
@@ -176,7 +223,7 @@ impl Buffer {
             attr_id,
             count as i32,
             ele_type,
-            false,
+            normalized,
             stride as i32,
             byte_offset as i32,
         );
@@ -6,6 +6,32 @@ use mod3d_base::TextureClient;
 use super::Model3DWebGL;
 use web_sys::{WebGl2RenderingContext, WebGlTexture};
 
+//a Sampler mapping
+//fi gl_wrap
+/// Map a [mod3d_base::TextureWrap] to a WebGl wrap mode
+fn gl_wrap(wrap: mod3d_base::TextureWrap) -> u32 {
+    use mod3d_base::TextureWrap::*;
+    match wrap {
+        Repeat => WebGl2RenderingContext::REPEAT,
+        MirroredRepeat => WebGl2RenderingContext::MIRRORED_REPEAT,
+        ClampToEdge => WebGl2RenderingContext::CLAMP_TO_EDGE,
+    }
+}
+
+//fi gl_filter
+/// Map a [mod3d_base::TextureFilter] to a WebGl filter
+fn gl_filter(filter: mod3d_base::TextureFilter) -> u32 {
+    use mod3d_base::TextureFilter::*;
+    match filter {
+        Nearest => WebGl2RenderingContext::NEAREST,
+        Linear => WebGl2RenderingContext::LINEAR,
+        NearestMipmapNearest => WebGl2RenderingContext::NEAREST_MIPMAP_NEAREST,
+        LinearMipmapNearest => WebGl2RenderingContext::LINEAR_MIPMAP_NEAREST,
+        NearestMipmapLinear => WebGl2RenderingContext::NEAREST_MIPMAP_LINEAR,
+        LinearMipmapLinear => WebGl2RenderingContext::LINEAR_MIPMAP_LINEAR,
+    }
+}
+
 //a Texture
 //tp Texture
 /// A simple structure provides a reference-counted OpenGl texture;
@@ -87,26 +113,30 @@ impl Texture {
                 Some(texture.data()),
             );
 
+        let sampler = texture.sampler();
         render_context.tex_parameteri(
             WebGl2RenderingContext::TEXTURE_2D,
             WebGl2RenderingContext::TEXTURE_WRAP_S,
-            WebGl2RenderingContext::REPEAT as i32,
+            gl_wrap(sampler.wrap_s) as i32,
         );
         render_context.tex_parameteri(
             WebGl2RenderingContext::TEXTURE_2D,
             WebGl2RenderingContext::TEXTURE_WRAP_T,
-            WebGl2RenderingContext::REPEAT as i32,
+            gl_wrap(sampler.wrap_t) as i32,
         );
         render_context.tex_parameteri(
             WebGl2RenderingContext::TEXTURE_2D,
             WebGl2RenderingContext::TEXTURE_MIN_FILTER,
-            WebGl2RenderingContext::LINEAR as i32,
+            gl_filter(sampler.min_filter) as i32,
         );
         render_context.tex_parameteri(
             WebGl2RenderingContext::TEXTURE_2D,
             WebGl2RenderingContext::TEXTURE_MAG_FILTER,
-            WebGl2RenderingContext::LINEAR as i32,
+            gl_filter(sampler.mag_filter.without_mipmaps()) as i32,
         );
+        if sampler.min_filter.uses_mipmaps() {
+            render_context.generate_mipmap(WebGl2RenderingContext::TEXTURE_2D);
+        }
         render_context.bind_texture(WebGl2RenderingContext::TEXTURE_2D, None);
 
         Self {
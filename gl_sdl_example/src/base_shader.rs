@@ -5,7 +5,10 @@ const VERT_SRC: &str = "
 // Must match ShaderMaterialBaseData in model3d-gltf
 struct ShaderMaterialBaseData {
     vec4 base_color;
-    vec4 mrxx;
+    vec4 mr_occlusion_normal; // metallic, roughness, occlusion strength, normal scale
+    vec4 emissive_cutoff;     // emissive RGB, alpha cutoff
+    vec4 alpha_etc;           // alpha mode, double-sided, texture coordinate sets, unlit
+    vec4 clearcoat_etc;       // clearcoat, clearcoat roughness, transmission, ior
 };
 
 struct Light { // 32 bytes
@@ -32,14 +35,16 @@ layout(std140) uniform World {
 };
 uniform mat4 uModelMatrix;
 uniform mat4 uMeshMatrix;
+// Must be at least MAX_INSTANCES_PER_DRAW in size; the identity if not instanced
+uniform mat4 uInstanceMatrices[16];
 uniform sampler2D BaseTexture;
 // uniform ShaderMaterialBaseData Material;
 
 void main()
 {
-    World_position = uModelMatrix * uMeshMatrix * vec4(Position, 1.);
+    World_position = uModelMatrix * uMeshMatrix * uInstanceMatrices[gl_InstanceID] * vec4(Position, 1.);
     gl_Position = world.view_matrix * World_position;
-    Normal_frag = (uModelMatrix * uMeshMatrix * vec4(Normal, 0.)).xyz;
+    Normal_frag = (uModelMatrix * uMeshMatrix * uInstanceMatrices[gl_InstanceID] * vec4(Normal, 0.)).xyz;
     Material_frag = TexCoord;
 }
 ";
@@ -65,7 +70,7 @@ in vec2 Material_frag;
 out vec4 Color;
 uniform sampler2D BaseTexture;
 uniform sampler2D EmissionTexture;
-uniform vec4[2] Material;
+uniform vec4[5] Material;
 
 layout(std140) uniform World {
     WorldData world;
@@ -116,6 +121,7 @@ pub fn compile_shader_program<G: Gl>(model3d: &G) -> Result<<G as Gl>::Program,
         &[
             ("uModelMatrix", model3d_gl::UniformId::ModelMatrix),
             ("uMeshMatrix", model3d_gl::UniformId::MeshMatrix),
+            ("uInstanceMatrices", model3d_gl::UniformId::InstanceMatrices),
             ("Material", model3d_gl::UniformId::Material),
         ],
         &[("World", 2)],
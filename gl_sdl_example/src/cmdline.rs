@@ -28,7 +28,7 @@ pub fn add_node_arg(cmd: Command) -> Command {
         Arg::new("node")
             .long("node")
             .short('n')
-            .help("Node to view")
+            .help("Node to view; if none are given, the default scene of the file is viewed")
             .action(ArgAction::Append),
     )
 }
@@ -38,16 +38,28 @@ pub fn add_scale_arg(cmd: Command) -> Command {
         Arg::new("scale")
             .long("scale")
             .short('S')
-            .default_value("1")
-            .help("Scale factor to apply to object")
+            .help("Scale factor to apply to object; if not given, the object is scaled to fit the view")
             .value_parser(value_parser!(f32))
             .action(ArgAction::Set),
     )
 }
 
-pub fn scale(matches: &ArgMatches) -> f32 {
-    *matches.get_one::<f32>("scale").unwrap()
+pub fn add_camera_arg(cmd: Command) -> Command {
+    cmd.arg(
+        Arg::new("camera")
+            .long("camera")
+            .short('c')
+            .help("View through the first camera of the scene rather than spinning the object")
+            .action(ArgAction::SetTrue),
+    )
+}
+
+pub fn scale(matches: &ArgMatches) -> Option<f32> {
+    matches.get_one::<f32>("scale").copied()
 }
 pub fn shader(matches: &ArgMatches) -> String {
     matches.get_one::<String>("shader").unwrap().to_owned()
 }
+pub fn camera(matches: &ArgMatches) -> bool {
+    matches.get_flag("camera")
+}
@@ -20,10 +20,12 @@ fn main() -> Result<(), anyhow::Error> {
     let cmd = cmdline::add_glb_arg(cmd);
     let cmd = cmdline::add_node_arg(cmd);
     let cmd = cmdline::add_scale_arg(cmd);
+    let cmd = cmdline::add_camera_arg(cmd);
 
     let matches = cmd.get_matches();
 
     let scale = cmdline::scale(&matches);
+    let use_camera = cmdline::camera(&matches);
     let shader_filename = cmdline::shader(&matches);
 
     let glb_filename = matches.get_one::<String>("glb").unwrap();
@@ -32,8 +34,6 @@ fn main() -> Result<(), anyhow::Error> {
         for v in values {
             node_names.push(v.to_string());
         }
-    } else {
-        node_names.push("0".to_string());
     }
     let node_name_refs: Vec<&str> = node_names.iter().map(|s| &**s).collect();
 
@@ -45,10 +45,23 @@ fn main() -> Result<(), anyhow::Error> {
     let shader_program =
         shader_program::create_shader_program(&mut model3d, &shader_filename, &shader_paths)?;
 
-    let base =
-        model::Base::new(&mut model3d, shader_program, glb_filename, &node_name_refs).unwrap();
+    let base = model::Base::new(
+        &mut model3d,
+        shader_program,
+        glb_filename,
+        &node_name_refs,
+        use_camera,
+    )
+    .unwrap();
     let instantiables = base.make_instantiable(&mut model3d).unwrap();
+    let scale = scale.or_else(|| base.fit_scale()).unwrap_or(1.);
     let mut game_state = model::GameState::new(scale);
+    if !base.lights().is_empty() {
+        game_state.set_lights(base.lights());
+    }
+    if let Some(camera) = base.camera() {
+        game_state.set_camera(camera, sdl_window.aspect_ratio());
+    }
     let mut instances = base.make_instances();
 
     sdl_window.prepare_viewport();
@@ -59,6 +72,9 @@ fn main() -> Result<(), anyhow::Error> {
             types::Event::Quit => break 'main,
             types::Event::ResizeWindow(w, h) => {
                 sdl_window.resize_viewport(0, 0, w, h);
+                if let Some(camera) = base.camera() {
+                    game_state.set_camera(camera, w as f32 / h.max(1) as f32);
+                }
                 continue 'main;
             }
             _ => (),
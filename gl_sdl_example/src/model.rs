@@ -1,22 +1,15 @@
 //a Imports
 use mod3d_base::Instance;
-use mod3d_gl::{Gl, ShaderInstantiable, UniformBuffer};
+use mod3d_gl::{Gl, ShaderInstantiable, ShaderLight, UniformBuffer};
 
 use crate::objects;
 
-//a Light, WorldData
-#[derive(Debug, Default)]
-#[repr(C)]
-pub struct Light {
-    position: mod3d_gl::Vec4,
-    color: mod3d_gl::Vec4,
-}
-
+//a WorldData
 #[derive(Debug, Default)]
 #[repr(C)]
 pub struct WorldData {
     view_matrix: mod3d_gl::Mat4,
-    lights: [Light; 4],
+    lights: [ShaderLight; 4],
 }
 
 //a Base
@@ -28,6 +21,12 @@ pub struct Base<G: Gl> {
     shader_program: G::Program,
     /// Uniform buffers
     world_gl: UniformBuffer<G>,
+    /// The camera from the file to view through, if any
+    camera: Option<mod3d_base::Camera>,
+    /// The lights of the scene in the file
+    lights: Vec<mod3d_base::Light>,
+    /// The animations of the skeleton of the objects
+    animations: Vec<mod3d_base::AnimationClip>,
 }
 
 //tp Instantiable
@@ -54,6 +53,7 @@ impl<G: Gl> Base<G> {
         shader_program: G::Program,
         filename: &str,
         node_names: &[&str],
+        use_camera: bool,
     ) -> Result<Self, String> {
         // Use uniform binding point 1 for the material
         //
@@ -71,14 +71,37 @@ impl<G: Gl> Base<G> {
         gl.uniform_index_of_range(&world_gl, world_uid, 0, 0);
         let _ = gl.program_bind_uniform_index(&shader_program, 2, world_uid);
 
-        let objects = objects::new(gl, filename, node_names)?;
+        let (objects, camera, lights, animations) =
+            objects::new(gl, filename, node_names, use_camera)?;
         Ok(Self {
             objects,
             shader_program,
             world_gl,
+            camera,
+            lights,
+            animations,
         })
     }
 
+    //ap lights
+    pub fn lights(&self) -> &[mod3d_base::Light] {
+        &self.lights
+    }
+
+    //ap camera
+    pub fn camera(&self) -> Option<&mod3d_base::Camera> {
+        self.camera.as_ref()
+    }
+
+    //ap fit_scale
+    /// The scale that keeps the object within the view as it spins
+    /// about its origin, if it has any bounds
+    pub fn fit_scale(&self) -> Option<f32> {
+        let bounds = self.objects.bounds?;
+        let extent = geo_nd::vector::length(&bounds.center()) + bounds.radius();
+        (extent > 0.).then(|| 0.9 / extent)
+    }
+
     //fp make_instantiable
     pub fn make_instantiable<'inst>(
         &'inst self,
@@ -106,10 +129,25 @@ impl<G: Gl> Base<G> {
         // view_transformation.rotate_by(&spin);
         // world_data[0].view_matrix = view_transformation.mat4();
 
+        // Pose the skeleton with the first animation (if any), and
+        // update the bone matrices for this frame
+        if let Some(clip) = self.animations.first() {
+            let t = game_state.time % clip.duration().max(f32::EPSILON);
+            for pose in instances.instance.bone_poses.iter_mut() {
+                clip.apply_to_pose(t, pose);
+            }
+        }
+        game_state.tick += 1;
+        instances.instance.update_bone_matrices(game_state.tick);
+
         gl.uniform_buffer_update_data(&self.world_gl, &game_state.world_data, 0);
         gl.use_program(Some(&self.shader_program));
         instantiable.instantiables.gl_draw(gl, &instances.instance);
 
+        if self.camera.is_some() {
+            game_state.time += 0.015;
+            return;
+        }
         use geo_nd::quat;
         game_state.spin_axis = quat::apply3(&game_state.axis_spin, &game_state.spin_axis);
         let spin = geo_nd::quat::of_axis_angle(&game_state.spin_axis, 0.01);
@@ -125,6 +163,7 @@ impl<G: Gl> Base<G> {
 pub struct GameState {
     world_data: [WorldData; 1],
     time: f32,
+    tick: usize,
     axis_spin: mod3d_base::Quat,
     spin_axis: mod3d_base::Vec3,
 }
@@ -143,20 +182,37 @@ impl GameState {
 
         let distant = 0.8;
         let ambient = 0.3;
-        world_data[0].lights[0].position = [5., 10., 0., 0.1];
-        world_data[0].lights[0].color = [1., 0.4, 0.4, 0.];
-        world_data[0].lights[1].position = [-1., 0., 0., 0.1];
-        world_data[0].lights[1].color = [0.4, 1., 0.3, 0.];
-        world_data[0].lights[2].position = [-1., 0., 0., -1.];
-        world_data[0].lights[2].color = [distant, distant, distant, 0.];
-        world_data[0].lights[3].position = [0., 0., 0., 0.];
-        world_data[0].lights[3].color = [ambient, ambient, ambient, 0.];
+        world_data[0].lights[0] = ShaderLight::new([5., 10., 0., 0.1], [1., 0.4, 0.4, 0.]);
+        world_data[0].lights[1] = ShaderLight::new([-1., 0., 0., 0.1], [0.4, 1., 0.3, 0.]);
+        world_data[0].lights[2] =
+            ShaderLight::new([-1., 0., 0., -1.], [distant, distant, distant, 0.]);
+        world_data[0].lights[3] = ShaderLight::ambient([ambient, ambient, ambient]);
 
         Self {
             world_data,
             time,
+            tick: 0,
             axis_spin,
             spin_axis,
         }
     }
+
+    //mp set_lights
+    /// Replace the default lights with those of the scene, keeping an
+    /// ambient light
+    pub fn set_lights(&mut self, lights: &[mod3d_base::Light]) {
+        let ambient = 0.3;
+        ShaderLight::fill(
+            &mut self.world_data[0].lights,
+            lights,
+            [ambient, ambient, ambient],
+        );
+    }
+
+    //mp set_camera
+    /// View the world through a camera, with the aspect ratio of the
+    /// viewport
+    pub fn set_camera(&mut self, camera: &mod3d_base::Camera, aspect_ratio: f32) {
+        self.world_data[0].view_matrix = camera.view_projection_matrix(aspect_ratio);
+    }
 }
@@ -23,9 +23,18 @@ pub fn new<G: Gl>(
     render_context: &mut G,
     filename: &str,
     node_names: &[&str],
-) -> Result<mod3d_base::Instantiable<G>, String> {
+    use_camera: bool,
+) -> Result<
+    (
+        mod3d_base::Instantiable<G>,
+        Option<mod3d_base::Camera>,
+        Vec<mod3d_base::Light>,
+        Vec<mod3d_base::AnimationClip>,
+    ),
+    String,
+> {
     fn buf_reader(
-        file: &mut std::fs::File,
+        file: &mut std::io::Take<&mut std::fs::File>,
         byte_length: usize,
     ) -> Result<Option<Vec<u8>>, std::io::Error> {
         use std::io::Read;
@@ -33,19 +42,56 @@ pub fn new<G: Gl>(
         file.read_exact(&mut buffer)?;
         Ok(Some(buffer))
     }
-    let mut file = std::fs::File::open(filename).map_err(|e| format!("{e:?}"))?;
-    let (mut gltf, opt_buffer_0) = mod3d_gltf::glb_load(&mut file, &buf_reader, 16 * 1000 * 1000)
-        .map_err(|e| format!("{e:?}"))?;
+    // A .gltf file is Json, with URIs relative to the file; anything
+    // else is expected to be a GLB file
+    let resolver = mod3d_gltf::FileUriResolver::of_gltf_path(filename);
+    let (mut gltf, opt_buffer_0) = {
+        if filename.ends_with(".gltf") {
+            let file = std::fs::File::open(filename).map_err(|e| format!("{e:?}"))?;
+            let json_value: serde_json::Value =
+                serde_json::from_reader(std::io::BufReader::new(file))
+                    .map_err(|e| format!("{e:?}"))?;
+            let gltf = mod3d_gltf::Gltf::of_json_value(json_value).map_err(|e| format!("{e:?}"))?;
+            (gltf, None)
+        } else {
+            let mut file = std::fs::File::open(filename).map_err(|e| format!("{e:?}"))?;
+            mod3d_gltf::glb_load(&mut file, &buf_reader, 16 * 1000 * 1000)
+                .map_err(|e| format!("{e:?}"))?
+        }
+    };
 
+    // With no node names the whole of the default scene is used,
+    // falling back to node 0 if the file has no scenes
     let mut od = mod3d_gltf::ObjectData::new(&gltf);
     for n in node_names {
         od.add_object(&gltf, gltf.get_node(n).unwrap());
     }
+    if node_names.is_empty() && od.add_default_scene(&gltf).is_none() {
+        od.add_object(&gltf, 0.into());
+    }
 
     od.derive_uses(&gltf);
 
+    // Use the first camera of the default scene (or scene 0), if
+    // required, and the lights of that scene
+    let mut camera = None;
+    let mut lights = vec![];
+    if let Some(scene) = gltf.default_scene() {
+        if use_camera {
+            if let Some(node) = gltf.scene_camera_nodes(scene).first() {
+                camera = gltf.camera_of_node(*node);
+            }
+        }
+        lights = gltf.scene_lights(scene);
+    }
+
     let buffers = od
-        .gen_byte_buffers(&mut gltf, &mod3d_gltf::buf_parse_fail, opt_buffer_0)
+        .gen_resolved_buffers(&mut gltf, &resolver, opt_buffer_0)
+        .map_err(|e| format!("{e:?}"))?;
+    od.gen_skins(&gltf, &|x| &buffers[x])
+        .map_err(|e| format!("{e:?}"))?;
+    let animations = od
+        .gen_animations(&gltf, &|x| &buffers[x], &|n| od.bones_of_node(n))
         .map_err(|e| format!("{e:?}"))?;
 
     let buffer_data = od.gen_buffer_data::<_, _, G>(&|x| &buffers[x]);
@@ -63,31 +109,21 @@ pub fn new<G: Gl>(
     use image::ImageFormat;
     use image::ImageReader;
     use std::io::Cursor;
-    fn image_load(
-        (buffer_index, byte_offset, byte_length): (usize, usize, usize),
-        uri_or_type: &str,
-        buffers: &[Vec<u8>],
-    ) -> Result<DynamicImage, String> {
-        eprintln!("Load image {buffer_index} {byte_offset} {byte_length} {uri_or_type}");
-        if byte_length == 0 {
-            Err(format!("Cannot load image from file yet {uri_or_type}"))
-        } else {
-            assert!(buffer_index < buffers.len());
-            let buffer =
-                Cursor::new(&buffers[buffer_index][byte_offset..byte_offset + byte_length]);
-            let reader = match uri_or_type {
-                "image/jpeg" => ImageReader::with_format(buffer, ImageFormat::Jpeg),
-                "image/png" => ImageReader::with_format(buffer, ImageFormat::Png),
-                _ => return Err(format!("Unknown image format {uri_or_type}")),
-            };
-            let image = reader
-                .decode()
-                .map_err(|e| format!("Failed to parse image buffer: {e}"))?;
-            Ok(image)
-        }
+    fn image_load(data: &[u8], mime_type: &str) -> Result<DynamicImage, String> {
+        eprintln!("Load image of {} bytes {mime_type}", data.len());
+        let buffer = Cursor::new(data);
+        let reader = match mime_type {
+            "image/jpeg" => ImageReader::with_format(buffer, ImageFormat::Jpeg),
+            "image/png" => ImageReader::with_format(buffer, ImageFormat::Png),
+            _ => return Err(format!("Unknown image format {mime_type}")),
+        };
+        let image = reader
+            .decode()
+            .map_err(|e| format!("Failed to parse image buffer: {e}"))?;
+        Ok(image)
     }
     let images = od
-        .gen_images(&gltf, &|b, u| image_load(b, u, &buffers))
+        .gen_resolved_images(&gltf, &resolver, &|x| &buffers[x], &image_load)
         .map_err(|e| format!("Failed to parse image buffer: {e}"))?;
     fn texture_of_image<'textures, G>(
         image: &'textures image::DynamicImage,
@@ -124,5 +160,10 @@ pub fn new<G: Gl>(
     let mut obj = od.gen_object(&gltf, &vertices, &textures, &materials);
 
     obj.analyze();
-    Ok(obj.into_instantiable(render_context))
+    Ok((
+        obj.into_instantiable(render_context),
+        camera,
+        lights,
+        animations,
+    ))
 }
@@ -60,6 +60,12 @@ impl SdlWindow {
         mod3d_gl::opengl_utils::check_errors().unwrap();
     }
 
+    //mp aspect_ratio
+    pub fn aspect_ratio(&self) -> f32 {
+        let (w, h) = self.window.drawable_size();
+        w as f32 / h.max(1) as f32
+    }
+
     //mp resize_viewport
     pub fn resize_viewport(&self, x: isize, y: isize, w: usize, h: usize) {
         unsafe { gl::Viewport(x as i32, y as i32, w as i32, h as i32) };
@@ -32,6 +32,8 @@ pub struct Base<G: Gl> {
     material_gl: UniformBuffer<G>,
     /// World
     world_gl: UniformBuffer<G>,
+    /// The animations of the skeleton of the objects
+    animations: Vec<mod3d_base::AnimationClip>,
 }
 
 //tp Instantiable
@@ -98,12 +100,13 @@ impl<G: Gl> Base<G> {
         let glb = files
             .get(filename)
             .ok_or_else(|| format!("Failed to find GLB file {filename}"))?;
-        let objects = { objects::new_of_glb(gl, glb, node_names).unwrap() };
+        let (objects, animations) = objects::new_of_glb(gl, glb, node_names)?;
         Ok(Self {
             objects,
             shader_program,
             material_gl,
             world_gl,
+            animations,
         })
     }
 
@@ -135,6 +138,17 @@ impl<G: Gl> Base<G> {
         // view_transformation.rotate_by(&spin);
         // world_data[0].view_matrix = view_transformation.mat4();
 
+        // Pose the skeleton with the first animation (if any), and
+        // update the bone matrices for this frame
+        if let Some(clip) = self.animations.first() {
+            let t = game_state.time % clip.duration().max(f32::EPSILON);
+            for pose in instances.instance.bone_poses.iter_mut() {
+                clip.apply_to_pose(t, pose);
+            }
+        }
+        game_state.tick += 1;
+        instances.instance.update_bone_matrices(game_state.tick);
+
         gl.uniform_buffer_update_data(&self.world_gl, &game_state.world_data, 0);
         gl.use_program(Some(&self.shader_program));
         instantiable.instantiables.gl_draw(gl, &instances.instance);
@@ -159,6 +173,7 @@ impl<G: Gl> Base<G> {
 pub struct GameState {
     world_data: [WorldData; 1],
     time: f32,
+    tick: usize,
     #[allow(dead_code)]
     view_transformation: mod3d_base::Transformation,
     spin: mod3d_base::Quat,
@@ -191,6 +206,7 @@ impl GameState {
         Self {
             world_data,
             time,
+            tick: 0,
             view_transformation,
             spin,
         }
@@ -8,11 +8,14 @@ pub fn new_of_glb<G: Gl>(
     render_context: &mut G,
     glb: &[u8],
     node_names: &[&str],
-) -> Result<mod3d_base::Instantiable<G>, String> {
-    fn buf_reader(file: &mut &[u8], byte_length: usize) -> Result<Option<Vec<u8>>, std::io::Error> {
+) -> Result<(mod3d_base::Instantiable<G>, Vec<mod3d_base::AnimationClip>), String> {
+    fn buf_reader(
+        file: &mut std::io::Take<&mut &[u8]>,
+        byte_length: usize,
+    ) -> Result<Option<Vec<u8>>, std::io::Error> {
         use std::io::Read;
         let mut buffer = vec![0; byte_length];
-        crate::console_log!("file {} {}", file.len(), byte_length);
+        crate::console_log!("file {} {}", file.get_ref().len(), byte_length);
         file.read_exact(&mut buffer).unwrap();
         Ok(Some(buffer))
     }
@@ -31,6 +34,11 @@ pub fn new_of_glb<G: Gl>(
     let buffers = od
         .gen_byte_buffers(&mut gltf, &mod3d_gltf::buf_parse_fail, opt_buffer_0)
         .map_err(|e| format!("{e:?}"))?;
+    od.gen_skins(&gltf, &|x| &buffers[x])
+        .map_err(|e| format!("{e:?}"))?;
+    let animations = od
+        .gen_animations(&gltf, &|x| &buffers[x], &|n| od.bones_of_node(n))
+        .map_err(|e| format!("{e:?}"))?;
 
     let buffer_data = od.gen_buffer_data::<_, _, G>(&|x| &buffers[x]);
 
@@ -105,5 +113,5 @@ pub fn new_of_glb<G: Gl>(
     let materials = od.gen_materials(&gltf);
     let mut obj = od.gen_object(&gltf, &vertices, &textures, &materials);
     obj.analyze();
-    Ok(obj.into_instantiable(render_context))
+    Ok((obj.into_instantiable(render_context), animations))
 }
@@ -25,7 +25,7 @@ pub fn new<'tgt>(
     node_names: &[&str],
 ) -> Result<mod3d_base::Instantiable<Model3DWGpu<'tgt>>, String> {
     fn buf_reader(
-        file: &mut std::fs::File,
+        file: &mut std::io::Take<&mut std::fs::File>,
         byte_length: usize,
     ) -> Result<Option<Vec<u8>>, std::io::Error> {
         use std::io::Read;
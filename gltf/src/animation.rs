@@ -0,0 +1,143 @@
+//a Imports
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "serde")]
+use crate::{deserialize, serialize};
+
+use crate::Named;
+use crate::{AccessorIndex, AnimationIndex, NodeIndex};
+
+//a GltfAnimationSampler
+//tp GltfAnimationSampler
+/// A type that contains the data from a Gltf Json animation 'sampler'
+///
+/// The input accessor provides the keyframe times (SCALAR float); the
+/// output accessor provides the keyframe values, which for
+/// CUBICSPLINE interpolation are in-tangent, value, out-tangent
+/// triples
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GltfAnimationSampler {
+    /// Accessor of the keyframe times
+    pub input: AccessorIndex,
+    /// Interpolation between keyframes; defaults to LINEAR
+    #[cfg_attr(
+        feature = "serde",
+        serde(deserialize_with = "deserialize::interpolation")
+    )]
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize::interpolation"))]
+    pub interpolation: mod3d_base::Interpolation,
+    /// Accessor of the keyframe values
+    pub output: AccessorIndex,
+}
+
+//ip GltfAnimationSampler
+impl GltfAnimationSampler {
+    //ap input
+    pub fn input(&self) -> AccessorIndex {
+        self.input
+    }
+    //ap output
+    pub fn output(&self) -> AccessorIndex {
+        self.output
+    }
+    //ap interpolation
+    pub fn interpolation(&self) -> mod3d_base::Interpolation {
+        self.interpolation
+    }
+}
+
+//a GltfAnimationTarget
+//tp GltfAnimationTarget
+/// The target node and property of a Gltf animation channel
+///
+/// If the node is None then the channel should be ignored (it is
+/// presumably for an extension)
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GltfAnimationTarget {
+    /// The node being animated
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub node: Option<NodeIndex>,
+    /// The property of the node being animated
+    #[cfg_attr(
+        feature = "serde",
+        serde(deserialize_with = "deserialize::animation_path")
+    )]
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize::animation_path"))]
+    pub path: mod3d_base::AnimationProperty,
+}
+
+//a GltfAnimationChannel
+//tp GltfAnimationChannel
+/// A type that contains the data from a Gltf Json animation 'channel'
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GltfAnimationChannel {
+    /// Index into the samplers of the animation
+    pub sampler: usize,
+    /// The target of the channel
+    pub target: GltfAnimationTarget,
+}
+
+//ip GltfAnimationChannel
+impl GltfAnimationChannel {
+    //ap sampler
+    pub fn sampler(&self) -> usize {
+        self.sampler
+    }
+    //ap node
+    pub fn node(&self) -> Option<NodeIndex> {
+        self.target.node
+    }
+    //ap path
+    pub fn path(&self) -> mod3d_base::AnimationProperty {
+        self.target.path
+    }
+}
+
+//a GltfAnimation
+//tp GltfAnimation
+/// A type that contains the data from a Gltf Json 'animation'
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GltfAnimation {
+    /// Optional name of the animation
+    pub name: String,
+    /// The channels of the animation, each referring to a sampler
+    pub channels: Vec<GltfAnimationChannel>,
+    /// The samplers of the animation
+    pub samplers: Vec<GltfAnimationSampler>,
+}
+
+//ip GltfAnimation
+impl GltfAnimation {
+    //ap channels
+    pub fn channels(&self) -> &[GltfAnimationChannel] {
+        &self.channels
+    }
+    //ap samplers
+    pub fn samplers(&self) -> &[GltfAnimationSampler] {
+        &self.samplers
+    }
+    //mp targets_any
+    /// Return true if any channel of the animation targets one of the nodes
+    pub fn targets_any(&self, nodes: &[NodeIndex]) -> bool {
+        self.channels
+            .iter()
+            .any(|c| c.node().is_some_and(|n| nodes.contains(&n)))
+    }
+}
+
+//ip Named for GltfAnimation
+impl Named for GltfAnimation {
+    type Index = AnimationIndex;
+    fn is_name(&self, name: &str) -> bool {
+        self.name == name
+    }
+}
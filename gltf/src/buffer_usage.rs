@@ -6,7 +6,8 @@ use crate::{ODBufDataIndex, ODBufIndex};
 //a BufferUsage
 //tp BufferUsage
 /// This type monitors the usage of a buffer - which range of bytes
-/// are used for indices, and which range for vertex data
+/// are used for indices, which range for vertex data, and which range
+/// for data only used by the CPU (such as animation keyframes)
 ///
 /// Once a buffer is exposed to the client of the gltf (in a Vec<B>)
 /// this also maps to the index of that array; it also holds the
@@ -18,6 +19,9 @@ pub struct BufferUsage {
     vertex_data: Range<usize>,
     /// The range of the buffer used for index data
     index_data: Range<usize>,
+    /// The range of the buffer used for data that is read by the CPU
+    /// (and so is not a BufferData)
+    cpu_data: Range<usize>,
     /// The index into the user buffer Vec that this refers to
     buffer_index: ODBufIndex,
     /// The index into the user BufferData Vec that the vertex data range uses
@@ -43,7 +47,7 @@ impl BufferUsage {
     //ap is_used
     /// Return true if this buffer is used at all
     pub fn is_used(&self) -> bool {
-        self.has_vertex_data() || self.has_index_data()
+        self.has_vertex_data() || self.has_index_data() || !self.cpu_data.is_empty()
     }
 
     //ap buffer_index
@@ -99,6 +103,17 @@ impl BufferUsage {
         };
     }
 
+    //mp use_cpu_data
+    /// Record the use of a portion of a buffer for data read by the CPU
+    pub fn use_cpu_data(&mut self, byte_start: usize, byte_length: usize) {
+        let range = &mut self.cpu_data;
+        if std::ops::Range::<usize>::is_empty(range) {
+            *range = byte_start..(byte_start + byte_length)
+        } else {
+            *range = byte_start.min(range.start)..(byte_start + byte_length).max(range.end)
+        };
+    }
+
     //mp set_buffer_index
     pub fn set_buffer_index(&mut self, buffer_index: ODBufIndex) {
         self.buffer_index = buffer_index;
@@ -5,7 +5,8 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "serde")]
 use crate::{deserialize, serialize};
 
-use crate::{BufferIndex, ViewIndex};
+use crate::{BufferIndex, GltfBufferViewExtensions, GltfMeshoptCompression, ViewIndex};
+use crate::{Error, Result};
 
 //a GltfBuffer
 //tp GltfBuffer
@@ -20,6 +21,10 @@ use crate::{BufferIndex, ViewIndex};
 pub struct GltfBuffer {
     /// The URI specified by the buffer; this might be a data:URI containing
     /// the data itself, or maybe a relative path to a binary data or image
+    ///
+    /// This is empty (and not serialized) for the binary chunk of a
+    /// GLB file
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "String::is_empty"))]
     uri: String,
     /// The byte length of the buffer - any provided URI contents must be at
     /// least this length
@@ -52,6 +57,16 @@ impl GltfBuffer {
         Self { uri, byte_length }
     }
 
+    //cp of_uri
+    /// Create a buffer of a given byte length referring to a URI
+    ///
+    /// For the binary chunk of a GLB file the URI should be empty, in
+    /// which case it is not serialized
+    pub fn of_uri<S: Into<String>>(uri: S, byte_length: usize) -> Self {
+        let uri = uri.into();
+        Self { uri, byte_length }
+    }
+
     //mp take_buffer
     /// Take all the contents of the buffer, leaving a buffer in place with an
     /// empty URI
@@ -81,7 +96,10 @@ pub struct GltfBufferView {
     #[cfg_attr(feature = "serde", serde(rename = "byteOffset"))]
     pub byte_offset: usize,
     #[cfg_attr(feature = "serde", serde(rename = "byteStride"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub byte_stride: Option<usize>,
+    /// The supported extensions of the view, such as meshopt compression
+    pub extensions: GltfBufferViewExtensions,
 }
 
 impl GltfBufferView {
@@ -109,6 +127,71 @@ impl GltfBufferView {
     pub fn byte_end(&self) -> usize {
         self.byte_offset + self.byte_length
     }
+
+    //ap meshopt_compression
+    /// Get the compression of the view, if its contents must be
+    /// decoded from another buffer (EXT_meshopt_compression)
+    pub fn meshopt_compression(&self) -> Option<&GltfMeshoptCompression> {
+        self.extensions.meshopt_compression.as_ref()
+    }
+}
+
+//tp GltfSparseIndices
+/// The indices of the elements of an accessor that are replaced by a
+/// sparse accessor; these are tightly packed unsigned integers in a
+/// buffer view, and must be strictly increasing
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GltfSparseIndices {
+    /// The buffer view containing the indices
+    #[cfg_attr(feature = "serde", serde(rename = "bufferView"))]
+    pub buffer_view: ViewIndex,
+    /// Byte offset of the indices from the start of the view
+    #[cfg_attr(feature = "serde", serde(rename = "byteOffset"))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub byte_offset: usize,
+    /// The type of the indices - UInt8, UInt16 or UInt32
+    #[cfg_attr(feature = "serde", serde(rename = "componentType"))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(deserialize_with = "deserialize::comp_type_to_ele_type")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "serialize::ele_type_to_comp_type")
+    )]
+    pub component_type: mod3d_base::BufferElementType,
+}
+
+//tp GltfSparseValues
+/// The values of the elements of an accessor that are replaced by a
+/// sparse accessor; these are tightly packed elements of the
+/// accessor's type in a buffer view
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GltfSparseValues {
+    /// The buffer view containing the values
+    #[cfg_attr(feature = "serde", serde(rename = "bufferView"))]
+    pub buffer_view: ViewIndex,
+    /// Byte offset of the values from the start of the view
+    #[cfg_attr(feature = "serde", serde(rename = "byteOffset"))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub byte_offset: usize,
+}
+
+//tp GltfAccessorSparse
+/// The sparse storage of an accessor - a number of elements whose
+/// values replace those of the accessor's buffer view (or zeros, if it
+/// has none)
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GltfAccessorSparse {
+    /// Number of elements that are replaced
+    pub count: usize,
+    /// The indices of the replaced elements
+    pub indices: GltfSparseIndices,
+    /// The values of the replaced elements
+    pub values: GltfSparseValues,
 }
 
 //tp GltfAccessor
@@ -130,6 +213,7 @@ pub struct GltfAccessor {
     /// If this is None then zeros are supposed to be used for the accessor
     /// contents
     #[cfg_attr(feature = "serde", serde(rename = "bufferView"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     buffer_view: Option<ViewIndex>,
 
     /// Byte offset from start of the view (or offset+k*stride) for the
@@ -167,7 +251,29 @@ pub struct GltfAccessor {
     #[cfg_attr(feature = "serde", serde(serialize_with = "serialize::num_to_type"))]
     // SCALAR, VEC2, VEC3, VEC5, MAT2, MAT3, MAT4, string
     elements_per_data: usize,
-    // optional: normalized, max, min, sparse
+
+    /// True if integer elements are normalized to 0.0 to 1.0 (or -1.0
+    /// to 1.0 if signed) when read as floats
+    #[cfg_attr(feature = "serde", serde(default))]
+    normalized: bool,
+
+    /// Minimum value of each element of the data (required by Gltf for
+    /// vertex positions)
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    min: Option<Vec<f32>>,
+
+    /// Maximum value of each element of the data (required by Gltf for
+    /// vertex positions)
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    max: Option<Vec<f32>>,
+
+    /// Sparse storage of elements that differ from those in the
+    /// buffer view (or from zero, if there is no buffer view)
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    sparse: Option<GltfAccessorSparse>,
     // optional: name, extensions, extras
 }
 
@@ -187,6 +293,27 @@ impl GltfAccessor {
             count,
             component_type,
             elements_per_data,
+            normalized: false,
+            min: None,
+            max: None,
+            sparse: None,
+        }
+    }
+
+    //mp set_bounds
+    /// Set the minimum and maximum values of each element of the data
+    pub fn set_bounds(&mut self, min: Vec<f32>, max: Vec<f32>) {
+        self.min = Some(min);
+        self.max = Some(max);
+    }
+
+    //ap bounds
+    /// Get the minimum and maximum values of each element of the data,
+    /// if they were specified
+    pub fn bounds(&self) -> Option<(&[f32], &[f32])> {
+        match (&self.min, &self.max) {
+            (Some(min), Some(max)) => Some((min, max)),
+            _ => None,
         }
     }
 
@@ -195,6 +322,29 @@ impl GltfAccessor {
         self.buffer_view
     }
 
+    //ap sparse
+    /// Get the sparse storage of the accessor, if any
+    pub fn sparse(&self) -> Option<&GltfAccessorSparse> {
+        self.sparse.as_ref()
+    }
+
+    //ap requires_materializing
+    /// Return true if the accessor data is not simply the contents of a
+    /// buffer view - it has no buffer view, or it is sparse
+    pub fn requires_materializing(&self) -> bool {
+        self.buffer_view.is_none() || self.sparse.is_some()
+    }
+
+    //mp set_dense_view
+    /// Set the accessor to be the data at an offset in a buffer view,
+    /// dropping any sparse storage; this is used once the data has
+    /// been materialized
+    pub fn set_dense_view(&mut self, buffer_view: ViewIndex, byte_offset: usize) {
+        self.buffer_view = Some(buffer_view);
+        self.byte_offset = byte_offset;
+        self.sparse = None;
+    }
+
     //ap byte_offset
     pub fn byte_offset(&self) -> usize {
         self.byte_offset
@@ -220,6 +370,18 @@ impl GltfAccessor {
         self.elements_per_data
     }
 
+    //ap is_normalized
+    /// Return true if integer elements are normalized when read as floats
+    pub fn is_normalized(&self) -> bool {
+        self.normalized
+    }
+
+    //mp set_normalized
+    /// Set whether integer elements are normalized when read as floats
+    pub fn set_normalized(&mut self, normalized: bool) {
+        self.normalized = normalized;
+    }
+
     //ap byte_stride
     pub fn byte_stride(&self, view_byte_stride: usize) -> usize {
         if view_byte_stride != 0 {
@@ -235,4 +397,105 @@ impl GltfAccessor {
         let byte_stride = self.byte_stride(view_byte_stride);
         self.byte_offset + byte_stride * (self.count - 1) + self.ele_byte_size()
     }
+
+    //mp materialize
+    /// Generate the dense contents of the accessor, with each element
+    /// at a multiple of byte_stride, applying any sparse storage
+    ///
+    /// The view_data function must return the buffer view and the data
+    /// of the whole buffer that the view is of
+    pub fn materialize<'a, F>(&self, byte_stride: usize, view_data: &F) -> Result<Vec<u8>>
+    where
+        F: Fn(ViewIndex) -> Result<(&'a GltfBufferView, &'a [u8])>,
+    {
+        let ele_size = self.ele_byte_size();
+        let mut result = vec![0; byte_stride * self.count];
+        let view_slice = |view: ViewIndex, byte_offset: usize, byte_length: usize| {
+            let (view, data) = view_data(view)?;
+            let start = view.byte_offset() + byte_offset;
+            if byte_offset + byte_length > view.byte_length() {
+                return Err(Error::BufferTooShort);
+            }
+            data.get(start..start + byte_length)
+                .ok_or(Error::BufferTooShort)
+        };
+        if let Some(bv) = self.buffer_view {
+            if self.count > 0 {
+                let view_stride = view_data(bv)?.0.byte_stride(0);
+                let src_stride = self.byte_stride(view_stride);
+                let src = view_slice(
+                    bv,
+                    self.byte_offset,
+                    self.byte_view_end(view_stride) - self.byte_offset,
+                )?;
+                for i in 0..self.count {
+                    result[i * byte_stride..i * byte_stride + ele_size]
+                        .copy_from_slice(&src[i * src_stride..i * src_stride + ele_size]);
+                }
+            }
+        }
+        let Some(sparse) = &self.sparse else {
+            return Ok(result);
+        };
+        let index_size = sparse.indices.component_type.byte_length() as usize;
+        let indices = view_slice(
+            sparse.indices.buffer_view,
+            sparse.indices.byte_offset,
+            index_size * sparse.count,
+        )?;
+        let values = view_slice(
+            sparse.values.buffer_view,
+            sparse.values.byte_offset,
+            ele_size * sparse.count,
+        )?;
+        for k in 0..sparse.count {
+            let b = &indices[k * index_size..(k + 1) * index_size];
+            let i = match sparse.indices.component_type {
+                mod3d_base::BufferElementType::UInt8 => b[0] as usize,
+                mod3d_base::BufferElementType::UInt16 => u16::from_le_bytes([b[0], b[1]]) as usize,
+                mod3d_base::BufferElementType::UInt32 => {
+                    u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize
+                }
+                t => {
+                    return Err(Error::BadJson(format!(
+                        "Sparse accessor index type {t:?} must be unsigned"
+                    )));
+                }
+            };
+            if i >= self.count {
+                return Err(Error::BadJson(format!(
+                    "Sparse accessor index {i} out of range (must be < {})",
+                    self.count
+                )));
+            }
+            result[i * byte_stride..i * byte_stride + ele_size]
+                .copy_from_slice(&values[k * ele_size..(k + 1) * ele_size]);
+        }
+        Ok(result)
+    }
+
+    //mp read_floats
+    /// Read the contents of the accessor as f32, given its buffer view and
+    /// the data of the whole buffer that the view is of
+    ///
+    /// Integer component types are normalized if the accessor is
+    /// (as they must be for animation keyframe values, for example),
+    /// otherwise they are converted to the nearest float
+    pub fn read_floats(&self, view: &GltfBufferView, data: &[u8]) -> Result<Vec<f32>> {
+        let byte_stride = self.byte_stride(view.byte_stride(0));
+        let byte_start = view.byte_offset() + self.byte_offset;
+        if self.count > 0 && view.byte_offset() + self.byte_view_end(byte_stride) > data.len() {
+            return Err(Error::BufferTooShort);
+        }
+        let ele_size = self.component_type.byte_length() as usize;
+        let mut result = Vec::with_capacity(self.count * self.elements_per_data);
+        for i in 0..self.count {
+            let d = &data[byte_start + i * byte_stride..];
+            for e in 0..self.elements_per_data {
+                let b = &d[e * ele_size..(e + 1) * ele_size];
+                result.push(self.component_type.read_f32(b, self.normalized));
+            }
+        }
+        Ok(result)
+    }
 }
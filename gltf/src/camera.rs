@@ -0,0 +1,139 @@
+//a Imports
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{CameraIndex, Named};
+use crate::{Error, Result};
+
+//a GltfPerspective, GltfOrthographic
+//tp GltfPerspective
+/// The perspective projection of a Gltf camera
+#[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GltfPerspective {
+    /// Optional aspect ratio of the field of view; if not present
+    /// then the aspect ratio of the viewport should be used
+    #[cfg_attr(feature = "serde", serde(rename = "aspectRatio"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub aspect_ratio: Option<f32>,
+    /// Vertical field of view in radians
+    pub yfov: f32,
+    /// Optional distance to the far clipping plane; if not present
+    /// then the projection is infinite
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub zfar: Option<f32>,
+    /// Distance to the near clipping plane
+    pub znear: f32,
+}
+
+//tp GltfOrthographic
+/// The orthographic projection of a Gltf camera
+#[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GltfOrthographic {
+    /// Horizontal magnification of the view
+    pub xmag: f32,
+    /// Vertical magnification of the view
+    pub ymag: f32,
+    /// Distance to the far clipping plane
+    pub zfar: f32,
+    /// Distance to the near clipping plane
+    pub znear: f32,
+}
+
+//a GltfCamera
+//tp GltfCamera
+/// A type that contains the data from a Gltf Json 'camera'
+///
+/// The camera type is either "perspective" or "orthographic", and the
+/// respective projection must be provided
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GltfCamera {
+    /// Optional name of the camera
+    pub name: String,
+    /// The type of the camera - "perspective" or "orthographic"
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub camera_type: String,
+    /// The perspective projection, if the camera is a perspective camera
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub perspective: Option<GltfPerspective>,
+    /// The orthographic projection, if the camera is an orthographic camera
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub orthographic: Option<GltfOrthographic>,
+}
+
+//ip GltfCamera
+impl GltfCamera {
+    //mp validate
+    /// Validate the camera - its type must match the projection provided
+    pub fn validate(&self, c: CameraIndex) -> Result<()> {
+        match self.camera_type.as_str() {
+            "perspective" => {
+                let Some(p) = &self.perspective else {
+                    return Err(Error::BadJson(format!(
+                        "Camera {c} is perspective but has no perspective projection",
+                    )));
+                };
+                if p.yfov <= 0. || p.znear <= 0. || p.zfar.is_some_and(|f| f <= p.znear) {
+                    return Err(Error::BadJson(format!(
+                        "Camera {c} has a bad perspective projection",
+                    )));
+                }
+            }
+            "orthographic" => {
+                let Some(o) = &self.orthographic else {
+                    return Err(Error::BadJson(format!(
+                        "Camera {c} is orthographic but has no orthographic projection",
+                    )));
+                };
+                if o.xmag == 0. || o.ymag == 0. || o.znear < 0. || o.zfar <= o.znear {
+                    return Err(Error::BadJson(format!(
+                        "Camera {c} has a bad orthographic projection",
+                    )));
+                }
+            }
+            t => {
+                return Err(Error::BadJson(format!(
+                    "Camera {c} has unknown camera type '{t}'",
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    //ap projection
+    /// Get the [mod3d_base::Projection] of the camera
+    ///
+    /// This assumes the camera has been validated
+    pub fn projection(&self) -> mod3d_base::Projection {
+        if self.camera_type == "orthographic" {
+            let o = self.orthographic.unwrap_or_default();
+            mod3d_base::Projection::Orthographic {
+                xmag: o.xmag,
+                ymag: o.ymag,
+                znear: o.znear,
+                zfar: o.zfar,
+            }
+        } else {
+            let p = self.perspective.unwrap_or_default();
+            mod3d_base::Projection::Perspective {
+                yfov: p.yfov,
+                aspect_ratio: p.aspect_ratio,
+                znear: p.znear,
+                zfar: p.zfar,
+            }
+        }
+    }
+}
+
+//ip Named for GltfCamera
+impl Named for GltfCamera {
+    type Index = CameraIndex;
+    fn is_name(&self, name: &str) -> bool {
+        self.name == name
+    }
+}
@@ -3,38 +3,92 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Deserializer};
 
-use crate::AccessorIndex;
+use crate::{AccessorIndex, GltfAttributes};
 
 //a Deserializer functions
-//fi attr_to_attr
-/// Map an array of Gltf string attribute name/value pairs to a Vec of
-/// tuples of mod3d_base::VertexAttr and AccessorIndex
-pub fn attr_to_attr<'de, D>(
-    de: D,
-) -> std::result::Result<Vec<(mod3d_base::VertexAttr, AccessorIndex)>, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    let m: HashMap<String, usize> = Deserialize::deserialize(de)?;
+//fi attrs_of_map
+/// Map Gltf string attribute name/value pairs to a Vec of tuples of
+/// mod3d_base::VertexAttr and AccessorIndex, and a Vec of the name/value
+/// pairs of the attributes that have no VertexAttr (such as further
+/// texture coordinate sets, or application-specific attributes whose
+/// names start with '_')
+#[allow(clippy::type_complexity)]
+fn attrs_of_map(
+    m: HashMap<String, usize>,
+) -> (
+    Vec<(mod3d_base::VertexAttr, AccessorIndex)>,
+    Vec<(String, AccessorIndex)>,
+) {
     let mut r = vec![];
+    let mut ignored = vec![];
     for (k, v) in m.into_iter() {
         use mod3d_base::VertexAttr::*;
         let k = match k.as_ref() {
             "POSITION" => Position,
             "NORMAL" => Normal,
             "COLOR_0" => Color,
+            "COLOR_1" => Color1,
             "TANGENT" => Tangent,
             "JOINTS_0" => Joints,
+            "JOINTS_1" => Joints1,
             "WEIGHTS_0" => Weights,
+            "WEIGHTS_1" => Weights1,
             "TEXCOORD_0" => TexCoords0,
             "TEXCOORD_1" => TexCoords1,
+            "TEXCOORD_2" => TexCoords2,
+            "TEXCOORD_3" => TexCoords3,
             _ => {
-                return Err(serde::de::Error::custom(format!("Unknown attribute {k}")));
+                ignored.push((k, v.into()));
+                continue;
             }
         };
         r.push((k, v.into()));
     }
-    Ok(r)
+    ignored.sort_by(|a, b| a.0.cmp(&b.0));
+    (r, ignored)
+}
+
+//fi attr_to_attr
+/// Map an array of Gltf string attribute name/value pairs to the
+/// [GltfAttributes] of a primitive
+///
+/// Attributes that have no VertexAttr are kept as ignored attributes,
+/// which are reported as warnings by validation
+pub fn attr_to_attr<'de, D>(de: D) -> std::result::Result<GltfAttributes, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let m: HashMap<String, usize> = Deserialize::deserialize(de)?;
+    let (attributes, ignored) = attrs_of_map(m);
+    Ok(GltfAttributes {
+        attributes,
+        ignored,
+    })
+}
+
+//fi targets
+/// Map an array of Gltf morph targets, each of string attribute
+/// name/value pairs, to a Vec of Vec of tuples of
+/// mod3d_base::VertexAttr and AccessorIndex
+#[allow(clippy::type_complexity)]
+pub fn targets<'de, D>(
+    de: D,
+) -> std::result::Result<Vec<Vec<(mod3d_base::VertexAttr, AccessorIndex)>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let ms: Vec<HashMap<String, usize>> = Deserialize::deserialize(de)?;
+    let mut targets = vec![];
+    for m in ms {
+        let (target, ignored) = attrs_of_map(m);
+        if let Some((k, _)) = ignored.first() {
+            return Err(serde::de::Error::custom(format!(
+                "Unsupported morph target attribute {k}"
+            )));
+        }
+        targets.push(target);
+    }
+    Ok(targets)
 }
 
 //fi primitive_type
@@ -123,6 +177,103 @@ where
     .map_err(serde::de::Error::custom)
 }
 
+//fi interpolation
+/// Map a Gltf animation sampler interpolation name to a
+/// mod3d_base::Interpolation
+pub fn interpolation<'de, D>(de: D) -> std::result::Result<mod3d_base::Interpolation, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: String = Deserialize::deserialize(de)?;
+    use mod3d_base::Interpolation::*;
+    match s.as_ref() {
+        "STEP" => Ok(Step),
+        "LINEAR" => Ok(Linear),
+        "CUBICSPLINE" => Ok(CubicSpline),
+        _ => Err(format!("Unknown animation interpolation {s}")),
+    }
+    .map_err(serde::de::Error::custom)
+}
+
+//fi texture_wrap
+/// Map a Gltf sampler wrap mode (a GL enumeration) to a
+/// mod3d_base::TextureWrap
+pub fn texture_wrap<'de, D>(de: D) -> std::result::Result<mod3d_base::TextureWrap, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let w: usize = Deserialize::deserialize(de)?;
+    use mod3d_base::TextureWrap::*;
+    match w {
+        33071 => Ok(ClampToEdge),
+        33648 => Ok(MirroredRepeat),
+        10497 => Ok(Repeat),
+        _ => Err(format!("Unknown sampler wrap mode {w}")),
+    }
+    .map_err(serde::de::Error::custom)
+}
+
+//fi texture_filter
+/// Map a Gltf sampler filter (a GL enumeration) to a
+/// mod3d_base::TextureFilter
+pub fn texture_filter<'de, D>(de: D) -> std::result::Result<mod3d_base::TextureFilter, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let f: usize = Deserialize::deserialize(de)?;
+    use mod3d_base::TextureFilter::*;
+    match f {
+        9728 => Ok(Nearest),
+        9729 => Ok(Linear),
+        9984 => Ok(NearestMipmapNearest),
+        9985 => Ok(LinearMipmapNearest),
+        9986 => Ok(NearestMipmapLinear),
+        9987 => Ok(LinearMipmapLinear),
+        _ => Err(format!("Unknown sampler filter {f}")),
+    }
+    .map_err(serde::de::Error::custom)
+}
+
+//fi texture_mag_filter
+/// Map a Gltf sampler magnification filter (a GL enumeration) to a
+/// mod3d_base::TextureFilter; only NEAREST and LINEAR are valid
+pub fn texture_mag_filter<'de, D>(de: D) -> std::result::Result<mod3d_base::TextureFilter, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let f: usize = Deserialize::deserialize(de)?;
+    use mod3d_base::TextureFilter::*;
+    match f {
+        9728 => Ok(Nearest),
+        9729 => Ok(Linear),
+        _ => Err(format!("Unknown sampler magnification filter {f}")),
+    }
+    .map_err(serde::de::Error::custom)
+}
+
+//fi animation_path
+/// Map a Gltf animation channel target path to a
+/// mod3d_base::AnimationProperty
+pub fn animation_path<'de, D>(de: D) -> std::result::Result<mod3d_base::AnimationProperty, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: String = Deserialize::deserialize(de)?;
+    use mod3d_base::AnimationProperty::*;
+    match s.as_ref() {
+        "translation" => Ok(Translation),
+        "rotation" => Ok(Rotation),
+        "scale" => Ok(Scale),
+        "weights" => Ok(Weights),
+        _ => Err(format!("Unknown animation target path {s}")),
+    }
+    .map_err(serde::de::Error::custom)
+}
+
 pub fn f32_one() -> f32 {
     1.0
 }
+
+pub fn f32_half() -> f32 {
+    0.5
+}
@@ -9,6 +9,8 @@ pub enum GltfError {
     Json(#[from] serde_json::Error),
     #[error("Bad GLTF Json")]
     BadJson(String),
+    #[error("GLTF failed validation: {0}")]
+    Validation(crate::ValidationReport),
     #[error("Bad UTF8 in GLTF Json")]
     Utf8(#[from] std::str::Utf8Error),
     #[error("Bad base64 in GLTF Json")]
@@ -17,6 +19,8 @@ pub enum GltfError {
     BufferTooShort,
     #[error("Buffer could not be read")]
     BufferRead,
+    #[error("Failed to resolve URI {uri}: {reason}")]
+    Uri { uri: String, reason: String },
     #[error("Failed to load image {reason}")]
     ImageLoad { reason: String },
     #[error("Bad GLB header")]
@@ -27,10 +31,24 @@ pub enum GltfError {
     GlbJsonIo(std::io::Error),
     #[error("GLB json ends beyond file extent")]
     GlbJsonLength,
+    #[error("GLB json chunk is longer than permitted")]
+    GlbJsonTooLong,
+    #[error("GLB chunk lengths do not match the GLB header byte length")]
+    GlbLength,
+    #[error("IO error reading GLB chunk")]
+    GlbChunkIo(std::io::Error),
+    #[error("GLB json or binary chunk is out of order")]
+    GlbChunkOrder,
     #[error("Bad GLB binary buffer header")]
     GlbBinHdr,
     #[error("IO error reading GLB binary")]
     GlbBinIo(std::io::Error),
+    #[error("GLB file would be larger than 4GB")]
+    GlbTooLarge,
+    #[error("Failed to decode meshopt compressed data: {0}")]
+    Meshopt(String),
+    #[error("Cannot export to GLTF: {0}")]
+    Export(String),
     #[error("unknown data store error")]
     Unknown,
 }
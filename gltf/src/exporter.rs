@@ -0,0 +1,570 @@
+//a Documentation
+/*!
+
+This provides the export of mod3d_base [Object]s to a [Gltf], with all of
+the vertex data, indices and texture images in a single binary buffer
+
+The binary buffer may be embedded in the Gltf Json as a base64 data:
+URI, written as a separate file, or used as the binary chunk of a GLB
+file.
+
+!*/
+
+//a Imports
+use mod3d_base::{
+    AlphaMode, BufferDescriptor, BufferElementType, Material, MaterialAspect, Object, Renderable,
+    Texture, VertexAttr, Vertices,
+};
+
+use crate::{AccessorIndex, MaterialIndex, SceneIndex, TextureIndex, ViewIndex};
+use crate::{Error, Result};
+use crate::{
+    Gltf, GltfAsset, GltfBuffer, GltfImage, GltfMaterial, GltfMaterialExtensions, GltfMesh,
+    GltfNode, GltfPbrMetallicRoughness, GltfSampler, GltfScene, GltfTexture, GltfTextureInfo,
+    GltfTextureInfoExtensions, GltfTextureTransform,
+};
+use crate::{GltfClearcoat, GltfEmissiveStrength, GltfIor, GltfTransmission, GltfUnlit};
+
+//a GltfBufferUri
+//tp GltfBufferUri
+/// How the binary buffer of an exported [Gltf] is referred to by the Json
+#[derive(Debug, Clone)]
+pub enum GltfBufferUri {
+    /// Embed the buffer in the Json as a base64 data: URI
+    Base64,
+    /// Refer to a separate binary file (such as 'model.bin') that the
+    /// caller writes with the buffer contents
+    External(String),
+    /// The buffer is the binary chunk of a GLB file, and so has no URI
+    Glb,
+}
+
+//a ExportedVertices
+//ti ExportedVertices
+/// The Gltf view of the indices and the accessors of the attributes
+/// for a [Vertices] that has been exported
+struct ExportedVertices {
+    /// The view of the indices and their type, if the vertices are indexed
+    indices: Option<(ViewIndex, BufferElementType)>,
+    /// The accessors for each of the vertex attributes
+    attributes: Vec<(VertexAttr, AccessorIndex)>,
+}
+
+//a ExportedDescriptor
+//ti ExportedDescriptor
+/// The Gltf view of the vertex data of a [BufferDescriptor] that has
+/// been exported, with the layout of the data in the view
+struct ExportedDescriptor {
+    /// The view of the vertex data
+    view: ViewIndex,
+    /// The byte offset within a vertex of each element of the descriptor
+    offsets: Vec<usize>,
+    /// The byte stride of the vertices
+    stride: usize,
+    /// The byte length of the view
+    byte_length: usize,
+}
+
+//a GltfExporter
+//tp GltfExporter
+/// An exporter of mod3d_base [Object]s to a [Gltf] and a single binary
+/// buffer
+///
+/// Each object that is added becomes a scene of the Gltf, with one node
+/// for each component of the object; the first object added is the
+/// default scene. The vertex data, indices and texture images of the
+/// object are copied into the binary buffer.
+///
+/// Skeletons (and hence skins) of objects are not exported
+pub struct GltfExporter {
+    /// The Gltf being built
+    gltf: Gltf,
+    /// The contents of the binary buffer, which is buffer 0 of the Gltf
+    data: Vec<u8>,
+}
+
+//ip GltfExporter
+impl GltfExporter {
+    //cp new
+    /// Create a new [GltfExporter] with no objects
+    pub fn new(asset: GltfAsset) -> Self {
+        let mut gltf = Gltf::default();
+        gltf.set_asset(asset);
+        let data = vec![];
+        Self { gltf, data }
+    }
+
+    //ap gltf
+    /// Borrow the [Gltf] as it has been exported so far
+    pub fn gltf(&self) -> &Gltf {
+        &self.gltf
+    }
+
+    //mi add_view_data
+    /// Append data to the binary buffer (at a 4-byte aligned offset) and
+    /// add a buffer view of it
+    fn add_view_data(&mut self, data: &[u8], byte_stride: Option<usize>) -> ViewIndex {
+        self.pad_data();
+        let byte_offset = self.data.len();
+        self.data.extend_from_slice(data);
+        self.gltf
+            .add_view(0.into(), byte_offset, data.len(), byte_stride)
+    }
+
+    //mi pad_data
+    /// Pad the binary buffer with zeros to a multiple of 4 bytes
+    fn pad_data(&mut self) {
+        while !self.data.len().is_multiple_of(4) {
+            self.data.push(0);
+        }
+    }
+
+    //mi add_texture
+    /// Add a texture, with its image and sampler, if the image can be
+    /// encoded
+    fn add_texture<R, F>(&mut self, texture: &Texture<R>, encode_image: &F) -> Option<TextureIndex>
+    where
+        R: Renderable,
+        F: Fn(&Texture<R>) -> Option<(String, Vec<u8>)>,
+    {
+        let (mime_type, image_data) = encode_image(texture)?;
+        let buffer_view = self.add_view_data(&image_data, None);
+        let image = self.gltf.add_image(GltfImage {
+            name: String::new(),
+            uri: None,
+            mime_type,
+            buffer_view,
+        });
+        let sampler = self
+            .gltf
+            .add_sampler(GltfSampler::of_texture_sampler(texture.sampler()));
+        Some(self.gltf.add_texture(GltfTexture {
+            name: String::new(),
+            image,
+            sampler: Some(sampler),
+        }))
+    }
+
+    //mi add_material
+    /// Add a material, given the Gltf textures for the textures of the
+    /// object (if they were exported)
+    fn add_material<M: Material>(
+        &mut self,
+        material: &M,
+        textures: &[Option<TextureIndex>],
+    ) -> MaterialIndex {
+        let uses_texture_transform = std::cell::Cell::new(false);
+        let texture_transform = |aspect| {
+            let uv_transform = material.uv_transform(aspect);
+            if uv_transform == [1., 0., 0., 0., 1., 0., 0., 0., 1.] {
+                None
+            } else {
+                uses_texture_transform.set(true);
+                Some(GltfTextureTransform::of_uv_matrix(&uv_transform))
+            }
+        };
+        // Only normal textures have a scale, and only occlusion
+        // textures have a strength
+        let texture_info = |aspect| {
+            let n: Option<usize> = material.texture(aspect).into();
+            let index = (*textures.get(n?)?)?;
+            let scale = match aspect {
+                MaterialAspect::Normal => material.normal_scale(),
+                _ => 1.,
+            };
+            let strength = match aspect {
+                MaterialAspect::Occlusion => material.occlusion_strength(),
+                _ => 1.,
+            };
+            Some(GltfTextureInfo {
+                index,
+                tex_coord: material.tex_coord(aspect),
+                scale,
+                strength,
+                extensions: GltfTextureInfoExtensions {
+                    texture_transform: texture_transform(aspect),
+                },
+            })
+        };
+        let base_data = material.base_data();
+        let (r, g, b, a) = base_data.rgba_tuple();
+        let (metallic_factor, roughness_factor) = base_data.metallic_roughness();
+        let emissive_rgb = base_data.emissive_rgb;
+        let emissive_factor = [0, 8, 16].map(|s| ((emissive_rgb >> s) & 0xff) as f32 / 255.0);
+        let pbr_metallic_roughness = GltfPbrMetallicRoughness {
+            base_color_factor: Some([r, g, b, a].iter().map(|c| *c as f32 / 255.0).collect()),
+            base_color_texture: texture_info(MaterialAspect::Color),
+            metallic_roughness_texture: texture_info(MaterialAspect::MetallicRoughness),
+            metallic_factor,
+            roughness_factor,
+        };
+        let alpha_mode = match base_data.alpha_mode() {
+            AlphaMode::Opaque => "OPAQUE",
+            AlphaMode::Mask => "MASK",
+            AlphaMode::Blend => "BLEND",
+        };
+        let mut extensions = GltfMaterialExtensions::default();
+        if material.emissive_strength() != 1.0 {
+            extensions.emissive_strength = Some(GltfEmissiveStrength {
+                emissive_strength: material.emissive_strength(),
+            });
+        }
+        if base_data.unlit() {
+            extensions.unlit = Some(GltfUnlit {});
+        }
+        let (clearcoat_factor, clearcoat_roughness_factor) = material.clearcoat();
+        if clearcoat_factor != 0. {
+            extensions.clearcoat = Some(GltfClearcoat {
+                clearcoat_factor,
+                clearcoat_texture: texture_info(MaterialAspect::Clearcoat),
+                clearcoat_roughness_factor,
+                clearcoat_roughness_texture: texture_info(MaterialAspect::ClearcoatRoughness),
+                clearcoat_normal_texture: texture_info(MaterialAspect::ClearcoatNormal),
+            });
+        }
+        if material.transmission() != 0. {
+            extensions.transmission = Some(GltfTransmission {
+                transmission_factor: material.transmission(),
+                transmission_texture: texture_info(MaterialAspect::Transmission),
+            });
+        }
+        if material.ior() != 1.5 {
+            extensions.ior = Some(GltfIor {
+                ior: material.ior(),
+            });
+        }
+        let normal_texture = texture_info(MaterialAspect::Normal);
+        let occlusion_texture = texture_info(MaterialAspect::Occlusion);
+        let emissive_texture = texture_info(MaterialAspect::Emission);
+
+        let extensions_used = [
+            (
+                extensions.emissive_strength.is_some(),
+                "KHR_materials_emissive_strength",
+            ),
+            (extensions.unlit.is_some(), "KHR_materials_unlit"),
+            (extensions.clearcoat.is_some(), "KHR_materials_clearcoat"),
+            (
+                extensions.transmission.is_some(),
+                "KHR_materials_transmission",
+            ),
+            (extensions.ior.is_some(), "KHR_materials_ior"),
+            (uses_texture_transform.get(), "KHR_texture_transform"),
+        ];
+        for (used, name) in extensions_used {
+            if used {
+                self.gltf.add_extension_used(name);
+            }
+        }
+        self.gltf.add_material(GltfMaterial {
+            name: String::new(),
+            normal_texture,
+            occlusion_texture,
+            emissive_texture,
+            pbr_metallic_roughness: Some(pbr_metallic_roughness),
+            emissive_factor,
+            alpha_mode: Some(alpha_mode.into()),
+            alpha_cutoff: base_data.alpha_cutoff(),
+            double_sided: base_data.double_sided(),
+            extensions,
+        })
+    }
+
+    //mi add_vertex_data
+    /// Add the vertex data of a [BufferDescriptor] as a buffer view
+    ///
+    /// Gltf requires each element of a vertex attribute to be at a
+    /// 4-byte aligned offset within its vertex, with a byte stride that
+    /// is a multiple of 4; if the descriptor does not meet this (such
+    /// as tightly packed UInt8 colors) then its elements are laid out
+    /// again in order, each padded to a multiple of 4 bytes
+    fn add_vertex_data<R: Renderable>(&mut self, desc: &BufferDescriptor<R>) -> ExportedDescriptor {
+        let elements = desc.elements();
+        let stride = desc.stride() as usize;
+        let data: &[u8] = desc.as_ref();
+        let offsets: Vec<usize> = elements.iter().map(|e| e.byte_offset() as usize).collect();
+
+        let aligned = stride.is_multiple_of(4) && offsets.iter().all(|o| o.is_multiple_of(4));
+        let (offsets, new_stride, new_data) = {
+            if aligned {
+                (offsets, stride, None)
+            } else {
+                let mut order: Vec<usize> = (0..elements.len()).collect();
+                order.sort_by_key(|i| offsets[*i]);
+                let mut new_offsets = vec![0; elements.len()];
+                let mut end = 0;
+                for i in order {
+                    new_offsets[i] = end;
+                    end += (elements[i].byte_length() as usize).next_multiple_of(4);
+                }
+                let new_stride = end.max(4);
+                let num_vertices = elements
+                    .iter()
+                    .zip(offsets.iter())
+                    .filter_map(|(e, o)| {
+                        let end = o + e.byte_length() as usize;
+                        (data.len() >= end).then(|| (data.len() - end) / stride + 1)
+                    })
+                    .max()
+                    .unwrap_or(0);
+                let mut new_data = vec![0; num_vertices * new_stride];
+                for v in 0..num_vertices {
+                    for ((e, o), n) in elements.iter().zip(offsets.iter()).zip(new_offsets.iter()) {
+                        let len = e.byte_length() as usize;
+                        let src = v * stride + o;
+                        if src + len <= data.len() {
+                            let dst = v * new_stride + n;
+                            new_data[dst..dst + len].copy_from_slice(&data[src..src + len]);
+                        }
+                    }
+                }
+                (new_offsets, new_stride, Some(new_data))
+            }
+        };
+
+        let byte_stride = {
+            if elements.len() == 1 && elements[0].byte_length() as usize == new_stride {
+                None
+            } else {
+                Some(new_stride)
+            }
+        };
+        let data = new_data.as_deref().unwrap_or(data);
+        let byte_length = data.len();
+        let view = self.add_view_data(data, byte_stride);
+        ExportedDescriptor {
+            view,
+            offsets,
+            stride: new_stride,
+            byte_length,
+        }
+    }
+
+    //mi add_vertices
+    /// Add the indices and vertex attributes of a [Vertices], reusing
+    /// the buffer views of any [BufferDescriptor] already exported
+    fn add_vertices<'a, R: Renderable>(
+        &mut self,
+        vertices: &'a Vertices<'a, R>,
+        descriptors: &mut Vec<(&'a BufferDescriptor<'a, R>, ExportedDescriptor)>,
+    ) -> Result<ExportedVertices> {
+        let indices = {
+            if let Some(indices) = vertices.borrow_indices() {
+                let ele_type = indices.ele_type();
+                use BufferElementType::*;
+                if !matches!(ele_type, UInt8 | UInt16 | UInt32) {
+                    return Err(Error::Export(format!(
+                        "indices must be unsigned integers, not {ele_type:?}"
+                    )));
+                }
+                Some((self.add_view_data(indices.as_ref(), None), ele_type))
+            } else {
+                None
+            }
+        };
+
+        let mut attributes = vec![];
+        for accessor in vertices.iter_attrs() {
+            let attr = accessor.vertex_attr();
+            let vertex_desc = accessor.vertex_desc();
+            let ele_type = vertex_desc.ele_type();
+            if ele_type == BufferElementType::Float16 {
+                return Err(Error::Export(format!(
+                    "vertex attribute {attr:?} has element type {ele_type:?}"
+                )));
+            }
+            let desc = accessor.desc();
+            let exported = {
+                if let Some(i) = descriptors.iter().position(|(d, _)| std::ptr::eq(*d, desc)) {
+                    &descriptors[i].1
+                } else {
+                    let exported = self.add_vertex_data(desc);
+                    descriptors.push((desc, exported));
+                    &descriptors.last().unwrap().1
+                }
+            };
+            let view = exported.view;
+
+            let byte_offset = exported.offsets[accessor.desc_index() as usize];
+            let ele_byte_length = vertex_desc.byte_length() as usize;
+            let byte_length = exported.byte_length;
+            if byte_length < byte_offset + ele_byte_length {
+                return Err(Error::Export(format!(
+                    "vertex attribute {attr:?} has no data"
+                )));
+            }
+            let count = (byte_length - byte_offset - ele_byte_length) / exported.stride + 1;
+            let acc = self.gltf.add_accessor(
+                view,
+                byte_offset as u32,
+                count as u32,
+                ele_type,
+                vertex_desc.count() as usize,
+            );
+            self.gltf[acc].set_normalized(vertex_desc.is_normalized());
+
+            // Gltf requires the bounds of vertex positions
+            if attr == VertexAttr::Position && ele_type == BufferElementType::Float32 {
+                let n = vertex_desc.count() as usize;
+                let floats = self.gltf[acc].read_floats(&self.gltf[view], &self.data[..])?;
+                let mut min = floats[0..n].to_vec();
+                let mut max = min.clone();
+                for v in floats.chunks_exact(n) {
+                    for (i, f) in v.iter().enumerate() {
+                        min[i] = min[i].min(*f);
+                        max[i] = max[i].max(*f);
+                    }
+                }
+                self.gltf[acc].set_bounds(min, max);
+            }
+            attributes.push((attr, acc));
+        }
+        Ok(ExportedVertices {
+            indices,
+            attributes,
+        })
+    }
+
+    //mp add_object
+    /// Add an [Object] to the Gltf as a new scene, returning its index
+    ///
+    /// Each texture of the object is encoded by 'encode_image', which
+    /// returns the MIME type (such as "image/png") and data of the
+    /// encoded image; if it returns None then the texture is not
+    /// exported, and materials will not refer to it.
+    ///
+    /// The primitives of the object that use unindexed vertices are
+    /// exported as drawing all of the vertices
+    pub fn add_object<'object, M, R, F>(
+        &mut self,
+        object: &Object<'object, M, R>,
+        encode_image: &F,
+    ) -> Result<SceneIndex>
+    where
+        M: Material + 'object,
+        R: Renderable,
+        F: Fn(&Texture<R>) -> Option<(String, Vec<u8>)>,
+    {
+        let textures: Vec<Option<TextureIndex>> = object
+            .textures
+            .iter()
+            .map(|t| self.add_texture(t, encode_image))
+            .collect();
+
+        let materials: Vec<MaterialIndex> = object
+            .materials
+            .iter()
+            .map(|m| self.add_material(*m, &textures))
+            .collect();
+
+        let mut descriptors = vec![];
+        let mut vertices = vec![];
+        for v in &object.vertices {
+            vertices.push(self.add_vertices(v, &mut descriptors)?);
+        }
+
+        let elements = object.components.borrow_elements();
+        let first_node = self.gltf.nodes().len();
+        let mut scene = GltfScene::default();
+        for element in elements {
+            let component = &element.data;
+            let mut node = GltfNode::default();
+            if let Some(transformation) = &component.transformation {
+                node.set_transformation(transformation);
+                node.derive_gltf();
+            }
+            if !component.mesh.primitives.is_empty() {
+                let mut mesh = GltfMesh::default();
+                for primitive in &component.mesh.primitives {
+                    let Some(vi) = Option::<usize>::from(primitive.vertices_index()) else {
+                        return Err(Error::Export("primitive does not have any vertices".into()));
+                    };
+                    let exported = &vertices[vi];
+                    let indices = exported.indices.map(|(view, ele_type)| {
+                        self.gltf.add_accessor(
+                            view,
+                            primitive.byte_offset(),
+                            primitive.index_count(),
+                            ele_type,
+                            1,
+                        )
+                    });
+                    let material = Option::<usize>::from(primitive.material_index())
+                        .and_then(|m| materials.get(m).copied());
+                    let pi = mesh.add_primitive(primitive.primitive_type(), indices, material);
+                    for (attr, acc) in &exported.attributes {
+                        mesh[pi].add_attribute(*attr, *acc);
+                    }
+                }
+                node.set_mesh(self.gltf.add_mesh(mesh));
+            }
+            for c in element.children() {
+                node.add_child((first_node + c).into());
+            }
+            let n = self.gltf.add_node(node);
+            if !element.has_parent() {
+                scene.add_node(n);
+            }
+        }
+        let scene = self.gltf.add_scene(scene);
+        if self.gltf.scene().is_none() {
+            self.gltf.set_scene(Some(scene));
+        }
+        Ok(scene)
+    }
+
+    //dp into_gltf
+    /// Complete the export, returning the [Gltf] and the contents of its
+    /// binary buffer
+    ///
+    /// The Gltf has its node hierarchy generated, so it may be used just
+    /// as if it had been loaded from a file
+    pub fn into_gltf(mut self, buffer_uri: GltfBufferUri) -> (Gltf, Vec<u8>) {
+        self.pad_data();
+        let Self { mut gltf, data } = self;
+        if !data.is_empty() {
+            let buffer = match buffer_uri {
+                GltfBufferUri::Base64 => GltfBuffer::of_base64(&data),
+                GltfBufferUri::External(uri) => GltfBuffer::of_uri(uri, data.len()),
+                GltfBufferUri::Glb => GltfBuffer::of_uri("", data.len()),
+            };
+            gltf.add_buffer(buffer);
+        }
+        gltf.gen_node_hierarchy();
+        gltf.derive();
+        (gltf, data)
+    }
+
+    //dp write_gltf
+    /// Complete the export, writing the Gltf Json and returning the
+    /// contents of the binary buffer
+    ///
+    /// For an external buffer the caller must write the binary buffer
+    /// contents to the URI
+    #[cfg(feature = "serde_json")]
+    pub fn write_gltf<W: std::io::Write>(
+        self,
+        writer: &mut W,
+        buffer_uri: GltfBufferUri,
+    ) -> Result<Vec<u8>> {
+        let (gltf, data) = self.into_gltf(buffer_uri);
+        serde_json::to_writer_pretty(&mut *writer, &gltf)?;
+        Ok(data)
+    }
+
+    //dp write_glb
+    /// Complete the export, writing a GLB file with the Gltf Json chunk
+    /// and the binary buffer chunk
+    #[cfg(feature = "serde_json")]
+    pub fn write_glb<W: std::io::Write>(self, writer: &mut W) -> Result<()> {
+        let (gltf, data) = self.into_gltf(GltfBufferUri::Glb);
+        let opt_buffer_0 = if data.is_empty() {
+            None
+        } else {
+            Some(&data[..])
+        };
+        crate::glb_save(writer, &gltf, opt_buffer_0)
+    }
+
+    //zz All done
+}
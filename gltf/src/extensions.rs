@@ -0,0 +1,149 @@
+//a Imports
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::GltfSpecularGlossiness;
+use crate::{GltfClearcoat, GltfEmissiveStrength, GltfIor, GltfTransmission, GltfUnlit};
+use crate::{GltfLightsPunctual, GltfNodeLight, GltfTextureTransform};
+use crate::{GltfMeshGpuInstancing, GltfMeshoptCompression};
+
+//a GltfExtensions
+//tp GltfExtensions
+/// The supported extensions of the Gltf root object
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GltfExtensions {
+    /// Punctual lights (KHR_lights_punctual)
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            rename = "KHR_lights_punctual",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub lights_punctual: Option<GltfLightsPunctual>,
+}
+
+//a GltfNodeExtensions
+//tp GltfNodeExtensions
+/// The supported extensions of a Gltf node
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GltfNodeExtensions {
+    /// A punctual light placed at the node (KHR_lights_punctual)
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            rename = "KHR_lights_punctual",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub light: Option<GltfNodeLight>,
+    /// Instances of the mesh of the node (EXT_mesh_gpu_instancing)
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            rename = "EXT_mesh_gpu_instancing",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub gpu_instancing: Option<GltfMeshGpuInstancing>,
+}
+
+//a GltfBufferViewExtensions
+//tp GltfBufferViewExtensions
+/// The supported extensions of a Gltf buffer view
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GltfBufferViewExtensions {
+    /// Compressed contents of the view (EXT_meshopt_compression)
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            rename = "EXT_meshopt_compression",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub meshopt_compression: Option<GltfMeshoptCompression>,
+}
+
+//a GltfTextureInfoExtensions
+//tp GltfTextureInfoExtensions
+/// The supported extensions of a Gltf texture info
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GltfTextureInfoExtensions {
+    /// Transformation of the texture coordinates (KHR_texture_transform)
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            rename = "KHR_texture_transform",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub texture_transform: Option<GltfTextureTransform>,
+}
+
+//a GltfMaterialExtensions
+//tp GltfMaterialExtensions
+/// The supported extensions of a Gltf material
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GltfMaterialExtensions {
+    /// Multiplier for the emissive color (KHR_materials_emissive_strength)
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            rename = "KHR_materials_emissive_strength",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub emissive_strength: Option<GltfEmissiveStrength>,
+    /// Present if the material is unlit (KHR_materials_unlit)
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            rename = "KHR_materials_unlit",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub unlit: Option<GltfUnlit>,
+    /// Clearcoat layer (KHR_materials_clearcoat)
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            rename = "KHR_materials_clearcoat",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub clearcoat: Option<GltfClearcoat>,
+    /// Transmission (KHR_materials_transmission)
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            rename = "KHR_materials_transmission",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub transmission: Option<GltfTransmission>,
+    /// Index of refraction (KHR_materials_ior)
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "KHR_materials_ior", skip_serializing_if = "Option::is_none")
+    )]
+    pub ior: Option<GltfIor>,
+    /// Specular-glossiness model (KHR_materials_pbrSpecularGlossiness)
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            rename = "KHR_materials_pbrSpecularGlossiness",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub specular_glossiness: Option<GltfSpecularGlossiness>,
+}
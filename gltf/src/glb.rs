@@ -33,32 +33,47 @@
 ///
 /// Methods are required on the Gltf descriptor that access the
 /// scenes; the default scene; the nodes by name; the skeletons; etc.
-use serde_json::json;
+use std::io::{Read, Take};
+
 use serde_json::Value as JsonValue;
 
 use crate::{Error, Gltf, Result};
 
-struct GlbLoader<'file, F: std::io::Read, B> {
+//a Constants
+/// The chunk type of the Json chunk, which must be the first chunk
+const CHUNK_JSON: [u8; 4] = [0x4a, 0x53, 0x4f, 0x4e];
+
+/// The chunk type of the binary chunk, which if present must directly
+/// follow the Json chunk
+const CHUNK_BIN: [u8; 4] = [0x42, 0x49, 0x4e, 0x00];
+
+//a GlbLoader
+//ti GlbLoader
+struct GlbLoader<'file, F: Read, B> {
     file: &'file mut F,
     max_json_length: usize,
-    byte_length: u32,
-    json_value: JsonValue,
+    /// Byte length of the GLB file given by its header
+    byte_length: usize,
+    /// Number of bytes of the file consumed so far
+    bytes_read: usize,
+    /// Number of chunks read so far
+    num_chunks: usize,
+    json_value: Option<JsonValue>,
     buffer_0: Option<B>,
 }
-impl<'file, F: std::io::Read, B> GlbLoader<'file, F, B> {
+impl<'file, F: Read, B> GlbLoader<'file, F, B> {
     //cp new
     /// Create a new GlbLoader given a file and maximum length
     /// expected of the Json within it
     fn new(file: &'file mut F, max_json_length: usize) -> Self {
-        let byte_length = 0;
-        let json_value = json!(null);
-        let buffer_0 = None;
         Self {
             file,
             max_json_length,
-            byte_length,
-            json_value,
-            buffer_0,
+            byte_length: 0,
+            bytes_read: 0,
+            num_chunks: 0,
+            json_value: None,
+            buffer_0: None,
         }
     }
 
@@ -71,69 +86,111 @@ impl<'file, F: std::io::Read, B> GlbLoader<'file, F, B> {
         if hdr[0..8] != [0x67, 0x6c, 0x54, 0x46, 0x02, 0x00, 0x00, 0x00] {
             return Err(Error::GlbHdr);
         }
-        let byte_length = (hdr[8] as u32)
-            | ((hdr[9] as u32) << 8)
-            | ((hdr[10] as u32) << 16)
-            | ((hdr[11] as u32) << 24);
-        self.byte_length = byte_length;
+        self.byte_length = u32::from_le_bytes([hdr[8], hdr[9], hdr[10], hdr[11]]) as usize;
+        self.bytes_read = 12;
+        if self.byte_length < self.bytes_read {
+            return Err(Error::GlbLength);
+        }
         Ok(())
     }
 
-    //mp read_json
-    /// Read the Json chunk of the GLB file (assuming the header has
-    /// been read), and validate the lengtht of it, and parse the Json
+    //mp read_chunk_hdr
+    /// Read the header of the next chunk, returning its byte length and
+    /// chunk type; return None if the file is complete
     ///
-    /// Do not at this point attempt to validate the Json to be Gltf Json
-    fn read_json(&mut self) -> Result<()> {
+    /// The chunk must lie within the byte length given by the GLB
+    /// header
+    fn read_chunk_hdr(&mut self) -> Result<Option<(usize, [u8; 4])>> {
+        let remaining = self.byte_length - self.bytes_read;
+        if remaining == 0 {
+            return Ok(None);
+        }
+        if remaining < 8 {
+            return Err(Error::GlbLength);
+        }
         let mut hdr = [0; 8];
-        self.file.read_exact(&mut hdr).map_err(Error::GlbJsonIo)?;
-        if hdr[4..8] != [0x4a, 0x53, 0x4f, 0x4e] {
-            return Err(Error::GlbJsonHdr);
+        self.file.read_exact(&mut hdr).map_err(Error::GlbChunkIo)?;
+        let chunk_length = u32::from_le_bytes([hdr[0], hdr[1], hdr[2], hdr[3]]) as usize;
+        if chunk_length > remaining - 8 {
+            return Err(Error::GlbLength);
         }
-        let json_byte_length = (hdr[0] as u32)
-            | ((hdr[1] as u32) << 8)
-            | ((hdr[2] as u32) << 16)
-            | ((hdr[3] as u32) << 24);
-        let json_byte_length = json_byte_length as usize;
+        self.bytes_read += 8 + chunk_length;
+        self.num_chunks += 1;
+        Ok(Some((chunk_length, [hdr[4], hdr[5], hdr[6], hdr[7]])))
+    }
+
+    //mp read_json
+    /// Read the Json chunk data of the GLB file, given its byte length,
+    /// and parse the Json
+    ///
+    /// Do not at this point attempt to validate the Json to be Gltf Json
+    fn read_json(&mut self, json_byte_length: usize) -> Result<()> {
         if json_byte_length > self.max_json_length {
-            return Err(Error::GlbJsonHdr);
+            return Err(Error::GlbJsonTooLong);
         }
         let mut buffer = vec![0; json_byte_length];
-        let bytes_read = self.file.read(&mut buffer)?;
-        if bytes_read < json_byte_length {
-            return Err(Error::GlbJsonLength);
+        self.file.read_exact(&mut buffer).map_err(|e| {
+            if e.kind() == std::io::ErrorKind::UnexpectedEof {
+                Error::GlbJsonLength
+            } else {
+                Error::GlbJsonIo(e)
+            }
+        })?;
+        // Padding should be spaces, but permit zeros
+        while buffer.last() == Some(&0) {
+            buffer.pop();
         }
-        self.json_value = serde_json::from_str(std::str::from_utf8(&buffer)?)?;
+        self.json_value = Some(serde_json::from_str(std::str::from_utf8(&buffer)?)?);
         Ok(())
     }
 
     //mp read_buffer
-    /// Read the 'binary' buffer  chunk of the GLB if it is present
+    /// Read the 'binary' buffer chunk data of the GLB file, given its
+    /// byte length
     ///
-    /// This does *NOT* support GLB files with a Json chunk and an
-    /// extension chunk without a binary chunk. It consumes the header
-    /// of the next Glb chunk and checks that it is a binary chunk; if
-    /// no header is there (no binary) then all is okay, but if there
-    /// is no binary chunk but there is an extension chunk then its
-    /// header will have been consumed
-    fn read_buffer<BR>(&mut self, buf_reader: &BR) -> Result<()>
+    /// The buffer reader is limited to the chunk; anything it leaves
+    /// unread is skipped
+    fn read_buffer<BR>(&mut self, buf_reader: &BR, bin_byte_length: usize) -> Result<()>
     where
-        BR: Fn(&mut F, usize) -> std::result::Result<Option<B>, std::io::Error>,
+        BR: Fn(&mut Take<&mut F>, usize) -> std::result::Result<Option<B>, std::io::Error>,
     {
-        let mut hdr = [0; 8];
-        let n = self.file.read(&mut hdr).map_err(Error::GlbJsonIo)?;
-        if n == 0 {
-            return Ok(());
+        let mut chunk = (&mut *self.file).take(bin_byte_length as u64);
+        self.buffer_0 = buf_reader(&mut chunk, bin_byte_length).map_err(Error::GlbBinIo)?;
+        let unread = chunk.limit() as usize;
+        self.skip_chunk(unread)
+    }
+
+    //mp skip_chunk
+    /// Skip the data of a chunk that is not supported
+    fn skip_chunk(&mut self, byte_length: usize) -> Result<()> {
+        let mut chunk = (&mut *self.file).take(byte_length as u64);
+        let skipped = std::io::copy(&mut chunk, &mut std::io::sink()).map_err(Error::GlbChunkIo)?;
+        if skipped < byte_length as u64 {
+            return Err(Error::GlbChunkIo(std::io::ErrorKind::UnexpectedEof.into()));
         }
-        if hdr[4..8] != [0x42, 0x49, 0x4e, 0x00] {
-            return Err(Error::GlbBinHdr);
+        Ok(())
+    }
+
+    //mp read_chunks
+    /// Read all of the chunks of the GLB file
+    ///
+    /// The first chunk must be the Json chunk; the binary chunk is
+    /// optional, but if present must be the second chunk. Any other
+    /// chunks (extensions) are skipped. The chunks must exactly fill
+    /// the byte length given in the GLB header.
+    fn read_chunks<BR>(&mut self, buf_reader: &BR) -> Result<()>
+    where
+        BR: Fn(&mut Take<&mut F>, usize) -> std::result::Result<Option<B>, std::io::Error>,
+    {
+        while let Some((chunk_length, chunk_type)) = self.read_chunk_hdr()? {
+            match (self.num_chunks, chunk_type) {
+                (1, CHUNK_JSON) => self.read_json(chunk_length)?,
+                (1, _) => return Err(Error::GlbJsonHdr),
+                (2, CHUNK_BIN) => self.read_buffer(buf_reader, chunk_length)?,
+                (_, CHUNK_JSON) | (_, CHUNK_BIN) => return Err(Error::GlbChunkOrder),
+                _ => self.skip_chunk(chunk_length)?,
+            }
         }
-        let bin_byte_length = (hdr[0] as u32)
-            | ((hdr[1] as u32) << 8)
-            | ((hdr[2] as u32) << 16)
-            | ((hdr[3] as u32) << 24);
-        let bin_byte_length = bin_byte_length as usize;
-        self.buffer_0 = buf_reader(self.file, bin_byte_length).map_err(Error::GlbBinIo)?;
         Ok(())
     }
 
@@ -141,19 +198,82 @@ impl<'file, F: std::io::Read, B> GlbLoader<'file, F, B> {
     /// Drop the borrow of the file and return a GltfJsonValue and the
     /// binary buffer loaded from the Glb file (if any)
     fn into_gltf(self) -> Result<(Gltf, Option<B>)> {
-        let gltf_json_value = Gltf::of_json_value(self.json_value)?;
+        let Some(json_value) = self.json_value else {
+            return Err(Error::GlbJsonHdr);
+        };
+        let gltf_json_value = Gltf::of_json_value(json_value)?;
         Ok((gltf_json_value, self.buffer_0))
     }
 }
 
+//fp glb_load
+/// Load a GLB file, returning the [Gltf] and the binary chunk (if any)
+///
+/// The binary chunk is read by invoking the buffer reader with the
+/// file (limited to the chunk) and the byte length of the chunk; any
+/// of the chunk that it does not read is skipped
 pub fn glb_load<F, B, BR>(file: &mut F, b: &BR, max_json_length: usize) -> Result<(Gltf, Option<B>)>
 where
-    F: std::io::Read,
-    BR: Fn(&mut F, usize) -> std::result::Result<Option<B>, std::io::Error>,
+    F: Read,
+    BR: Fn(&mut Take<&mut F>, usize) -> std::result::Result<Option<B>, std::io::Error>,
 {
     let mut loader = GlbLoader::new(file, max_json_length);
     loader.read_glb_hdr()?;
-    loader.read_json()?;
-    loader.read_buffer(b)?;
+    loader.read_chunks(b)?;
     loader.into_gltf()
 }
+
+//fi write_chunk
+/// Write a GLB chunk header and its data, padding the data to a
+/// multiple of 4 bytes with the pad byte
+fn write_chunk<W: std::io::Write>(
+    writer: &mut W,
+    chunk_type: &[u8; 4],
+    data: &[u8],
+    pad: u8,
+) -> Result<()> {
+    let padding = glb_padding(data.len());
+    let byte_length = (data.len() + padding) as u32;
+    writer.write_all(&byte_length.to_le_bytes())?;
+    writer.write_all(chunk_type)?;
+    writer.write_all(data)?;
+    writer.write_all(&[pad; 3][0..padding])?;
+    Ok(())
+}
+
+//fi glb_padding
+/// Get the number of bytes of padding required after data of a length
+/// to keep GLB chunks on 4-byte boundaries
+fn glb_padding(byte_length: usize) -> usize {
+    (4 - (byte_length % 4)) % 4
+}
+
+//fp glb_save
+/// Save a [Gltf] as a GLB file, with an optional binary chunk
+///
+/// The binary chunk, if provided, is buffer 0 of the Gltf, and that
+/// buffer must not have a URI; a [crate::GltfBuffer] created with an
+/// empty URI has its URI absent from the Json.
+///
+/// The Json chunk is padded with spaces and the binary chunk with zeros,
+/// so that each chunk is a multiple of 4 bytes long
+pub fn glb_save<W>(writer: &mut W, gltf: &Gltf, opt_buffer_0: Option<&[u8]>) -> Result<()>
+where
+    W: std::io::Write,
+{
+    let json = serde_json::to_vec(gltf)?;
+    let mut byte_length = 12 + 8 + json.len() + glb_padding(json.len());
+    if let Some(buffer) = opt_buffer_0 {
+        byte_length += 8 + buffer.len() + glb_padding(buffer.len());
+    }
+    let Ok(byte_length) = u32::try_from(byte_length) else {
+        return Err(Error::GlbTooLarge);
+    };
+    writer.write_all(&[0x67, 0x6c, 0x54, 0x46, 0x02, 0x00, 0x00, 0x00])?;
+    writer.write_all(&byte_length.to_le_bytes())?;
+    write_chunk(writer, &CHUNK_JSON, &json, b' ')?;
+    if let Some(buffer) = opt_buffer_0 {
+        write_chunk(writer, &CHUNK_BIN, buffer, 0)?;
+    }
+    Ok(())
+}
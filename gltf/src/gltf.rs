@@ -12,14 +12,16 @@ use serde_json::Value as JsonValue;
 pub type JsonValue = ();
 
 use crate::{
-    AccessorIndex, BufferIndex, ImageIndex, Indexable, MaterialIndex, MeshIndex, NHIndex,
-    NodeIndex, SceneIndex, TextureIndex, ViewIndex,
+    AccessorIndex, AnimationIndex, BufferIndex, CameraIndex, ImageIndex, Indexable, LightIndex,
+    MaterialIndex, MeshIndex, NHIndex, NodeIndex, SamplerIndex, SceneIndex, SkinIndex,
+    TextureIndex, ViewIndex,
 };
-use crate::{Error, Named, Result};
 use crate::{
-    GltfAccessor, GltfAsset, GltfBuffer, GltfBufferView, GltfImage, GltfMaterial, GltfMesh,
-    GltfNode, GltfScene, GltfTexture,
+    GltfAccessor, GltfAnimation, GltfAsset, GltfBuffer, GltfBufferView, GltfCamera, GltfExtensions,
+    GltfImage, GltfLight, GltfMaterial, GltfMesh, GltfNode, GltfPrimitive, GltfSampler, GltfScene,
+    GltfSkin, GltfTexture,
 };
+use crate::{Named, Result, ValidationReport};
 
 //a Gltf
 //tp Gltf
@@ -33,6 +35,13 @@ pub struct Gltf {
     /// information
     asset: GltfAsset,
 
+    /// The names of the extensions used by the Json file
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "extensionsUsed", skip_serializing_if = "Vec::is_empty")
+    )]
+    extensions_used: Vec<String>,
+
     /// All the 'buffers' from the Json file, in gltf order; this is
     /// the URI but not any client-side buffer representation
     buffers: Vec<GltfBuffer>,
@@ -63,6 +72,7 @@ pub struct Gltf {
     nodes: Vec<GltfNode>,
 
     /// The default scene to be presented by the gltf
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     scene: Option<SceneIndex>,
 
     /// The scenes in the gltf; each refers to an array of NodeIndex
@@ -70,25 +80,33 @@ pub struct Gltf {
     /// rendered for a scene
     scenes: Vec<GltfScene>,
 
-    /// The camers in the gltf; currently unfilled
-    cameras: Vec<JsonValue>,
+    /// The cameras in the gltf; nodes refer to these by CameraIndex
+    /// to place them in the scenes
+    cameras: Vec<GltfCamera>,
 
     /// The image descriptors from the Json file; this is the URI or
     /// buffer views, not the underlying image data
     images: Vec<GltfImage>,
 
     /// The sampler descriptors from the Json file
-    samplers: JsonValue,
+    samplers: Vec<GltfSampler>,
 
     /// The texture descriptors from the Json file; these refer to
     /// SamplerIndex and ImageIndex
     textures: Vec<GltfTexture>,
 
-    /// The skin (skeleton) descriptors from the Json file
-    skins: Vec<JsonValue>,
+    /// The skin (skeleton) descriptors from the Json file; these refer to
+    /// NodeIndex for the joints and AccessorIndex for the inverse bind
+    /// matrices
+    skins: Vec<GltfSkin>,
+
+    /// The animations in the Json file; these refer to NodeIndex for their
+    /// targets and AccessorIndex for their keyframe times and values
+    animations: Vec<GltfAnimation>,
 
-    /// The animations in the Json file
-    animations: JsonValue,
+    /// The supported extensions of the Json file, such as the lights
+    /// of KHR_lights_punctual
+    extensions: GltfExtensions,
 
     /// The hierarchy of nodes
     ///
@@ -124,6 +142,13 @@ impl std::ops::Index<AccessorIndex> for Gltf {
     }
 }
 
+//ip IndexMut<AccessorIndex> for Gltf
+impl std::ops::IndexMut<AccessorIndex> for Gltf {
+    fn index_mut(&mut self, index: AccessorIndex) -> &mut Self::Output {
+        &mut self.accessors[index.as_usize()]
+    }
+}
+
 //ip Index<ViewIndex> for Gltf
 impl std::ops::Index<ViewIndex> for Gltf {
     type Output = GltfBufferView;
@@ -164,12 +189,69 @@ impl std::ops::Index<MaterialIndex> for Gltf {
     }
 }
 
+//ip Index<SkinIndex> for Gltf
+impl std::ops::Index<SkinIndex> for Gltf {
+    type Output = GltfSkin;
+    fn index(&self, index: SkinIndex) -> &Self::Output {
+        &self.skins[index.as_usize()]
+    }
+}
+
+//ip Index<CameraIndex> for Gltf
+impl std::ops::Index<CameraIndex> for Gltf {
+    type Output = GltfCamera;
+    fn index(&self, index: CameraIndex) -> &Self::Output {
+        &self.cameras[index.as_usize()]
+    }
+}
+
+//ip Index<SamplerIndex> for Gltf
+impl std::ops::Index<SamplerIndex> for Gltf {
+    type Output = GltfSampler;
+    fn index(&self, index: SamplerIndex) -> &Self::Output {
+        &self.samplers[index.as_usize()]
+    }
+}
+
+//ip Index<LightIndex> for Gltf
+impl std::ops::Index<LightIndex> for Gltf {
+    type Output = GltfLight;
+    fn index(&self, index: LightIndex) -> &Self::Output {
+        &self.lights()[index.as_usize()]
+    }
+}
+
+//ip Index<SceneIndex> for Gltf
+impl std::ops::Index<SceneIndex> for Gltf {
+    type Output = GltfScene;
+    fn index(&self, index: SceneIndex) -> &Self::Output {
+        &self.scenes[index.as_usize()]
+    }
+}
+
+//ip Index<AnimationIndex> for Gltf
+impl std::ops::Index<AnimationIndex> for Gltf {
+    type Output = GltfAnimation;
+    fn index(&self, index: AnimationIndex) -> &Self::Output {
+        &self.animations[index.as_usize()]
+    }
+}
+
 //ip Gltf
 impl Gltf {
     pub fn set_asset(&mut self, asset: GltfAsset) {
         self.asset = asset;
     }
 
+    //mp add_extension_used
+    /// Add the name of an extension to those used by the Gltf, if it
+    /// is not already there
+    pub fn add_extension_used(&mut self, name: &str) {
+        if !self.extensions_used.iter().any(|e| e == name) {
+            self.extensions_used.push(name.into());
+        }
+    }
+
     pub fn add_buffer(&mut self, buffer: GltfBuffer) -> BufferIndex {
         let n = self.buffers.len();
         self.buffers.push(buffer);
@@ -185,11 +267,49 @@ impl Gltf {
         self.nodes.push(node);
         n.into()
     }
+    pub fn add_skin(&mut self, skin: GltfSkin) -> SkinIndex {
+        let n = self.skins.len();
+        self.skins.push(skin);
+        n.into()
+    }
+    pub fn add_animation(&mut self, animation: GltfAnimation) -> AnimationIndex {
+        let n = self.animations.len();
+        self.animations.push(animation);
+        n.into()
+    }
+    pub fn add_camera(&mut self, camera: GltfCamera) -> CameraIndex {
+        let n = self.cameras.len();
+        self.cameras.push(camera);
+        n.into()
+    }
     pub fn add_scene(&mut self, scene: GltfScene) -> SceneIndex {
         let n = self.scenes.len();
         self.scenes.push(scene);
         n.into()
     }
+    pub fn set_scene(&mut self, scene: Option<SceneIndex>) {
+        self.scene = scene;
+    }
+    pub fn add_material(&mut self, material: GltfMaterial) -> MaterialIndex {
+        let n = self.materials.len();
+        self.materials.push(material);
+        n.into()
+    }
+    pub fn add_image(&mut self, image: GltfImage) -> ImageIndex {
+        let n = self.images.len();
+        self.images.push(image);
+        n.into()
+    }
+    pub fn add_sampler(&mut self, sampler: GltfSampler) -> SamplerIndex {
+        let n = self.samplers.len();
+        self.samplers.push(sampler);
+        n.into()
+    }
+    pub fn add_texture(&mut self, texture: GltfTexture) -> TextureIndex {
+        let n = self.textures.len();
+        self.textures.push(texture);
+        n.into()
+    }
     pub fn add_view(
         &mut self,
         buffer: BufferIndex,
@@ -202,6 +322,7 @@ impl Gltf {
             byte_length,
             byte_offset,
             byte_stride,
+            extensions: Default::default(),
         };
 
         let n = self.buffer_views.len();
@@ -229,102 +350,801 @@ impl Gltf {
         n.into()
     }
     //mp validate_buffer_views
-    /// Validate the contents - check indices in range, etc
-    fn validate_buffer_views(&self) -> Result<()> {
+    /// Validate the buffer views - check buffer indices in range, and
+    /// that each view fits inside its buffer
+    fn validate_buffer_views(&self, report: &mut ValidationReport) {
         let n = self.buffers.len();
         for (i, bv) in self.buffer_views.iter().enumerate() {
             let b = bv.buffer();
-            if b.as_usize() >= n {
-                return Err(Error::BadJson(format!(
-                    "Buffer view index {i} has buffer {b} out of range (must be < {n})",
-                )));
-            }
-            let l = self.buffers[b.as_usize()].byte_length();
+            let Some(buffer) = self.buffers.get(b.as_usize()) else {
+                report.add(
+                    format!("/bufferViews/{i}/buffer"),
+                    format!("Buffer view index {i} has buffer {b} out of range (must be < {n})"),
+                );
+                continue;
+            };
+            let l = buffer.byte_length();
             if bv.byte_end() > l {
-                return Err(Error::BadJson(format!(
-                    "Buffer view index {i} specifies subrange outside the buffer size {l})",
-                )));
+                report.add(
+                    format!("/bufferViews/{i}/byteLength"),
+                    format!("Buffer view index {i} specifies subrange outside the buffer size {l}"),
+                );
+            }
+            if let Some(compression) = bv.meshopt_compression() {
+                report.add_result(
+                    format!("/bufferViews/{i}/extensions/EXT_meshopt_compression"),
+                    compression.validate(i.into(), bv.byte_length(), &self.buffers),
+                );
             }
         }
-        Ok(())
     }
 
     //mp validate_accessors
-    /// Validate the contents - check indices in range, etc
-    fn validate_accessors(&self) -> Result<()> {
+    /// Validate the accessors - check buffer view indices in range, and
+    /// that the data (including any sparse data) fits inside the views
+    fn validate_accessors(&self, report: &mut ValidationReport) {
         let n = self.buffer_views.len();
-        for acc in &self.accessors {
-            let bv_index = acc.buffer_view();
-            let Some(bv_index) = bv_index else {
-                return Err(Error::BadJson(
-                    "Accessor is not permitted to not specify a BufferView in this GLTF reader"
-                        .into(),
-                ));
+        let check_view = |report: &mut ValidationReport,
+                          pointer: String,
+                          bv_index: ViewIndex,
+                          byte_end: usize| {
+            let Some(bv) = self.buffer_views.get(bv_index.as_usize()) else {
+                report.add(
+                    format!("{pointer}/bufferView"),
+                    format!("Accessor's buffer view index {bv_index} out of range (must be < {n})"),
+                );
+                return;
             };
-            if bv_index.as_usize() >= n {
-                return Err(Error::BadJson(format!(
-                    "Accessor's buffer view index {bv_index} out of range (must be < {n})",
-                )));
-            }
-            let bv = &self.buffer_views[bv_index.as_usize()];
-            let acc_byte_end = acc.byte_view_end(bv.byte_stride(0));
-            if acc_byte_end > bv.byte_length() {
-                return Err(Error::BadJson(format!(
-                    "Accessor's last element ends (@{0}) beyond end of buffer view index {1} (at {2})",
-                    acc_byte_end,
-                    bv.buffer(),
-                    bv.byte_length()
-                )));
+            if byte_end > bv.byte_length() {
+                report.add(
+                        pointer,
+                        format!(
+                            "Accessor's last element ends (@{byte_end}) beyond end of buffer view index {bv_index} (at {})",
+                            bv.byte_length()
+                        ),
+                    );
+            }
+        };
+        for (i, acc) in self.accessors.iter().enumerate() {
+            if let Some(bv_index) = acc.buffer_view() {
+                let byte_stride = self
+                    .buffer_views
+                    .get(bv_index.as_usize())
+                    .map_or(0, |bv| bv.byte_stride(0));
+                let byte_end = {
+                    if acc.count() > 0 {
+                        acc.byte_view_end(byte_stride)
+                    } else {
+                        0
+                    }
+                };
+                check_view(report, format!("/accessors/{i}"), bv_index, byte_end);
+            }
+            if let Some(sparse) = acc.sparse() {
+                if sparse.count > acc.count() {
+                    report.add(
+                        format!("/accessors/{i}/sparse/count"),
+                        format!(
+                            "Sparse accessor count {0} exceeds accessor count {1}",
+                            sparse.count,
+                            acc.count()
+                        ),
+                    );
+                }
+                let index_size = sparse.indices.component_type.byte_length() as usize;
+                check_view(
+                    report,
+                    format!("/accessors/{i}/sparse/indices"),
+                    sparse.indices.buffer_view,
+                    sparse.indices.byte_offset + index_size * sparse.count,
+                );
+                check_view(
+                    report,
+                    format!("/accessors/{i}/sparse/values"),
+                    sparse.values.buffer_view,
+                    sparse.values.byte_offset + acc.ele_byte_size() * sparse.count,
+                );
+            }
+        }
+    }
+
+    //mi validate_attribute
+    /// Validate an attribute (or morph target) accessor of a mesh
+    /// primitive, returning its count if it is in range
+    fn validate_attribute(
+        &self,
+        report: &mut ValidationReport,
+        pointer: &str,
+        mesh: usize,
+        attr: mod3d_base::VertexAttr,
+        a: AccessorIndex,
+        is_target: bool,
+    ) -> Option<usize> {
+        let pointer = format!("{pointer}/{}", GltfPrimitive::attribute_name(attr));
+        let Some(ba) = self.accessors.get(a.as_usize()) else {
+            report.add(
+                pointer,
+                format!("Mesh {mesh} has attribute {attr:?} accessor {a} out of range"),
+            );
+            return None;
+        };
+        if !GltfPrimitive::attribute_type_permitted(
+            attr,
+            is_target,
+            ba.component_type(),
+            ba.is_normalized(),
+        ) {
+            report.add(
+                pointer,
+                format!(
+                    "Mesh {mesh} has attribute {attr:?} accessor {a} with a component type that is not permitted"
+                ),
+            );
+        }
+        Some(ba.count())
+    }
+
+    //mp validate_meshes
+    /// Validate the meshes - check that the morph targets of the
+    /// primitives of each mesh match, that their accessors are in
+    /// range and of permitted types, and that the attributes of each
+    /// primitive have the same count
+    ///
+    /// Attributes that are ignored (as they have no
+    /// mod3d_base::VertexAttr) are reported as warnings
+    pub fn validate_meshes(&self, report: &mut ValidationReport) {
+        for (i, m) in self.meshes.iter().enumerate() {
+            let num_targets = m.num_targets();
+            if !m.weights().is_empty() && m.weights().len() != num_targets {
+                report.add(
+                    format!("/meshes/{i}/weights"),
+                    format!("Mesh {i} has morph target weights that do not match its targets"),
+                );
+            }
+            for (j, p) in m.primitives().iter().enumerate() {
+                let pointer = format!("/meshes/{i}/primitives/{j}");
+                if p.targets().len() != num_targets {
+                    report.add(
+                        format!("{pointer}/targets"),
+                        format!("Mesh {i} has primitives with differing numbers of morph targets"),
+                    );
+                }
+                let mut vertex_count = None;
+                let attributes = format!("{pointer}/attributes");
+                for (name, _) in p.ignored_attributes() {
+                    let escaped = name.replace('~', "~0").replace('/', "~1");
+                    report.add_warning(
+                        format!("{attributes}/{escaped}"),
+                        format!("Mesh {i} primitive {j} attribute {name} is not supported, and is ignored"),
+                    );
+                }
+                for (attr, a) in p.attributes() {
+                    let Some(count) =
+                        self.validate_attribute(report, &attributes, i, *attr, *a, false)
+                    else {
+                        continue;
+                    };
+                    if vertex_count.is_some_and(|c| c != count) {
+                        report.add(
+                            &attributes,
+                            format!("Mesh {i} primitive {j} has attributes with differing counts"),
+                        );
+                    }
+                    vertex_count = Some(count);
+                }
+                for (k, t) in p.targets().iter().enumerate() {
+                    let target = format!("{pointer}/targets/{k}");
+                    for (attr, a) in t {
+                        let count = self.validate_attribute(report, &target, i, *attr, *a, true);
+                        if count.is_some() && vertex_count.is_some() && count != vertex_count {
+                            report.add(
+                                &target,
+                                format!(
+                                    "Mesh {i} primitive {j} has morph target {k} with a count that differs from its attributes"
+                                ),
+                            );
+                        }
+                    }
+                }
+                if let Some(a) = p.indices() {
+                    use mod3d_base::BufferElementType::*;
+                    match self.accessors.get(a.as_usize()) {
+                        None => report.add(
+                            format!("{pointer}/indices"),
+                            format!("Mesh {i} has indices accessor {a} out of range"),
+                        ),
+                        Some(ba)
+                            if ba.elements_per_data() != 1
+                                || ba.is_normalized()
+                                || !matches!(ba.component_type(), UInt8 | UInt16 | UInt32) =>
+                        {
+                            report.add(
+                                format!("{pointer}/indices"),
+                                format!(
+                                    "Mesh {i} has indices accessor {a} that is not unsigned integer scalars"
+                                ),
+                            )
+                        }
+                        _ => (),
+                    }
+                }
+                if let Some(material) = p.material() {
+                    if material.as_usize() >= self.materials.len() {
+                        report.add(
+                            format!("{pointer}/material"),
+                            format!("Mesh {i} has material index {material} out of range"),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    //mp validate_index_values
+    /// Validate the index values of the mesh primitives - check that
+    /// each is below the vertex count of its primitive, and that the
+    /// indices are unsigned bytes, shorts or ints
+    ///
+    /// This requires the buffer data, which the 'buffer_data' function
+    /// should provide for a buffer if it has been loaded; index data in
+    /// buffers without data, in meshopt compressed buffer views, or in
+    /// sparse accessors is not checked
+    pub fn validate_index_values<'a, F>(&self, report: &mut ValidationReport, buffer_data: &F)
+    where
+        F: Fn(BufferIndex) -> Option<&'a [u8]>,
+    {
+        use mod3d_base::BufferElementType::*;
+        for (i, m) in self.meshes.iter().enumerate() {
+            for (j, p) in m.primitives().iter().enumerate() {
+                let Some(ba) = p.indices().and_then(|a| self.accessors.get(a.as_usize())) else {
+                    continue;
+                };
+                let pointer = format!("/meshes/{i}/primitives/{j}/indices");
+                if !matches!(ba.component_type(), UInt8 | UInt16 | UInt32) {
+                    report.add(
+                        pointer,
+                        format!(
+                            "Mesh {i} primitive {j} has indices that are not unsigned bytes, shorts or ints"
+                        ),
+                    );
+                    continue;
+                }
+                let vertex_count = p
+                    .attributes()
+                    .iter()
+                    .find(|(attr, _)| *attr == mod3d_base::VertexAttr::Position)
+                    .or(p.attributes().first())
+                    .and_then(|(_, a)| self.accessors.get(a.as_usize()))
+                    .map(|acc| acc.count());
+                let Some(vertex_count) = vertex_count else {
+                    continue;
+                };
+                if ba.sparse().is_some() || ba.count() == 0 {
+                    continue;
+                }
+                let Some(bv) = ba
+                    .buffer_view()
+                    .and_then(|bv| self.buffer_views.get(bv.as_usize()))
+                else {
+                    continue;
+                };
+                if bv.meshopt_compression().is_some() {
+                    continue;
+                }
+                let Some(data) = buffer_data(bv.buffer()) else {
+                    continue;
+                };
+                let byte_stride = ba.byte_stride(bv.byte_stride(0));
+                let byte_start = bv.byte_offset() + ba.byte_offset();
+                if bv.byte_offset() + ba.byte_view_end(byte_stride) > data.len() {
+                    report.add(
+                        pointer,
+                        format!(
+                            "Mesh {i} primitive {j} has indices beyond the end of the buffer data"
+                        ),
+                    );
+                    continue;
+                }
+                let max_index = (0..ba.count())
+                    .map(|k| {
+                        let d = &data[byte_start + k * byte_stride..];
+                        match ba.component_type() {
+                            UInt8 => d[0] as usize,
+                            UInt16 => u16::from_le_bytes([d[0], d[1]]) as usize,
+                            _ => u32::from_le_bytes([d[0], d[1], d[2], d[3]]) as usize,
+                        }
+                    })
+                    .max()
+                    .unwrap_or(0);
+                if max_index >= vertex_count {
+                    report.add(
+                        pointer,
+                        format!(
+                            "Mesh {i} primitive {j} has index {max_index} which is not below its vertex count {vertex_count}"
+                        ),
+                    );
+                }
             }
         }
-        Ok(())
     }
 
     //mp validate_nodes
-    pub fn validate_nodes(&self) -> Result<()> {
+    /// Validate the nodes - check indices in range, and that the morph
+    /// target weights and instancing attributes match
+    pub fn validate_nodes(&self, report: &mut ValidationReport) {
         let l = self.nodes.len();
         for (i, n) in self.nodes.iter().enumerate() {
-            for c in n.iter_children() {
+            for (k, c) in n.iter_children().enumerate() {
                 if c.as_usize() >= l {
-                    return Err(Error::BadJson(format!(
-                        "Node {i} has child index {c} out of range",
-                    )));
+                    report.add(
+                        format!("/nodes/{i}/children/{k}"),
+                        format!("Node {i} has child index {c} out of range"),
+                    );
                 }
             }
             if let Some(m) = n.mesh() {
-                if m.as_usize() > self.meshes.len() {
-                    return Err(Error::BadJson(format!(
-                        "Node {i} has mesh index {m} out of range",
-                    )));
+                match self.meshes.get(m.as_usize()) {
+                    None => report.add(
+                        format!("/nodes/{i}/mesh"),
+                        format!("Node {i} has mesh index {m} out of range"),
+                    ),
+                    Some(mesh) => {
+                        if n.weights().is_some_and(|w| w.len() != mesh.num_targets()) {
+                            report.add(
+                                format!("/nodes/{i}/weights"),
+                                format!(
+                                    "Node {i} has morph target weights that do not match its mesh {m}"
+                                ),
+                            );
+                        }
+                    }
                 }
             }
             if let Some(c) = n.camera() {
-                if c.as_usize() > self.cameras.len() {
-                    return Err(Error::BadJson(format!(
-                        "Node {i} has camera index {c} out of range",
-                    )));
+                if c.as_usize() >= self.cameras.len() {
+                    report.add(
+                        format!("/nodes/{i}/camera"),
+                        format!("Node {i} has camera index {c} out of range"),
+                    );
                 }
             }
             if let Some(s) = n.skin() {
-                if s.as_usize() > self.skins.len() {
-                    return Err(Error::BadJson(format!(
-                        "Node {i} has skin index {s} out of range",
-                    )));
+                if s.as_usize() >= self.skins.len() {
+                    report.add(
+                        format!("/nodes/{i}/skin"),
+                        format!("Node {i} has skin index {s} out of range"),
+                    );
+                }
+            }
+            if let Some(instancing) = n.gpu_instancing() {
+                let pointer = format!("/nodes/{i}/extensions/EXT_mesh_gpu_instancing/attributes");
+                let mut count = None;
+                for (a, elements) in instancing.iter() {
+                    let Some(acc) = self.accessors.get(a.as_usize()) else {
+                        report.add(
+                            &pointer,
+                            format!("Node {i} has instancing accessor {a} out of range"),
+                        );
+                        continue;
+                    };
+                    if acc.elements_per_data() != elements {
+                        report.add(
+                            &pointer,
+                            format!("Node {i} has instancing accessor {a} with the wrong type"),
+                        );
+                    }
+                    if count.is_some_and(|c| c != acc.count()) {
+                        report.add(
+                            &pointer,
+                            format!("Node {i} has instancing accessors with differing counts"),
+                        );
+                    }
+                    count = Some(acc.count());
+                }
+            }
+            report.add_result(format!("/nodes/{i}"), n.validate(i.into()));
+        }
+    }
+
+    //mi validate_node_graph
+    /// Validate the graph of nodes - each node must have at most one
+    /// parent, and there must be no cycles
+    ///
+    /// Returns the parent of each node if the nodes form distinct trees
+    fn validate_node_graph(&self, report: &mut ValidationReport) -> Option<Vec<Option<usize>>> {
+        let l = self.nodes.len();
+        let mut parents = vec![None; l];
+        let mut is_forest = true;
+        for (i, n) in self.nodes.iter().enumerate() {
+            for (k, c) in n.iter_children().enumerate() {
+                let c = c.as_usize();
+                if c >= l {
+                    is_forest = false;
+                } else if let Some(p) = parents[c] {
+                    report.add(
+                        format!("/nodes/{i}/children/{k}"),
+                        format!("Node {c} is a child of more than one node ({p} and {i})"),
+                    );
+                    is_forest = false;
+                } else {
+                    parents[c] = Some(i);
+                }
+            }
+        }
+
+        // Walk up the parents from each node, marking nodes on the
+        // current walk as 1 and those known to reach a root as 2;
+        // reaching a node marked as 1 means it is in a cycle
+        let mut state = vec![0_u8; l];
+        for i in 0..l {
+            let mut walk = vec![];
+            let mut n = Some(i);
+            while let Some(x) = n {
+                match state[x] {
+                    0 => {
+                        state[x] = 1;
+                        walk.push(x);
+                        n = parents[x];
+                    }
+                    1 => {
+                        report.add(
+                            format!("/nodes/{x}/children"),
+                            format!("Node {x} is in a cycle of nodes"),
+                        );
+                        is_forest = false;
+                        break;
+                    }
+                    _ => break,
                 }
             }
-            n.validate(i.into())?;
+            for x in walk {
+                state[x] = 2;
+            }
         }
-        Ok(())
+        is_forest.then_some(parents)
+    }
+
+    //mp validate_skins
+    /// Validate the skins - check joint and accessor indices in range
+    pub fn validate_skins(&self, report: &mut ValidationReport) {
+        let l = self.nodes.len();
+        for (i, s) in self.skins.iter().enumerate() {
+            if s.joints().is_empty() {
+                report.add(
+                    format!("/skins/{i}/joints"),
+                    format!("Skin {i} has no joints"),
+                );
+            }
+            for (k, j) in s.joints().iter().enumerate() {
+                if j.as_usize() >= l {
+                    report.add(
+                        format!("/skins/{i}/joints/{k}"),
+                        format!("Skin {i} has joint node index {j} out of range"),
+                    );
+                }
+            }
+            if let Some(r) = s.skeleton() {
+                if r.as_usize() >= l {
+                    report.add(
+                        format!("/skins/{i}/skeleton"),
+                        format!("Skin {i} has skeleton node index {r} out of range"),
+                    );
+                }
+            }
+            if let Some(a) = s.inverse_bind_matrices() {
+                let pointer = format!("/skins/{i}/inverseBindMatrices");
+                match self.accessors.get(a.as_usize()) {
+                    None => report.add(
+                        pointer,
+                        format!("Skin {i} has inverse bind matrices accessor {a} out of range"),
+                    ),
+                    Some(acc) if acc.elements_per_data() != 16 || acc.count() < s.joints().len() => {
+                        report.add(
+                            pointer,
+                            format!(
+                                "Skin {i} has inverse bind matrices accessor {a} that is not a MAT4 per joint"
+                            ),
+                        )
+                    }
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    //mi validate_skin_roots
+    /// Validate that the joints of each skin share a common root, and
+    /// that the skeleton (if given) is an ancestor of all the joints
+    fn validate_skin_roots(&self, report: &mut ValidationReport, parents: &[Option<usize>]) {
+        let is_ancestor = |a: usize, n: usize| {
+            let mut n = Some(n);
+            while let Some(x) = n {
+                if x == a {
+                    return true;
+                }
+                n = parents[x];
+            }
+            false
+        };
+        let root_of = |mut n: usize| {
+            while let Some(p) = parents[n] {
+                n = p;
+            }
+            n
+        };
+        for (i, s) in self.skins.iter().enumerate() {
+            let joints: Vec<usize> = s
+                .joints()
+                .iter()
+                .map(|j| j.as_usize())
+                .filter(|j| *j < parents.len())
+                .collect();
+            let Some(root) = joints.first().map(|j| root_of(*j)) else {
+                continue;
+            };
+            if joints.iter().any(|j| root_of(*j) != root) {
+                report.add(
+                    format!("/skins/{i}/joints"),
+                    format!("Skin {i} has joints that do not share a common root"),
+                );
+            }
+            if let Some(r) = s.skeleton() {
+                let r = r.as_usize();
+                if r < parents.len() && joints.iter().any(|j| !is_ancestor(r, *j)) {
+                    report.add(
+                        format!("/skins/{i}/skeleton"),
+                        format!("Skin {i} has skeleton node {r} that is not a common root of its joints"),
+                    );
+                }
+            }
+        }
+    }
+
+    //mp validate_scenes
+    /// Validate the scenes - check the default scene is in range, and
+    /// that the scene nodes are in range and are root nodes
+    pub fn validate_scenes(&self, report: &mut ValidationReport) {
+        if let Some(s) = self.scene {
+            if s.as_usize() >= self.scenes.len() {
+                report.add("/scene", format!("Default scene index {s} out of range"));
+            }
+        }
+        let l = self.nodes.len();
+        let mut is_child = vec![false; l];
+        for c in self.nodes.iter().flat_map(|n| n.iter_children()) {
+            if let Some(x) = is_child.get_mut(c.as_usize()) {
+                *x = true;
+            }
+        }
+        for (i, s) in self.scenes.iter().enumerate() {
+            for (k, n) in s.nodes.iter().enumerate() {
+                if n.as_usize() >= l {
+                    report.add(
+                        format!("/scenes/{i}/nodes/{k}"),
+                        format!("Scene {i} has node index {n} out of range"),
+                    );
+                } else if is_child[n.as_usize()] {
+                    report.add(
+                        format!("/scenes/{i}/nodes/{k}"),
+                        format!("Scene {i} has node {n} which is not a root node"),
+                    );
+                }
+            }
+        }
+    }
+
+    //mp validate_cameras
+    /// Validate the cameras - check each has a valid projection
+    pub fn validate_cameras(&self, report: &mut ValidationReport) {
+        for (i, c) in self.cameras.iter().enumerate() {
+            report.add_result(format!("/cameras/{i}"), c.validate(i.into()));
+        }
+    }
+
+    //mp validate_materials
+    /// Validate the materials - check texture indices in range
+    pub fn validate_materials(&self, report: &mut ValidationReport) {
+        for (i, m) in self.materials.iter().enumerate() {
+            for (aspect, ti) in m.iter_textures() {
+                if ti.index().as_usize() >= self.textures.len() {
+                    report.add(
+                        format!("/materials/{i}/{}/index", m.texture_path(aspect)),
+                        format!(
+                            "Material {i} has {aspect:?} texture index {} out of range",
+                            ti.index()
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    //mp validate_textures
+    /// Validate the textures and images - check image, sampler and
+    /// buffer view indices in range
+    pub fn validate_textures(&self, report: &mut ValidationReport) {
+        for (i, t) in self.textures.iter().enumerate() {
+            if t.image().as_usize() >= self.images.len() {
+                report.add(
+                    format!("/textures/{i}/source"),
+                    format!("Texture {i} has image index {} out of range", t.image()),
+                );
+            }
+            if let Some(s) = t.sampler() {
+                if s.as_usize() >= self.samplers.len() {
+                    report.add(
+                        format!("/textures/{i}/sampler"),
+                        format!("Texture {i} has sampler index {s} out of range"),
+                    );
+                }
+            }
+        }
+        for (i, image) in self.images.iter().enumerate() {
+            if image.uri().is_none() && image.buffer_view().as_usize() >= self.buffer_views.len() {
+                report.add(
+                    format!("/images/{i}/bufferView"),
+                    format!(
+                        "Image {i} has buffer view index {} out of range",
+                        image.buffer_view()
+                    ),
+                );
+            }
+        }
+    }
+
+    //mp validate_lights
+    /// Validate the lights - check each is valid, and that node light
+    /// indices are in range
+    pub fn validate_lights(&self, report: &mut ValidationReport) {
+        let lights = self.lights();
+        for (i, l) in lights.iter().enumerate() {
+            report.add_result(
+                format!("/extensions/KHR_lights_punctual/lights/{i}"),
+                l.validate(i.into()),
+            );
+        }
+        for (i, n) in self.nodes.iter().enumerate() {
+            if let Some(l) = n.light() {
+                if l.as_usize() >= lights.len() {
+                    report.add(
+                        format!("/nodes/{i}/extensions/KHR_lights_punctual/light"),
+                        format!("Node {i} has light index {l} out of range"),
+                    );
+                }
+            }
+        }
+    }
+
+    //mp validate_animations
+    /// Validate the animations - check indices in range, and that the
+    /// samplers have matching input and output counts
+    pub fn validate_animations(&self, report: &mut ValidationReport) {
+        let l = self.nodes.len();
+        for (i, a) in self.animations.iter().enumerate() {
+            for (k, c) in a.channels().iter().enumerate() {
+                let pointer = format!("/animations/{i}/channels/{k}");
+                if let Some(node) = c.node() {
+                    if node.as_usize() >= l {
+                        report.add(
+                            format!("{pointer}/target/node"),
+                            format!("Animation {i} has channel target node {node} out of range"),
+                        );
+                    }
+                }
+                let Some(sampler) = a.samplers().get(c.sampler()) else {
+                    report.add(
+                        format!("{pointer}/sampler"),
+                        format!(
+                            "Animation {i} has channel sampler index {} out of range",
+                            c.sampler()
+                        ),
+                    );
+                    continue;
+                };
+                use mod3d_base::AnimationProperty::*;
+                let epd = match c.path() {
+                    Translation | Scale => 3,
+                    Rotation => 4,
+                    Weights => 1,
+                };
+                let output = sampler.output();
+                if self
+                    .accessors
+                    .get(output.as_usize())
+                    .is_some_and(|acc| acc.elements_per_data() != epd)
+                {
+                    report.add(
+                        pointer,
+                        format!(
+                            "Animation {i} has channel with output accessor {output} of the wrong type for {:?}",
+                            c.path()
+                        ),
+                    );
+                }
+            }
+            for (k, s) in a.samplers().iter().enumerate() {
+                let pointer = format!("/animations/{i}/samplers/{k}");
+                let input = self.accessors.get(s.input().as_usize());
+                let output = self.accessors.get(s.output().as_usize());
+                if input.is_none() {
+                    report.add(
+                        format!("{pointer}/input"),
+                        format!("Animation {i} has sampler input accessor out of range"),
+                    );
+                }
+                if output.is_none() {
+                    report.add(
+                        format!("{pointer}/output"),
+                        format!("Animation {i} has sampler output accessor out of range"),
+                    );
+                }
+                let (Some(input), Some(output)) = (input, output) else {
+                    continue;
+                };
+                let values_per_key = {
+                    if s.interpolation() == mod3d_base::Interpolation::CubicSpline {
+                        3
+                    } else {
+                        1
+                    }
+                };
+                // Morph target weights are scalars, with one per target per
+                // keyframe; other outputs have one value per keyframe
+                let num_values = input.count() * values_per_key;
+                if input.elements_per_data() != 1
+                    || input.count() == 0
+                    || !output.count().is_multiple_of(num_values)
+                    || (output.elements_per_data() != 1 && output.count() != num_values)
+                {
+                    report.add(
+                        pointer,
+                        format!(
+                            "Animation {i} has sampler with mismatched input and output accessors"
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    //mp validation_report
+    /// Validate the contents, checking every cross-reference, and
+    /// return a report of all the failures found
+    ///
+    /// Index values are not checked, as the buffer data is required;
+    /// use [Gltf::validate_index_values] for that
+    pub fn validation_report(&self) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        self.validate_buffer_views(&mut report);
+        self.validate_accessors(&mut report);
+        self.validate_meshes(&mut report);
+        self.validate_nodes(&mut report);
+        self.validate_skins(&mut report);
+        if let Some(parents) = self.validate_node_graph(&mut report) {
+            self.validate_skin_roots(&mut report, &parents);
+        }
+        self.validate_scenes(&mut report);
+        self.validate_materials(&mut report);
+        self.validate_textures(&mut report);
+        self.validate_cameras(&mut report);
+        self.validate_lights(&mut report);
+        self.validate_animations(&mut report);
+        report
     }
 
     //mp validate
     /// Validate the contents - check indices in range, etc
+    ///
+    /// Returns an [crate::Error::Validation] with the report of all the
+    /// failures if any are found
     pub fn validate(&self) -> Result<()> {
-        dbg!(self);
-        self.validate_buffer_views()?;
-        self.validate_accessors()?;
-        self.validate_nodes()?;
-        Ok(())
+        self.validation_report().into_result()
+    }
+
+    //ap extensions_used
+    /// Get the names of the extensions used by the Gltf
+    pub fn extensions_used(&self) -> &[String] {
+        &self.extensions_used
     }
 
     //ap buffers
@@ -358,6 +1178,15 @@ impl Gltf {
         self.buffers[buffer.as_usize()].take_buffer()
     }
 
+    //ap has_compressed_views
+    /// Return true if any buffer views of a buffer are compressed
+    /// (EXT_meshopt_compression), and so must be decoded into it
+    pub fn has_compressed_views(&self, buffer: BufferIndex) -> bool {
+        self.buffer_views
+            .iter()
+            .any(|bv| bv.buffer() == buffer && bv.meshopt_compression().is_some())
+    }
+
     //ap buffer_views
     pub fn buffer_views(&self) -> &[GltfBufferView] {
         &self.buffer_views
@@ -373,6 +1202,149 @@ impl Gltf {
         &self.meshes
     }
 
+    //ap skins
+    /// Get a reference to the skins
+    pub fn skins(&self) -> &[GltfSkin] {
+        &self.skins
+    }
+
+    //ap materials
+    /// Get a reference to the materials
+    pub fn materials(&self) -> &[GltfMaterial] {
+        &self.materials
+    }
+
+    //ap images
+    /// Get a reference to the image descriptors
+    pub fn images(&self) -> &[GltfImage] {
+        &self.images
+    }
+
+    //ap textures
+    /// Get a reference to the textures
+    pub fn textures(&self) -> &[GltfTexture] {
+        &self.textures
+    }
+
+    //ap samplers
+    /// Get a reference to the samplers
+    pub fn samplers(&self) -> &[GltfSampler] {
+        &self.samplers
+    }
+
+    //ap cameras
+    /// Get a reference to the cameras
+    pub fn cameras(&self) -> &[GltfCamera] {
+        &self.cameras
+    }
+
+    //ap scene
+    /// Get the default scene of the Gltf, if specified
+    pub fn scene(&self) -> Option<SceneIndex> {
+        self.scene
+    }
+
+    //ap scenes
+    /// Get a reference to the scenes
+    pub fn scenes(&self) -> &[GltfScene] {
+        &self.scenes
+    }
+
+    //ap default_scene
+    /// Get the scene to show for the Gltf - the default scene if
+    /// specified, else the first scene (if there are any)
+    pub fn default_scene(&self) -> Option<SceneIndex> {
+        self.scene
+            .or_else(|| (!self.scenes.is_empty()).then_some(0.into()))
+    }
+
+    //mp scene_camera_nodes
+    /// Get the nodes of a scene that are cameras, in hierarchy order
+    ///
+    /// The global transformation of each such node is the
+    /// camera-to-world transformation of the camera
+    pub fn scene_camera_nodes(&self, scene: SceneIndex) -> Vec<NodeIndex> {
+        let mut cameras = vec![];
+        for r in &self.scenes[scene.as_usize()].nodes {
+            let nh = self.nh_index(*r).as_usize();
+            for x in self.node_hierarchy.enum_from(nh) {
+                let (is_push, n, _) = x.unpack();
+                if is_push && self.nodes[*n].camera().is_some() {
+                    cameras.push((*n).into());
+                }
+            }
+        }
+        cameras
+    }
+
+    //mp camera_of_node
+    /// Generate a [mod3d_base::Camera] for a node that is a camera,
+    /// placed in the world by the global translation and rotation of
+    /// the node
+    ///
+    /// As required by glTF the scale of the node (and its ancestors)
+    /// is ignored
+    ///
+    /// Returns None if the node is not a camera
+    pub fn camera_of_node(&self, node: NodeIndex) -> Option<mod3d_base::Camera> {
+        let n = &self[node];
+        let gltf_camera = &self[n.camera()?];
+        let global = n.global_transformation();
+        let transformation = Transformation::new()
+            .with_translation(global.translation())
+            .with_rotation(global.rotation());
+        Some(
+            mod3d_base::Camera::new(&gltf_camera.name, gltf_camera.projection())
+                .with_transformation(transformation),
+        )
+    }
+
+    //ap lights
+    /// Get a reference to the lights (from the KHR_lights_punctual
+    /// extension)
+    pub fn lights(&self) -> &[GltfLight] {
+        self.extensions
+            .lights_punctual
+            .as_ref()
+            .map_or(&[], |l| &l.lights)
+    }
+
+    //mp scene_lights
+    /// Get the lights of a scene, placed in world space by the global
+    /// transformations of their nodes, in hierarchy order
+    pub fn scene_lights(&self, scene: SceneIndex) -> Vec<mod3d_base::Light> {
+        let mut lights = vec![];
+        for r in &self.scenes[scene.as_usize()].nodes {
+            let nh = self.nh_index(*r).as_usize();
+            for x in self.node_hierarchy.enum_from(nh) {
+                let (is_push, n, _) = x.unpack();
+                if is_push {
+                    if let Some(light) = self.light_of_node((*n).into()) {
+                        lights.push(light);
+                    }
+                }
+            }
+        }
+        lights
+    }
+
+    //mp light_of_node
+    /// Generate a [mod3d_base::Light] for a node that has a light,
+    /// placed in the world by the global transformation of the node
+    ///
+    /// Returns None if the node has no light
+    pub fn light_of_node(&self, node: NodeIndex) -> Option<mod3d_base::Light> {
+        let n = &self[node];
+        let gltf_light = &self[n.light()?];
+        Some(gltf_light.to_light().placed(n.global_transformation()))
+    }
+
+    //ap animations
+    /// Get a reference to the animations
+    pub fn animations(&self) -> &[GltfAnimation] {
+        &self.animations
+    }
+
     //ap nh_index
     pub fn nh_index(&self, node: NodeIndex) -> NHIndex {
         self.nh_index[node.as_usize()]
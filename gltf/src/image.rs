@@ -14,6 +14,7 @@ pub struct GltfImage {
     /// Optional name of the image
     pub name: String,
     /// Optional URI
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub uri: Option<String>,
     /// Optional mime type ("image/jpeg" or "image/png")
     #[cfg_attr(feature = "serde", serde(rename = "mimeType"))]
@@ -87,16 +87,22 @@ pub use traits::Named;
 #[cfg(feature = "serde_json")]
 mod glb;
 #[cfg(feature = "serde_json")]
-pub use glb::glb_load;
+pub use glb::{glb_load, glb_save};
 
+mod animation;
 mod asset;
 mod buffer_usage;
 mod buffers_accessors;
+mod camera;
+mod extensions;
 mod image;
+mod light;
 mod material;
+mod meshopt;
 mod node;
 mod primitives_meshes;
 mod scene;
+mod skin;
 mod texture;
 
 #[cfg(feature = "serde")]
@@ -104,22 +110,46 @@ mod deserialize;
 #[cfg(feature = "serde")]
 mod serialize;
 
+pub use animation::{
+    GltfAnimation, GltfAnimationChannel, GltfAnimationSampler, GltfAnimationTarget,
+};
 pub use asset::GltfAsset;
 pub(crate) use buffer_usage::BufferUsage;
 pub use buffers_accessors::{GltfAccessor, GltfBuffer, GltfBufferView};
+pub use buffers_accessors::{GltfAccessorSparse, GltfSparseIndices, GltfSparseValues};
+pub use camera::{GltfCamera, GltfOrthographic, GltfPerspective};
+pub use extensions::{
+    GltfBufferViewExtensions, GltfExtensions, GltfMaterialExtensions, GltfNodeExtensions,
+    GltfTextureInfoExtensions,
+};
 pub use image::GltfImage;
-pub use material::GltfMaterial;
-pub use node::GltfNode;
+pub use light::{GltfLight, GltfLightsPunctual, GltfNodeLight, GltfSpot};
+pub use material::{GltfClearcoat, GltfEmissiveStrength, GltfIor, GltfTransmission, GltfUnlit};
+pub use material::{GltfMaterial, GltfPbrMetallicRoughness, GltfSpecularGlossiness};
+pub use meshopt::GltfMeshoptCompression;
+pub use node::{GltfInstancingAttributes, GltfMeshGpuInstancing, GltfNode};
+pub(crate) use primitives_meshes::GltfAttributes;
 pub use primitives_meshes::{GltfMesh, GltfPrimitive};
 pub use scene::GltfScene;
-pub use texture::{GltfTexture, GltfTextureInfo};
+pub use skin::GltfSkin;
+pub use texture::{GltfSampler, GltfTexture, GltfTextureInfo, GltfTextureTransform};
 
 mod utils;
 pub use utils::{buf_parse_fail, try_buf_parse_base64};
+pub use utils::{parse_data_uri, percent_decode, uri_mime_type};
+
+mod uri;
+pub use uri::{DataUriResolver, FileUriResolver, UriResolver};
+
+mod validation;
+pub use validation::{ValidationIssue, ValidationReport};
 
 mod gltf;
 pub use gltf::Gltf;
 
+mod exporter;
+pub use exporter::{GltfBufferUri, GltfExporter};
+
 mod object_data;
 pub use object_data::ObjectData;
 mod od_use;
@@ -0,0 +1,165 @@
+//a Imports
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, Result};
+use crate::{LightIndex, Named};
+
+//a GltfSpot
+//tp GltfSpot
+/// The cone of a Gltf spot light
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GltfSpot {
+    /// Angle in radians from the light direction at which the light
+    /// starts to fall off
+    #[cfg_attr(feature = "serde", serde(rename = "innerConeAngle"))]
+    pub inner_cone_angle: f32,
+    /// Angle in radians from the light direction at which the light
+    /// is completely off
+    #[cfg_attr(feature = "serde", serde(rename = "outerConeAngle"))]
+    pub outer_cone_angle: f32,
+}
+
+//ip Default for GltfSpot
+impl std::default::Default for GltfSpot {
+    fn default() -> Self {
+        Self {
+            inner_cone_angle: 0.,
+            outer_cone_angle: std::f32::consts::FRAC_PI_4,
+        }
+    }
+}
+
+//a GltfLight
+//tp GltfLight
+/// A type that contains the data from a Gltf Json KHR_lights_punctual
+/// 'light'
+///
+/// The light type is one of "directional", "point" or "spot"; a spot
+/// light may have a 'spot' cone, which otherwise takes default values
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GltfLight {
+    /// Optional name of the light
+    pub name: String,
+    /// The type of the light
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub light_type: String,
+    /// Linear RGB color of the light; defaults to white
+    pub color: [f32; 3],
+    /// Intensity of the light; lux for directional lights, and candela
+    /// for point and spot lights
+    pub intensity: f32,
+    /// Optional distance beyond which the light has no effect
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub range: Option<f32>,
+    /// The cone of a spot light
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub spot: Option<GltfSpot>,
+}
+
+//ip Default for GltfLight
+impl std::default::Default for GltfLight {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            light_type: String::new(),
+            color: [1.; 3],
+            intensity: 1.,
+            range: None,
+            spot: None,
+        }
+    }
+}
+
+//ip GltfLight
+impl GltfLight {
+    //mp validate
+    /// Validate the light - it must have a known type, and a spot cone
+    /// must have 0 <= inner < outer <= PI/2
+    pub fn validate(&self, l: LightIndex) -> Result<()> {
+        match self.light_type.as_str() {
+            "directional" | "point" => (),
+            "spot" => {
+                let spot = self.spot.unwrap_or_default();
+                if spot.inner_cone_angle < 0.
+                    || spot.inner_cone_angle >= spot.outer_cone_angle
+                    || spot.outer_cone_angle > std::f32::consts::FRAC_PI_2
+                {
+                    return Err(Error::BadJson(format!("Light {l} has a bad spot cone",)));
+                }
+            }
+            t => {
+                return Err(Error::BadJson(format!(
+                    "Light {l} has unknown light type '{t}'",
+                )));
+            }
+        }
+        if self.range.is_some_and(|r| r <= 0.) {
+            return Err(Error::BadJson(format!(
+                "Light {l} has a range that is not positive",
+            )));
+        }
+        Ok(())
+    }
+
+    //ap kind
+    /// Get the [mod3d_base::LightKind] of the light
+    ///
+    /// This assumes the light has been validated
+    pub fn kind(&self) -> mod3d_base::LightKind {
+        match self.light_type.as_str() {
+            "point" => mod3d_base::LightKind::Point,
+            "spot" => {
+                let spot = self.spot.unwrap_or_default();
+                mod3d_base::LightKind::Spot {
+                    inner_cone_angle: spot.inner_cone_angle,
+                    outer_cone_angle: spot.outer_cone_angle,
+                }
+            }
+            _ => mod3d_base::LightKind::Directional,
+        }
+    }
+
+    //mp to_light
+    /// Create a [mod3d_base::Light] from the light, at the origin
+    /// shining down -Z
+    pub fn to_light(&self) -> mod3d_base::Light {
+        mod3d_base::Light::new(&self.name, self.kind(), self.color, self.intensity)
+            .with_range(self.range)
+    }
+}
+
+//ip Named for GltfLight
+impl Named for GltfLight {
+    type Index = LightIndex;
+    fn is_name(&self, name: &str) -> bool {
+        self.name == name
+    }
+}
+
+//a GltfLightsPunctual, GltfNodeLight
+//tp GltfLightsPunctual
+/// The KHR_lights_punctual extension of the Gltf root object, which
+/// contains the lights of the file
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GltfLightsPunctual {
+    /// The lights of the Gltf file
+    pub lights: Vec<GltfLight>,
+}
+
+//tp GltfNodeLight
+/// The KHR_lights_punctual extension of a Gltf node, which places a
+/// light at the node
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GltfNodeLight {
+    /// The light placed at the node
+    pub light: LightIndex,
+}
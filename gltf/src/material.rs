@@ -5,6 +5,9 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "serde")]
 use crate::deserialize;
 
+use mod3d_base::{AlphaMode, MaterialAspect};
+
+use crate::GltfMaterialExtensions;
 use crate::Named;
 use crate::{GltfTextureInfo, MaterialIndex};
 
@@ -16,14 +19,17 @@ use crate::{GltfTextureInfo, MaterialIndex};
 pub struct GltfPbrMetallicRoughness {
     /// Base color factor
     #[cfg_attr(feature = "serde", serde(rename = "baseColorFactor"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub base_color_factor: Option<Vec<f32>>,
 
     /// Base color texture
     #[cfg_attr(feature = "serde", serde(rename = "baseColorTexture"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub base_color_texture: Option<GltfTextureInfo>,
 
     /// Metallic/roughness texture
     #[cfg_attr(feature = "serde", serde(rename = "metallicRoughnessTexture"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub metallic_roughness_texture: Option<GltfTextureInfo>,
     #[cfg_attr(
         feature = "serde",
@@ -50,6 +56,207 @@ impl GltfPbrMetallicRoughness {
     }
 }
 
+//a Material extensions
+//tp GltfEmissiveStrength
+/// The KHR_materials_emissive_strength extension of a material, which
+/// scales the emissive color beyond 1.0
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GltfEmissiveStrength {
+    /// Multiplier for the emissive color
+    #[cfg_attr(feature = "serde", serde(rename = "emissiveStrength"))]
+    pub emissive_strength: f32,
+}
+
+//ip Default for GltfEmissiveStrength
+impl Default for GltfEmissiveStrength {
+    fn default() -> Self {
+        Self {
+            emissive_strength: 1.0,
+        }
+    }
+}
+
+//tp GltfUnlit
+/// The KHR_materials_unlit extension of a material, which indicates
+/// that the material should be rendered without lighting
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GltfUnlit {}
+
+//tp GltfClearcoat
+/// The KHR_materials_clearcoat extension of a material, which adds a
+/// clear reflective layer over the material
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GltfClearcoat {
+    /// Intensity of the clearcoat layer
+    #[cfg_attr(feature = "serde", serde(rename = "clearcoatFactor"))]
+    pub clearcoat_factor: f32,
+    /// Texture for the intensity (red channel)
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "clearcoatTexture", skip_serializing_if = "Option::is_none")
+    )]
+    pub clearcoat_texture: Option<GltfTextureInfo>,
+    /// Roughness of the clearcoat layer
+    #[cfg_attr(feature = "serde", serde(rename = "clearcoatRoughnessFactor"))]
+    pub clearcoat_roughness_factor: f32,
+    /// Texture for the roughness (green channel)
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            rename = "clearcoatRoughnessTexture",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub clearcoat_roughness_texture: Option<GltfTextureInfo>,
+    /// Normal texture for the clearcoat layer
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            rename = "clearcoatNormalTexture",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub clearcoat_normal_texture: Option<GltfTextureInfo>,
+}
+
+//tp GltfTransmission
+/// The KHR_materials_transmission extension of a material, which
+/// permits light to pass through the surface
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GltfTransmission {
+    /// Fraction of light that is transmitted
+    #[cfg_attr(feature = "serde", serde(rename = "transmissionFactor"))]
+    pub transmission_factor: f32,
+    /// Texture for the fraction (red channel)
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            rename = "transmissionTexture",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub transmission_texture: Option<GltfTextureInfo>,
+}
+
+//tp GltfIor
+/// The KHR_materials_ior extension of a material, which provides its
+/// index of refraction
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GltfIor {
+    /// Index of refraction
+    pub ior: f32,
+}
+
+//ip Default for GltfIor
+impl Default for GltfIor {
+    fn default() -> Self {
+        Self { ior: 1.5 }
+    }
+}
+
+//tp GltfSpecularGlossiness
+/// The KHR_materials_pbrSpecularGlossiness extension of a material,
+/// which describes the material with the specular-glossiness model
+/// in place of the metallic-roughness model
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GltfSpecularGlossiness {
+    /// Diffuse color RGBA
+    #[cfg_attr(feature = "serde", serde(rename = "diffuseFactor"))]
+    pub diffuse_factor: [f32; 4],
+    /// Diffuse color texture
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "diffuseTexture", skip_serializing_if = "Option::is_none")
+    )]
+    pub diffuse_texture: Option<GltfTextureInfo>,
+    /// Specular color RGB
+    #[cfg_attr(feature = "serde", serde(rename = "specularFactor"))]
+    pub specular_factor: [f32; 3],
+    /// Glossiness; 1 is fully smooth
+    #[cfg_attr(feature = "serde", serde(rename = "glossinessFactor"))]
+    pub glossiness_factor: f32,
+    /// Specular RGB and glossiness (alpha) texture
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            rename = "specularGlossinessTexture",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub specular_glossiness_texture: Option<GltfTextureInfo>,
+}
+
+//ip Default for GltfSpecularGlossiness
+impl Default for GltfSpecularGlossiness {
+    fn default() -> Self {
+        Self {
+            diffuse_factor: [1.; 4],
+            diffuse_texture: None,
+            specular_factor: [1.; 3],
+            glossiness_factor: 1.,
+            specular_glossiness_texture: None,
+        }
+    }
+}
+
+//ip GltfSpecularGlossiness
+impl GltfSpecularGlossiness {
+    //mp metallic_roughness
+    /// Convert the factors to the metallic-roughness model, returning
+    /// the base color RGBA, metallic and roughness
+    ///
+    /// This is the conversion used by the Khronos glTF tools: the
+    /// metallic is that which best explains the perceived brightness
+    /// of the diffuse and specular colors, given a dielectric specular
+    /// of 0.04, and the base color is blended from that derived from
+    /// the diffuse and that derived from the specular
+    pub fn metallic_roughness(&self) -> ([f32; 4], f32, f32) {
+        const DIELECTRIC: f32 = 0.04;
+        const EPSILON: f32 = 1E-6;
+        let brightness =
+            |c: &[f32]| (0.299 * c[0] * c[0] + 0.587 * c[1] * c[1] + 0.114 * c[2] * c[2]).sqrt();
+        let diffuse = &self.diffuse_factor;
+        let specular = &self.specular_factor;
+        let one_minus_specular = 1. - specular.iter().fold(0., |a: f32, s| a.max(*s));
+        let diffuse_brightness = brightness(diffuse);
+        let specular_brightness = brightness(specular);
+        let metallic = {
+            if specular_brightness < DIELECTRIC {
+                0.
+            } else {
+                let a = DIELECTRIC;
+                let b = diffuse_brightness * one_minus_specular / (1. - DIELECTRIC)
+                    + specular_brightness
+                    - 2. * DIELECTRIC;
+                let c = DIELECTRIC - specular_brightness;
+                let d = (b * b - 4. * a * c).max(0.);
+                ((-b + d.sqrt()) / (2. * a)).clamp(0., 1.)
+            }
+        };
+        let mut base_color = [0., 0., 0., diffuse[3]];
+        for i in 0..3 {
+            let from_diffuse =
+                diffuse[i] * one_minus_specular / (1. - DIELECTRIC) / (1. - metallic).max(EPSILON);
+            let from_specular =
+                (specular[i] - DIELECTRIC * (1. - metallic)) / metallic.max(EPSILON);
+            let t = metallic * metallic;
+            base_color[i] = (from_diffuse + (from_specular - from_diffuse) * t).clamp(0., 1.);
+        }
+        (base_color, metallic, 1. - self.glossiness_factor)
+    }
+}
+
 //a GltfMaterial
 //tp GltfMaterial
 /// A type representing a Gltf Material -
@@ -61,15 +268,19 @@ pub struct GltfMaterial {
     pub name: String,
     /// Image index (source)
     #[cfg_attr(feature = "serde", serde(rename = "normalTexture"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub normal_texture: Option<GltfTextureInfo>,
     /// occlusion texture
     #[cfg_attr(feature = "serde", serde(rename = "occlusionTexture"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub occlusion_texture: Option<GltfTextureInfo>,
     /// emissive texture
     #[cfg_attr(feature = "serde", serde(rename = "emissiveTexture"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub emissive_texture: Option<GltfTextureInfo>,
     /// pbrMetallicRoughness
     #[cfg_attr(feature = "serde", serde(rename = "pbrMetallicRoughness"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub pbr_metallic_roughness: Option<GltfPbrMetallicRoughness>,
 
     /// Emissive factor
@@ -78,11 +289,20 @@ pub struct GltfMaterial {
 
     /// One of OPAQUE, MASK, BLEND
     #[cfg_attr(feature = "serde", serde(rename = "alphaMode"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub alpha_mode: Option<String>,
-    #[cfg_attr(feature = "serde", serde(rename = "alphaCutoff"))]
+    /// Alpha cutoff for the MASK alpha mode
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "alphaCutoff", default = "deserialize::f32_half")
+    )]
     pub alpha_cutoff: f32,
+    /// True if back faces are to be rendered
     #[cfg_attr(feature = "serde", serde(rename = "doubleSided"))]
     pub double_sided: bool,
+    /// The supported extensions of the material, such as
+    /// KHR_materials_clearcoat
+    pub extensions: GltfMaterialExtensions,
 }
 
 impl GltfMaterial {
@@ -98,6 +318,119 @@ impl GltfMaterial {
     pub fn emissive_texture(&self) -> &Option<GltfTextureInfo> {
         &self.emissive_texture
     }
+    /// Get the alpha mode; anything other than MASK or BLEND is OPAQUE
+    pub fn alpha_mode(&self) -> AlphaMode {
+        match self.alpha_mode.as_deref() {
+            Some("MASK") => AlphaMode::Mask,
+            Some("BLEND") => AlphaMode::Blend,
+            _ => AlphaMode::Opaque,
+        }
+    }
+    pub fn alpha_cutoff(&self) -> f32 {
+        self.alpha_cutoff
+    }
+    pub fn double_sided(&self) -> bool {
+        self.double_sided
+    }
+    /// Get the multiplier for the emissive color
+    /// (KHR_materials_emissive_strength)
+    pub fn emissive_strength(&self) -> f32 {
+        self.extensions
+            .emissive_strength
+            .as_ref()
+            .map_or(1.0, |e| e.emissive_strength)
+    }
+    /// Return true if the material is unlit (KHR_materials_unlit)
+    pub fn unlit(&self) -> bool {
+        self.extensions.unlit.is_some()
+    }
+    /// Get the clearcoat layer, if any (KHR_materials_clearcoat)
+    pub fn clearcoat(&self) -> Option<&GltfClearcoat> {
+        self.extensions.clearcoat.as_ref()
+    }
+    /// Get the transmission, if any (KHR_materials_transmission)
+    pub fn transmission(&self) -> Option<&GltfTransmission> {
+        self.extensions.transmission.as_ref()
+    }
+    /// Get the index of refraction (KHR_materials_ior)
+    pub fn ior(&self) -> f32 {
+        self.extensions.ior.as_ref().map_or(1.5, |i| i.ior)
+    }
+    /// Get the specular-glossiness model of the material, if any
+    /// (KHR_materials_pbrSpecularGlossiness); if present this
+    /// supersedes the metallic-roughness model
+    pub fn specular_glossiness(&self) -> Option<&GltfSpecularGlossiness> {
+        self.extensions.specular_glossiness.as_ref()
+    }
+
+    //mp iter_textures
+    /// Iterate through the textures of the material, including those
+    /// of supported extensions, with the aspect each is used for
+    ///
+    /// For a specular-glossiness material the diffuse texture is used
+    /// for the color, and there is no metallic-roughness texture (the
+    /// specular-glossiness texture cannot be used in its place)
+    pub fn iter_textures(&self) -> impl Iterator<Item = (MaterialAspect, &GltfTextureInfo)> {
+        let (color_texture, mr_texture) = {
+            if let Some(sg) = self.specular_glossiness() {
+                (sg.diffuse_texture.as_ref(), None)
+            } else {
+                let pbr = self.pbr_metallic_roughness.as_ref();
+                (
+                    pbr.and_then(|p| p.base_color_texture.as_ref()),
+                    pbr.and_then(|p| p.metallic_roughness_texture.as_ref()),
+                )
+            }
+        };
+        let clearcoat = self.clearcoat();
+        let transmission = self.transmission();
+        [
+            (MaterialAspect::Color, color_texture),
+            (MaterialAspect::Normal, self.normal_texture.as_ref()),
+            (MaterialAspect::MetallicRoughness, mr_texture),
+            (MaterialAspect::Occlusion, self.occlusion_texture.as_ref()),
+            (MaterialAspect::Emission, self.emissive_texture.as_ref()),
+            (
+                MaterialAspect::Clearcoat,
+                clearcoat.and_then(|c| c.clearcoat_texture.as_ref()),
+            ),
+            (
+                MaterialAspect::ClearcoatRoughness,
+                clearcoat.and_then(|c| c.clearcoat_roughness_texture.as_ref()),
+            ),
+            (
+                MaterialAspect::ClearcoatNormal,
+                clearcoat.and_then(|c| c.clearcoat_normal_texture.as_ref()),
+            ),
+            (
+                MaterialAspect::Transmission,
+                transmission.and_then(|t| t.transmission_texture.as_ref()),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(aspect, ti)| ti.map(|ti| (aspect, ti)))
+    }
+
+    //ap texture_path
+    /// Get the path within the material Json of the texture used for
+    /// an aspect by [GltfMaterial::iter_textures]
+    pub fn texture_path(&self, aspect: MaterialAspect) -> &'static str {
+        use MaterialAspect::*;
+        match aspect {
+            Color if self.specular_glossiness().is_some() => {
+                "extensions/KHR_materials_pbrSpecularGlossiness/diffuseTexture"
+            }
+            Color => "pbrMetallicRoughness/baseColorTexture",
+            Normal => "normalTexture",
+            MetallicRoughness => "pbrMetallicRoughness/metallicRoughnessTexture",
+            Occlusion => "occlusionTexture",
+            Emission => "emissiveTexture",
+            Clearcoat => "extensions/KHR_materials_clearcoat/clearcoatTexture",
+            ClearcoatRoughness => "extensions/KHR_materials_clearcoat/clearcoatRoughnessTexture",
+            ClearcoatNormal => "extensions/KHR_materials_clearcoat/clearcoatNormalTexture",
+            Transmission => "extensions/KHR_materials_transmission/transmissionTexture",
+        }
+    }
 }
 
 //ip Named for GltfMaterial
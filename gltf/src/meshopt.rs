@@ -0,0 +1,580 @@
+//a Documentation
+//! Decoding of buffer views compressed with EXT_meshopt_compression
+//!
+//! A compressed buffer view refers to a range of a source buffer that
+//! contains the data compressed with one of the meshoptimizer codecs
+//! (the 'ATTRIBUTES' vertex codec, the 'TRIANGLES' index codec, or
+//! the 'INDICES' index sequence codec), optionally followed by a
+//! filter (octahedral, quaternion or exponential); the decoded data
+//! is 'count' elements of 'byteStride' bytes, which is the contents
+//! of the buffer view itself
+//!
+//! The buffer of the view is usually a 'fallback' buffer with no
+//! URI, whose contents are just the decoded data
+
+//a Imports
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{BufferIndex, GltfBuffer, Indexable, ViewIndex};
+use crate::{Error, Result};
+
+//a Constants
+/// The header of the vertex codec (version 0)
+const VERTEX_HEADER: u8 = 0xa0;
+
+/// The header of the index (triangle) codec (version 0 or 1)
+const INDEX_HEADER: u8 = 0xe0;
+
+/// The header of the index sequence codec (version 1)
+const SEQUENCE_HEADER: u8 = 0xd0;
+
+/// Maximum number of bytes of vertex data in a vertex block
+const VERTEX_BLOCK_SIZE_BYTES: usize = 8192;
+
+/// Maximum number of vertices in a vertex block
+const VERTEX_BLOCK_MAX_SIZE: usize = 256;
+
+/// Number of bytes in a byte group of the vertex codec
+const BYTE_GROUP_SIZE: usize = 16;
+
+/// Minimum size of the tail of vertex codec data
+const TAIL_MAX_SIZE: usize = 32;
+
+//a GltfMeshoptCompression
+//tp GltfMeshoptCompression
+/// The EXT_meshopt_compression extension of a Gltf buffer view
+///
+/// The mode is one of "ATTRIBUTES", "TRIANGLES" or "INDICES"; the
+/// filter (which only applies to "ATTRIBUTES") is one of "NONE",
+/// "OCTAHEDRAL", "QUATERNION" or "EXPONENTIAL"
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GltfMeshoptCompression {
+    /// The buffer containing the compressed data
+    pub buffer: BufferIndex,
+    /// Byte offset of the compressed data in the buffer
+    #[cfg_attr(feature = "serde", serde(rename = "byteOffset"))]
+    pub byte_offset: usize,
+    /// Byte length of the compressed data
+    #[cfg_attr(feature = "serde", serde(rename = "byteLength"))]
+    pub byte_length: usize,
+    /// Byte size of each decoded element
+    #[cfg_attr(feature = "serde", serde(rename = "byteStride"))]
+    pub byte_stride: usize,
+    /// Number of decoded elements
+    pub count: usize,
+    /// The codec that compressed the data
+    pub mode: String,
+    /// The filter to apply after decoding, if any
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub filter: Option<String>,
+}
+
+//ip GltfMeshoptCompression
+impl GltfMeshoptCompression {
+    //ap byte_end
+    /// Get the byte offset of the end of the compressed data in its
+    /// buffer
+    pub fn byte_end(&self) -> usize {
+        self.byte_offset + self.byte_length
+    }
+
+    //ap decoded_length
+    /// Get the byte length of the decoded data
+    pub fn decoded_length(&self) -> usize {
+        self.byte_stride * self.count
+    }
+
+    //ap filter
+    /// Get the filter to apply after decoding
+    pub fn filter(&self) -> &str {
+        self.filter.as_deref().unwrap_or("NONE")
+    }
+
+    //mp validate
+    /// Validate the compression of a buffer view of a given byte
+    /// length, given the buffers of the Gltf
+    pub fn validate(&self, v: ViewIndex, byte_length: usize, buffers: &[GltfBuffer]) -> Result<()> {
+        let Some(b) = buffers.get(self.buffer.as_usize()) else {
+            return Err(Error::BadJson(format!(
+                "Buffer view index {v} has meshopt buffer {} out of range",
+                self.buffer
+            )));
+        };
+        if self.byte_end() > b.byte_length() {
+            return Err(Error::BadJson(format!(
+                "Buffer view index {v} has meshopt data outside its buffer",
+            )));
+        }
+        if self.decoded_length() > byte_length {
+            return Err(Error::BadJson(format!(
+                "Buffer view index {v} has meshopt data that decodes to more than the view",
+            )));
+        }
+        let stride = self.byte_stride;
+        let valid = match (self.mode.as_str(), self.filter()) {
+            ("ATTRIBUTES", "NONE") => stride.is_multiple_of(4) && stride > 0 && stride <= 256,
+            ("ATTRIBUTES", "OCTAHEDRAL") => stride == 4 || stride == 8,
+            ("ATTRIBUTES", "QUATERNION") => stride == 8,
+            ("ATTRIBUTES", "EXPONENTIAL") => {
+                stride.is_multiple_of(4) && stride > 0 && stride <= 256
+            }
+            ("TRIANGLES", "NONE") => (stride == 2 || stride == 4) && self.count.is_multiple_of(3),
+            ("INDICES", "NONE") => stride == 2 || stride == 4,
+            _ => false,
+        };
+        if !valid {
+            return Err(Error::BadJson(format!(
+                "Buffer view index {v} has bad meshopt mode '{}', filter '{}' or stride {stride}",
+                self.mode,
+                self.filter(),
+            )));
+        }
+        Ok(())
+    }
+
+    //mp decode
+    /// Decode the compressed data into 'dest', which must be at least
+    /// [GltfMeshoptCompression::decoded_length] bytes long
+    ///
+    /// The compression must have been validated
+    pub fn decode(&self, source: &[u8], dest: &mut [u8]) -> Result<()> {
+        let dest = &mut dest[0..self.decoded_length()];
+        match self.mode.as_str() {
+            "ATTRIBUTES" => decode_vertex_buffer(dest, self.byte_stride, source)?,
+            "TRIANGLES" => decode_index_buffer(dest, self.count, self.byte_stride, source)?,
+            _ => decode_index_sequence(dest, self.count, self.byte_stride, source)?,
+        }
+        match self.filter() {
+            "OCTAHEDRAL" => decode_filter_oct(dest, self.byte_stride),
+            "QUATERNION" => decode_filter_quat(dest),
+            "EXPONENTIAL" => decode_filter_exp(dest),
+            _ => (),
+        }
+        Ok(())
+    }
+}
+
+//a Vertex codec
+//fi bad_data
+fn bad_data(reason: &str) -> Error {
+    Error::Meshopt(reason.into())
+}
+
+//fi unzigzag8
+/// Decode a zigzag-encoded byte delta
+fn unzigzag8(v: u8) -> u8 {
+    (0_u8.wrapping_sub(v & 1)) ^ (v >> 1)
+}
+
+//fi decode_bytes_group
+/// Decode a group of 16 bytes, each encoded with 0, 2, 4 or 8 bits
+/// (with 2 and 4 bit values of all ones indicating a following
+/// byte), returning the remaining data
+fn decode_bytes_group<'a>(data: &'a [u8], buffer: &mut [u8], bitslog2: u8) -> Result<&'a [u8]> {
+    let bits = match bitslog2 {
+        0 => {
+            buffer.fill(0);
+            return Ok(data);
+        }
+        1 => 2,
+        2 => 4,
+        _ => {
+            let Some(group) = data.get(0..BYTE_GROUP_SIZE) else {
+                return Err(bad_data("vertex data too short"));
+            };
+            buffer.copy_from_slice(group);
+            return Ok(&data[BYTE_GROUP_SIZE..]);
+        }
+    };
+    let header_size = BYTE_GROUP_SIZE * bits / 8;
+    let Some(header) = data.get(0..header_size) else {
+        return Err(bad_data("vertex data too short"));
+    };
+    let sentinel = (1_u8 << bits) - 1;
+    let mut extra = header_size;
+    let per_byte = 8 / bits;
+    for (i, b) in buffer.iter_mut().enumerate() {
+        let shift = 8 - bits * (i % per_byte + 1);
+        let enc = (header[i / per_byte] >> shift) & sentinel;
+        if enc == sentinel {
+            let Some(v) = data.get(extra) else {
+                return Err(bad_data("vertex data too short"));
+            };
+            *b = *v;
+            extra += 1;
+        } else {
+            *b = enc;
+        }
+    }
+    Ok(&data[extra..])
+}
+
+//fi decode_bytes
+/// Decode a byte stream for one byte of each vertex of a block,
+/// returning the remaining data
+fn decode_bytes<'a>(data: &'a [u8], buffer: &mut [u8]) -> Result<&'a [u8]> {
+    // Two bits of header for each group, rounded up to whole bytes
+    let header_size = (buffer.len() / BYTE_GROUP_SIZE).div_ceil(4);
+    if data.len() < header_size {
+        return Err(bad_data("vertex data too short"));
+    }
+    let (header, mut data) = data.split_at(header_size);
+    for (i, group) in buffer.chunks_exact_mut(BYTE_GROUP_SIZE).enumerate() {
+        let bitslog2 = (header[i / 4] >> ((i % 4) * 2)) & 3;
+        data = decode_bytes_group(data, group, bitslog2)?;
+    }
+    Ok(data)
+}
+
+//fi decode_vertex_block
+/// Decode a block of vertices, returning the remaining data
+fn decode_vertex_block<'a>(
+    mut data: &'a [u8],
+    vertex_data: &mut [u8],
+    vertex_count: usize,
+    vertex_size: usize,
+    last_vertex: &mut [u8],
+) -> Result<&'a [u8]> {
+    let mut buffer = [0_u8; VERTEX_BLOCK_MAX_SIZE];
+    let vertex_count_aligned = vertex_count.next_multiple_of(BYTE_GROUP_SIZE);
+    for k in 0..vertex_size {
+        data = decode_bytes(data, &mut buffer[0..vertex_count_aligned])?;
+        let mut p = last_vertex[k];
+        for (i, delta) in buffer[0..vertex_count].iter().enumerate() {
+            let v = unzigzag8(*delta).wrapping_add(p);
+            vertex_data[i * vertex_size + k] = v;
+            p = v;
+        }
+    }
+    let last = (vertex_count - 1) * vertex_size;
+    last_vertex.copy_from_slice(&vertex_data[last..last + vertex_size]);
+    Ok(data)
+}
+
+//fi decode_vertex_buffer
+/// Decode data compressed with the vertex codec, of vertices of
+/// 'vertex_size' bytes, filling 'dest'
+fn decode_vertex_buffer(dest: &mut [u8], vertex_size: usize, source: &[u8]) -> Result<()> {
+    if source.len() < 1 + vertex_size {
+        return Err(bad_data("vertex data too short"));
+    }
+    if source[0] != VERTEX_HEADER {
+        return Err(bad_data("unsupported vertex codec header"));
+    }
+    let mut last_vertex = [0_u8; 256];
+    let last_vertex = &mut last_vertex[0..vertex_size];
+    last_vertex.copy_from_slice(&source[source.len() - vertex_size..]);
+
+    // Block size is a multiple of the byte group size
+    let block_size = ((VERTEX_BLOCK_SIZE_BYTES / vertex_size) & !(BYTE_GROUP_SIZE - 1))
+        .min(VERTEX_BLOCK_MAX_SIZE);
+
+    let mut data = &source[1..];
+    for block in dest.chunks_mut(block_size * vertex_size) {
+        let count = block.len() / vertex_size;
+        data = decode_vertex_block(data, block, count, vertex_size, last_vertex)?;
+    }
+    if data.len() != vertex_size.max(TAIL_MAX_SIZE) {
+        return Err(bad_data("vertex data has the wrong length"));
+    }
+    Ok(())
+}
+
+//a Index codecs
+//fi decode_vbyte
+/// Decode a variable-length (7 bits per byte) integer, returning it
+/// and the remaining data
+fn decode_vbyte(data: &[u8]) -> Result<(u32, &[u8])> {
+    let mut result = 0;
+    for (i, group) in data.iter().enumerate().take(5) {
+        result |= ((group & 127) as u32) << (7 * i);
+        if *group < 128 {
+            return Ok((result, &data[i + 1..]));
+        }
+    }
+    if data.len() < 5 {
+        return Err(bad_data("index data too short"));
+    }
+    Ok((result, &data[5..]))
+}
+
+//fi decode_index
+/// Decode a zigzag-encoded delta from the last index
+fn decode_index(data: &[u8], last: u32) -> Result<(u32, &[u8])> {
+    let (v, data) = decode_vbyte(data)?;
+    let d = (v >> 1) ^ 0_u32.wrapping_sub(v & 1);
+    Ok((last.wrapping_add(d), data))
+}
+
+//fi write_index
+/// Write an index as a 2 or 4 byte little-endian value
+fn write_index(dest: &mut [u8], i: usize, index_size: usize, index: u32) {
+    if index_size == 2 {
+        dest[i * 2..i * 2 + 2].copy_from_slice(&(index as u16).to_le_bytes());
+    } else {
+        dest[i * 4..i * 4 + 4].copy_from_slice(&index.to_le_bytes());
+    }
+}
+
+//ti IndexFifos
+/// The vertex and edge FIFOs of the index codec
+struct IndexFifos {
+    vertices: [u32; 16],
+    vertex_offset: usize,
+    edges: [(u32, u32); 16],
+    edge_offset: usize,
+}
+
+//ii IndexFifos
+impl IndexFifos {
+    fn vertex(&self, n: usize) -> u32 {
+        self.vertices[self.vertex_offset.wrapping_sub(n) & 15]
+    }
+    fn edge(&self, n: usize) -> (u32, u32) {
+        self.edges[self.edge_offset.wrapping_sub(1 + n) & 15]
+    }
+    fn push_vertex(&mut self, v: u32, cond: bool) {
+        self.vertices[self.vertex_offset] = v;
+        self.vertex_offset = (self.vertex_offset + cond as usize) & 15;
+    }
+    fn push_edge(&mut self, a: u32, b: u32) {
+        self.edges[self.edge_offset] = (a, b);
+        self.edge_offset = (self.edge_offset + 1) & 15;
+    }
+}
+
+//fi decode_index_buffer
+/// Decode triangle indices compressed with the index codec
+fn decode_index_buffer(
+    dest: &mut [u8],
+    index_count: usize,
+    index_size: usize,
+    source: &[u8],
+) -> Result<()> {
+    // Minimum is header, a byte per triangle, and the codeaux table
+    if source.len() < 1 + index_count / 3 + 16 {
+        return Err(bad_data("index data too short"));
+    }
+    if source[0] & 0xf0 != INDEX_HEADER || source[0] & 0x0f > 1 {
+        return Err(bad_data("unsupported index codec header"));
+    }
+    let fec_max = if source[0] & 0x0f >= 1 { 13 } else { 15 };
+
+    let mut fifos = IndexFifos {
+        vertices: [u32::MAX; 16],
+        vertex_offset: 0,
+        edges: [(u32::MAX, u32::MAX); 16],
+        edge_offset: 0,
+    };
+    let mut next = 0_u32;
+    let mut last = 0_u32;
+
+    let codes = &source[1..1 + index_count / 3];
+    let data_end = source.len() - 16;
+    let codeaux_table = &source[data_end..];
+    let mut data = &source[1 + index_count / 3..data_end];
+
+    for (t, codetri) in codes.iter().enumerate() {
+        let codetri = *codetri;
+        let i = t * 3;
+        if codetri < 0xf0 {
+            let (a, b) = fifos.edge((codetri >> 4) as usize);
+            let fec = (codetri & 15) as usize;
+            let (c, new_vertex) = {
+                if fec < fec_max {
+                    if fec == 0 {
+                        next += 1;
+                        (next - 1, true)
+                    } else {
+                        (fifos.vertex(1 + fec), false)
+                    }
+                } else {
+                    if fec != 15 {
+                        // 13 and 14 are deltas of -1 and +1 from the last index
+                        last = last.wrapping_add(fec as u32).wrapping_sub(fec as u32 ^ 3);
+                    } else {
+                        (last, data) = decode_index(data, last)?;
+                    }
+                    (last, true)
+                }
+            };
+            write_index(dest, i, index_size, a);
+            write_index(dest, i + 1, index_size, b);
+            write_index(dest, i + 2, index_size, c);
+            fifos.push_vertex(c, new_vertex);
+            fifos.push_edge(c, b);
+            fifos.push_edge(a, c);
+        } else {
+            let (fea, codeaux) = {
+                if codetri < 0xfe {
+                    (0, codeaux_table[(codetri & 15) as usize])
+                } else {
+                    let Some(codeaux) = data.first() else {
+                        return Err(bad_data("index data too short"));
+                    };
+                    data = &data[1..];
+                    if *codeaux == 0 {
+                        next = 0;
+                    }
+                    (if codetri == 0xfe { 0 } else { 15 }, *codeaux)
+                }
+            };
+            let feb = (codeaux >> 4) as usize;
+            let fec = (codeaux & 15) as usize;
+            let free_vertex = |fe: usize, next: &mut u32| {
+                if fe == 0 {
+                    *next += 1;
+                    *next - 1
+                } else if fe < 15 {
+                    fifos.vertex(fe)
+                } else {
+                    0
+                }
+            };
+            let mut a = if fea == 0 {
+                free_vertex(0, &mut next)
+            } else {
+                0
+            };
+            let mut b = free_vertex(feb, &mut next);
+            let mut c = free_vertex(fec, &mut next);
+            if fea == 15 {
+                (last, data) = decode_index(data, last)?;
+                a = last;
+            }
+            if feb == 15 {
+                (last, data) = decode_index(data, last)?;
+                b = last;
+            }
+            if fec == 15 {
+                (last, data) = decode_index(data, last)?;
+                c = last;
+            }
+            write_index(dest, i, index_size, a);
+            write_index(dest, i + 1, index_size, b);
+            write_index(dest, i + 2, index_size, c);
+            fifos.push_vertex(a, true);
+            fifos.push_vertex(b, feb == 0 || feb == 15);
+            fifos.push_vertex(c, fec == 0 || fec == 15);
+            fifos.push_edge(b, a);
+            fifos.push_edge(c, b);
+            fifos.push_edge(a, c);
+        }
+    }
+    if !data.is_empty() {
+        return Err(bad_data("index data has the wrong length"));
+    }
+    Ok(())
+}
+
+//fi decode_index_sequence
+/// Decode indices compressed with the index sequence codec
+fn decode_index_sequence(
+    dest: &mut [u8],
+    index_count: usize,
+    index_size: usize,
+    source: &[u8],
+) -> Result<()> {
+    // Minimum is header, a byte per index, and a 4-byte tail
+    if source.len() < 1 + index_count + 4 {
+        return Err(bad_data("index sequence data too short"));
+    }
+    if source[0] & 0xf0 != SEQUENCE_HEADER || source[0] & 0x0f > 1 {
+        return Err(bad_data("unsupported index sequence codec header"));
+    }
+    let mut data = &source[1..source.len() - 4];
+    let mut last = [0_u32; 2];
+    for i in 0..index_count {
+        let v;
+        (v, data) = decode_vbyte(data)?;
+        // The bottom bit selects which of two baselines the delta is from
+        let current = (v & 1) as usize;
+        let v = v >> 1;
+        let d = (v >> 1) ^ 0_u32.wrapping_sub(v & 1);
+        last[current] = last[current].wrapping_add(d);
+        write_index(dest, i, index_size, last[current]);
+    }
+    if !data.is_empty() {
+        return Err(bad_data("index sequence data has the wrong length"));
+    }
+    Ok(())
+}
+
+//a Filters
+//fi round_to_int
+/// Round a float to the nearest integer, with halves away from zero
+fn round_to_int(v: f32) -> i32 {
+    (v + if v >= 0. { 0.5 } else { -0.5 }) as i32
+}
+
+//fi decode_filter_oct
+/// Decode octahedral-encoded unit vectors of 4 signed 8-bit or 16-bit
+/// components; the third component is the encoding of 1.0, and is
+/// replaced by the z of the vector
+fn decode_filter_oct(data: &mut [u8], stride: usize) {
+    let oct = |x: f32, y: f32, one: f32, max: f32| {
+        let z = one - x.abs() - y.abs();
+        let t = z.min(0.);
+        let x = x + if x >= 0. { t } else { -t };
+        let y = y + if y >= 0. { t } else { -t };
+        let s = max / (x * x + y * y + z * z).sqrt();
+        [x * s, y * s, z * s].map(round_to_int)
+    };
+    if stride == 4 {
+        for v in data.chunks_exact_mut(4) {
+            let [x, y, one] = [v[0], v[1], v[2]].map(|c| c as i8 as f32);
+            let xyz = oct(x, y, one, 127.);
+            for (d, c) in v.iter_mut().zip(xyz) {
+                *d = c as i8 as u8;
+            }
+        }
+    } else {
+        for v in data.chunks_exact_mut(8) {
+            let [x, y, one] = [0, 2, 4].map(|i| i16::from_le_bytes([v[i], v[i + 1]]) as f32);
+            let xyz = oct(x, y, one, 32767.);
+            for (d, c) in v.chunks_exact_mut(2).zip(xyz) {
+                d.copy_from_slice(&(c as i16).to_le_bytes());
+            }
+        }
+    }
+}
+
+//fi decode_filter_quat
+/// Decode quaternions of 4 signed 16-bit components, encoded as three
+/// components and the index of the largest (which is reconstructed)
+/// with the scale
+fn decode_filter_quat(data: &mut [u8]) {
+    let scale = std::f32::consts::FRAC_1_SQRT_2;
+    for v in data.chunks_exact_mut(8) {
+        let q: [i16; 4] = [0, 2, 4, 6].map(|i| i16::from_le_bytes([v[i], v[i + 1]]));
+        let ss = scale / ((q[3] | 3) as f32);
+        let [x, y, z] = [q[0], q[1], q[2]].map(|c| c as f32 * ss);
+        let w = (1. - x * x - y * y - z * z).max(0.).sqrt();
+        let qc = (q[3] & 3) as usize;
+        let components = [
+            ((qc + 1) & 3, round_to_int(x * 32767.)),
+            ((qc + 2) & 3, round_to_int(y * 32767.)),
+            ((qc + 3) & 3, round_to_int(z * 32767.)),
+            (qc, (w * 32767. + 0.5) as i32),
+        ];
+        for (i, c) in components {
+            v[i * 2..i * 2 + 2].copy_from_slice(&(c as i16).to_le_bytes());
+        }
+    }
+}
+
+//fi decode_filter_exp
+/// Decode 32-bit floats encoded as a 24-bit signed mantissa and an
+/// 8-bit signed exponent
+fn decode_filter_exp(data: &mut [u8]) {
+    for v in data.chunks_exact_mut(4) {
+        let e = i32::from_le_bytes([v[0], v[1], v[2], v[3]]);
+        let m = (e << 8) >> 8;
+        let e = e >> 24;
+        let f = f32::from_bits(((e + 127) as u32) << 23) * m as f32;
+        v.copy_from_slice(&f.to_le_bytes());
+    }
+}
@@ -13,16 +13,66 @@
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-#[cfg(feature = "serde_json")]
-use serde_json::Value as JsonValue;
-#[cfg(not(feature = "serde_json"))]
-pub type JsonValue = ();
-
 use mod3d_base::Transformation;
 
-use crate::{CameraIndex, MeshIndex, Named, NodeIndex, SkinIndex};
+use crate::GltfNodeExtensions;
+use crate::{AccessorIndex, CameraIndex, LightIndex, MeshIndex, Named, NodeIndex, SkinIndex};
 use crate::{Error, Result};
 
+//a GltfMeshGpuInstancing
+//tp GltfInstancingAttributes
+/// The per-instance attributes of the EXT_mesh_gpu_instancing
+/// extension; each is an accessor with one element per instance
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GltfInstancingAttributes {
+    /// Translation of each instance (VEC3)
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "TRANSLATION", skip_serializing_if = "Option::is_none")
+    )]
+    pub translation: Option<AccessorIndex>,
+    /// Rotation of each instance as a quaternion (VEC4)
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "ROTATION", skip_serializing_if = "Option::is_none")
+    )]
+    pub rotation: Option<AccessorIndex>,
+    /// Scale of each instance (VEC3)
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "SCALE", skip_serializing_if = "Option::is_none")
+    )]
+    pub scale: Option<AccessorIndex>,
+}
+
+//ip GltfInstancingAttributes
+impl GltfInstancingAttributes {
+    //mp iter
+    /// Iterate through the accessors of the attributes that are
+    /// present, with the number of elements they require
+    pub fn iter(&self) -> impl Iterator<Item = (AccessorIndex, usize)> + '_ {
+        [(self.translation, 3), (self.rotation, 4), (self.scale, 3)]
+            .into_iter()
+            .filter_map(|(a, n)| a.map(|a| (a, n)))
+    }
+}
+
+//tp GltfMeshGpuInstancing
+/// The EXT_mesh_gpu_instancing extension of a Gltf node, which draws
+/// the mesh of the node once for each of a set of TRS transformations
+///
+/// The instance transformations are applied before the
+/// transformation of the node
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GltfMeshGpuInstancing {
+    /// The per-instance attributes
+    pub attributes: GltfInstancingAttributes,
+}
+
 //a GltfNode
 //tp GltfNode
 #[derive(Debug, Default)]
@@ -33,19 +83,30 @@ pub struct GltfNode {
     name: String,
     /// The children of the node; if there are none then this is a root node
     children: Vec<NodeIndex>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     camera: Option<CameraIndex>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     skin: Option<SkinIndex>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     matrix: Option<[f32; 16]>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     mesh: Option<MeshIndex>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     rotation: Option<[f32; 4]>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     translation: Option<[f32; 3]>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     scale: Option<[f32; 3]>,
-    weights: Option<JsonValue>,
+    /// Weights of the morph targets of the mesh, overriding those of the mesh
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    weights: Option<Vec<f32>>,
+    /// The supported extensions of the node, such as a punctual light
+    extensions: GltfNodeExtensions,
     #[cfg_attr(feature = "serde", serde(skip))]
     local_transformation: Transformation,
     #[cfg_attr(feature = "serde", serde(skip))]
     global_transformation: Transformation,
-    // optional: extensions, extras
+    // optional: extras
 }
 
 //ip Named for GltfNode
@@ -71,10 +132,14 @@ impl GltfNode {
                 "Node {n} has a matrix and some TRS",
             )));
         }
-
-        if self.weights.is_some() {
+        if self.weights.is_some() && self.mesh.is_none() {
+            return Err(Error::BadJson(format!(
+                "Node {n} has morph target weights but no mesh",
+            )));
+        }
+        if self.extensions.gpu_instancing.is_some() && self.mesh.is_none() {
             return Err(Error::BadJson(format!(
-                "Node {n} has morpht target weights that are not supported",
+                "Node {n} has mesh instancing but no mesh",
             )));
         }
         Ok(())
@@ -89,7 +154,7 @@ impl GltfNode {
                 self.local_transformation.set_scale(scale);
             }
             if let Some(rotation) = self.rotation {
-                let rotation = (rotation[3], rotation[0], rotation[1], rotation[2]).into();
+                // Gltf quaternions are [x,y,z,w], as are those of geo_nd
                 self.local_transformation.set_rotation(rotation);
             }
             if let Some(translation) = self.translation {
@@ -113,15 +178,37 @@ impl GltfNode {
     pub fn skin(&self) -> Option<SkinIndex> {
         self.skin
     }
+    /// The weights of the morph targets of the mesh of the node, if
+    /// they override those of the mesh
+    pub fn weights(&self) -> Option<&[f32]> {
+        self.weights.as_deref()
+    }
     pub fn camera(&self) -> Option<CameraIndex> {
         self.camera
     }
+    pub fn light(&self) -> Option<LightIndex> {
+        self.extensions.light.as_ref().map(|l| l.light)
+    }
+    /// The per-instance attributes of the node if its mesh is
+    /// instanced (EXT_mesh_gpu_instancing)
+    pub fn gpu_instancing(&self) -> Option<&GltfInstancingAttributes> {
+        self.extensions
+            .gpu_instancing
+            .as_ref()
+            .map(|g| &g.attributes)
+    }
+    pub fn local_transformation(&self) -> &Transformation {
+        &self.local_transformation
+    }
     pub fn global_transformation(&self) -> &Transformation {
         &self.global_transformation
     }
     pub fn set_mesh(&mut self, mesh: MeshIndex) {
         self.mesh = Some(mesh);
     }
+    pub fn add_child(&mut self, child: NodeIndex) {
+        self.children.push(child);
+    }
     pub fn set_transformation(&mut self, transformation: &mod3d_base::Transformation) {
         self.local_transformation = *transformation;
     }
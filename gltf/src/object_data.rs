@@ -1,17 +1,20 @@
 //a Imports
+use geo_nd::matrix;
 use mod3d_base::hierarchy::NodeEnumOp;
 use mod3d_base::{
-    BufferData, BufferDataAccessor, BufferDescriptor, BufferIndexAccessor, ByteBuffer, Renderable,
-    VertexDesc,
+    AnimationChannel, AnimationClip, BufferData, BufferDataAccessor, BufferDescriptor,
+    BufferIndexAccessor, ByteBuffer, Mat4, Renderable, Skeleton, VertexDesc,
 };
 
 use crate::try_buf_parse_base64;
-use crate::Gltf;
+use crate::uri_mime_type;
+use crate::UriResolver;
 use crate::{
-    AccessorIndex, BufferIndex, BufferUsage, ImageIndex, MaterialIndex, MeshIndex, NodeIndex,
-    PrimitiveIndex, SamplerIndex, TextureIndex, ViewIndex,
+    AccessorIndex, AnimationIndex, BufferIndex, BufferUsage, ImageIndex, MaterialIndex, MeshIndex,
+    NodeIndex, PrimitiveIndex, SamplerIndex, SceneIndex, SkinIndex, TextureIndex, ViewIndex,
 };
 use crate::{Error, Result};
+use crate::{Gltf, GltfBuffer, ValidationReport};
 use crate::{
     Indexable, ODAccIndex, ODBufDescIndex, ODImagesIndex, ODMaterialsIndex, ODTexturesIndex,
     ODUses, ODVerticesIndex,
@@ -47,15 +50,22 @@ pub struct ObjectData {
     /// Nodes used in the object; this must contain all the mesh nodes (but not
     /// the joint nodes)
     nodes_used: Vec<NodeIndex>,
-    /// Nodes used in skeletons for the object
-    ///
-    /// There is no support (as yet) for unskinned meshes that are children of
-    /// joints; if a joint appears anywhere in the ancestors of an unskinned
-    /// mesh node then *unlike* the GLTF specification (which says the joint
-    /// pose should be used) the mesh is not posed, but placed at its static
-    /// positioning given by the hierarchy of nodes from its root node
-    #[allow(dead_code)]
+    /// Skins used by the skinned mesh nodes of the object
+    skins_used: Vec<SkinIndex>,
+    /// Joint nodes of the bones of the skeleton for the object, skin by
+    /// skin (in the order of skins_used); the position of a joint in
+    /// this array is the matrix index of its bone in the generated
+    /// Skeleton
+    ///
+    /// Each skin has its own bones, so a node that is a joint of more
+    /// than one skin has a bone for each of them
+    ///
+    /// An unskinned mesh node that has a joint as an ancestor (such as a
+    /// sword held in a hand) is rigidly attached to the bone of the nearest
+    /// such joint, and so follows the animated pose of that joint
     joints_used: Vec<NodeIndex>,
+    /// Animations that target any of the nodes or joints used by the object
+    animations_used: Vec<AnimationIndex>,
     materials_used: ODUses<MaterialIndex, ODMaterialsIndex>,
 
     /// For each image in the Gltf, the index into the Vec<> array (if used and it
@@ -100,6 +110,25 @@ pub struct ObjectData {
     /// used (in the order of the Gltf fiile itself) to a client
     /// Vertices index (which must be the same index as in the mod3d_base::Object
     meshes: Vec<Option<Vec<Option<ODVerticesIndex>>>>,
+
+    /// Accessors that have no buffer view or are sparse, with their
+    /// use (Some(true) for index data, Some(false) for vertex data,
+    /// None for CPU data)
+    ///
+    /// These are materialized into a generated buffer by gen_buffers
+    materialized_accessors: Vec<(Option<bool>, AccessorIndex)>,
+
+    /// The instance matrices of the nodes used by the object that
+    /// have instanced meshes (EXT_mesh_gpu_instancing)
+    ///
+    /// These are generated by gen_instances
+    instance_matrices: Vec<(NodeIndex, Vec<Mat4>)>,
+
+    /// The inverse bind matrix of each bone (in the order of
+    /// joints_used), from its skin, if the skins have been read
+    ///
+    /// These are generated by gen_skins
+    inverse_bind_matrices: Vec<Mat4>,
 }
 
 //ip Index<BufferIndex> for ObjectData
@@ -173,7 +202,9 @@ impl ObjectData {
         let num_accessors = gltf.accessors().len();
 
         let nodes_used = vec![];
+        let skins_used = vec![];
         let joints_used = vec![];
+        let animations_used = vec![];
         let materials_used = ODUses::new();
         let textures_used = ODUses::new();
         let images_used = ODUses::new();
@@ -183,9 +214,14 @@ impl ObjectData {
         let meshes = vec![Default::default(); num_meshes];
         let accessors_as_bd = vec![Default::default(); num_accessors];
         let accessors = vec![Default::default(); num_accessors];
+        let materialized_accessors = vec![];
+        let instance_matrices = vec![];
+        let inverse_bind_matrices = vec![];
         Self {
             nodes_used,
+            skins_used,
             joints_used,
+            animations_used,
             materials_used,
             textures_used,
             buffer_usage,
@@ -195,6 +231,9 @@ impl ObjectData {
             accessors,
             images_used,
             samplers_used,
+            materialized_accessors,
+            instance_matrices,
+            inverse_bind_matrices,
         }
     }
 
@@ -203,15 +242,6 @@ impl ObjectData {
         self.buffer_usage.get_mut(0)
     }
 
-    //mi add_joint_node
-    /// Add a joint node to the set of nodes used by this Object
-    #[allow(dead_code)]
-    fn add_joint_node(&mut self, node: NodeIndex) {
-        if !self.joints_used.contains(&node) {
-            self.joints_used.push(node);
-        }
-    }
-
     //mi add_node
     /// Add a node index to the set of nodes used by this Object
     fn add_node(&mut self, node: NodeIndex) {
@@ -220,18 +250,54 @@ impl ObjectData {
         }
     }
 
+    //mi add_skin
+    /// Add a skin, and hence a bone for each of its joints, to the set
+    /// used by this Object
+    fn add_skin(&mut self, gltf: &Gltf, skin: SkinIndex) {
+        if self.skins_used.contains(&skin) {
+            return;
+        }
+        self.skins_used.push(skin);
+        self.joints_used.extend_from_slice(gltf[skin].joints());
+    }
+
     //mp add_object
     /// Add an object to the ObjectData; adds all the nodes in the hierarchy of
-    /// the specified node
+    /// the specified node, and the joints of any skins that they use
     pub fn add_object(&mut self, gltf: &Gltf, node: NodeIndex) {
         let nh_index = gltf.nh_index(node);
         for eo in gltf.node_hierarchy().iter_from(nh_index.as_usize()) {
             if let NodeEnumOp::Push((_, n), _) = eo {
                 self.add_node(*n);
+                if let Some(skin) = gltf[*n].skin() {
+                    self.add_skin(gltf, skin);
+                }
             }
         }
     }
 
+    //mp add_scene
+    /// Add a scene to the ObjectData; adds the hierarchies of all of
+    /// the root nodes of the scene (and the joints of any skins that
+    /// they use), so that the object keeps all the trees of the scene
+    /// with their transformations
+    pub fn add_scene(&mut self, gltf: &Gltf, scene: SceneIndex) {
+        for node in gltf[scene].iter_roots() {
+            self.add_object(gltf, node);
+        }
+    }
+
+    //mp add_default_scene
+    /// Add the default scene of the Gltf (see [Gltf::default_scene])
+    /// to the ObjectData, returning the scene added
+    ///
+    /// Returns None (and adds nothing) if the Gltf has no scenes
+    pub fn add_default_scene(&mut self, gltf: &Gltf) -> Option<SceneIndex> {
+        let scene = gltf.default_scene()?;
+        self.add_scene(gltf, scene);
+        Some(scene)
+    }
+
     //mi use_buffer
     /// Record the use of a portion of a buffer in its Usage
     ///
@@ -248,11 +314,32 @@ impl ObjectData {
         self[buffer].use_buffer(as_index, byte_start, byte_length);
     }
 
+    //mi use_materialized_accessor
+    /// Record the use of an accessor that must be materialized, and
+    /// the use of the buffer regions it is generated from
+    fn use_materialized_accessor(&mut self, gltf: &Gltf, use_as: Option<bool>, acc: AccessorIndex) {
+        if self.materialized_accessors.iter().any(|(_, a)| *a == acc) {
+            return;
+        }
+        self.materialized_accessors.push((use_as, acc));
+        let ba = &gltf[acc];
+        let sparse_views = ba
+            .sparse()
+            .map(|s| [s.indices.buffer_view, s.values.buffer_view]);
+        for bv in ba
+            .buffer_view()
+            .into_iter()
+            .chain(sparse_views.into_iter().flatten())
+        {
+            let buffer = gltf[bv].buffer();
+            self[buffer].use_cpu_data(gltf[bv].byte_offset(), gltf[bv].byte_length());
+        }
+    }
+
     //mi derive_uses_of_meshes
     /// Fill out the meshes and buffer regions that are used
     fn derive_uses_of_meshes(&mut self, gltf: &Gltf) {
-        for n in 0..self.nodes_used.len() {
-            let ni: NodeIndex = n.into();
+        for ni in self.nodes_used.clone() {
             let node = &gltf[ni];
             if let Some(node_mesh) = node.mesh() {
                 let mesh = &mut self[node_mesh];
@@ -276,7 +363,7 @@ impl ObjectData {
                     if let Some(a) = p.indices() {
                         accessors.push((true, a));
                     }
-                    for (_, a) in p.attributes() {
+                    for (_, a) in p.attributes().iter().chain(p.targets().iter().flatten()) {
                         accessors.push((false, *a));
                     }
                     if let Some(m) = p.material() {
@@ -292,7 +379,9 @@ impl ObjectData {
     /// Fill out the meshes and buffer regions that are used
     fn derive_uses_of_accessors(&mut self, gltf: &Gltf, accessors: Vec<(bool, AccessorIndex)>) {
         for (as_index, a) in accessors {
-            if let Some(bv) = gltf[a].buffer_view() {
+            if gltf[a].requires_materializing() {
+                self.use_materialized_accessor(gltf, Some(as_index), a);
+            } else if let Some(bv) = gltf[a].buffer_view() {
                 let buffer = gltf[bv].buffer();
                 let byte_start = gltf[bv].byte_offset();
                 let byte_length = gltf[bv].byte_length();
@@ -301,28 +390,87 @@ impl ObjectData {
         }
     }
 
+    //mi derive_uses_of_animations
+    /// Fill out the animations that target the nodes and joints, and the
+    /// buffer regions that their samplers use
+    fn derive_uses_of_animations(&mut self, gltf: &Gltf) {
+        for (i, a) in gltf.animations().iter().enumerate() {
+            if !a.targets_any(&self.nodes_used) && !a.targets_any(&self.joints_used) {
+                continue;
+            }
+            self.animations_used.push(i.into());
+            for s in a.samplers() {
+                for acc in [s.input(), s.output()] {
+                    if gltf[acc].requires_materializing() {
+                        self.use_materialized_accessor(gltf, None, acc);
+                    } else if let Some(bv) = gltf[acc].buffer_view() {
+                        let buffer = gltf[bv].buffer();
+                        self[buffer].use_cpu_data(gltf[bv].byte_offset(), gltf[bv].byte_length());
+                    }
+                }
+            }
+        }
+    }
+
+    //mi derive_uses_of_skins
+    /// Fill out the buffer regions used by the inverse bind matrices
+    /// of the skins, which are read by the CPU
+    fn derive_uses_of_skins(&mut self, gltf: &Gltf) {
+        for skin in self.skins_used.clone() {
+            let Some(acc) = gltf[skin].inverse_bind_matrices() else {
+                continue;
+            };
+            if gltf[acc].requires_materializing() {
+                self.use_materialized_accessor(gltf, None, acc);
+            } else if let Some(bv) = gltf[acc].buffer_view() {
+                let buffer = gltf[bv].buffer();
+                self[buffer].use_cpu_data(gltf[bv].byte_offset(), gltf[bv].byte_length());
+            }
+        }
+    }
+
+    //mi derive_uses_of_instancing
+    /// Fill out the buffer regions used by the instance attributes of
+    /// the nodes, which are read by the CPU
+    fn derive_uses_of_instancing(&mut self, gltf: &Gltf) {
+        for n in self.nodes_used.clone() {
+            let Some(instancing) = gltf[n].gpu_instancing() else {
+                continue;
+            };
+            for (acc, _) in instancing.iter() {
+                if gltf[acc].requires_materializing() {
+                    self.use_materialized_accessor(gltf, None, acc);
+                } else if let Some(bv) = gltf[acc].buffer_view() {
+                    let buffer = gltf[bv].buffer();
+                    self[buffer].use_cpu_data(gltf[bv].byte_offset(), gltf[bv].byte_length());
+                }
+            }
+        }
+    }
+
+    //mi derive_uses_of_compressed_views
+    /// Fill out the buffer regions of the compressed data of the
+    /// buffer views (EXT_meshopt_compression) of the buffers that are
+    /// used, which are decoded by the CPU
+    fn derive_uses_of_compressed_views(&mut self, gltf: &Gltf) {
+        for bv in gltf.buffer_views() {
+            let Some(compression) = bv.meshopt_compression() else {
+                continue;
+            };
+            if self[bv.buffer()].is_used() {
+                self[compression.buffer]
+                    .use_cpu_data(compression.byte_offset, compression.byte_length);
+            }
+        }
+    }
+
     //mi derive_uses_of_textures
     /// Fill out the texture usage
     fn derive_uses_of_textures(&mut self, gltf: &Gltf) {
         for (mi, _use) in self.materials_used.iter_required() {
-            let material = &gltf[mi];
-            if let Some(ti) = material.normal_texture() {
-                self.textures_used.set_required(ti.index());
-            }
-            if let Some(ti) = material.occlusion_texture() {
-                self.textures_used.set_required(ti.index());
-            }
-            if let Some(ti) = material.emissive_texture() {
+            for (_, ti) in gltf[mi].iter_textures() {
                 self.textures_used.set_required(ti.index());
             }
-            if let Some(pbr) = material.pbr_metallic_roughness() {
-                if let Some(ti) = pbr.base_color_texture() {
-                    self.textures_used.set_required(ti.index());
-                }
-                if let Some(ti) = pbr.metallic_roughness_texture() {
-                    self.textures_used.set_required(ti.index());
-                }
-            }
         }
     }
 
@@ -332,7 +480,9 @@ impl ObjectData {
         for (ti, _use) in self.textures_used.iter_required() {
             let texture = &gltf[ti];
             self.images_used.set_required(texture.image());
-            self.samplers_used.set_required(texture.sampler());
+            if let Some(sampler) = texture.sampler() {
+                self.samplers_used.set_required(sampler);
+            }
         }
     }
 
@@ -343,6 +493,10 @@ impl ObjectData {
         self.derive_uses_of_meshes(gltf);
         let accessors = self.derive_uses_of_materials(gltf);
         self.derive_uses_of_accessors(gltf, accessors);
+        self.derive_uses_of_animations(gltf);
+        self.derive_uses_of_skins(gltf);
+        self.derive_uses_of_instancing(gltf);
+        self.derive_uses_of_compressed_views(gltf);
         self.derive_uses_of_textures(gltf);
         self.derive_uses_of_images_and_samplers(gltf);
         eprintln!("gltf : object_data : does not yet derive buffer uses of images - it won't gen_buffers for them");
@@ -372,6 +526,18 @@ impl ObjectData {
     ///
     /// The rest are created by invoking buf_parse on the Uri and
     /// byte_length specified in the [GltfJsonValue]
+    ///
+    /// Buffers that contain buffer views compressed with
+    /// EXT_meshopt_compression are generated by decoding those views
+    /// (on top of the contents of the buffer, if it has a URI)
+    ///
+    /// If any accessors used have no buffer view, or are sparse, then
+    /// their dense data is generated in a final additional buffer, and
+    /// the Gltf is updated to refer to that instead
+    ///
+    /// The index values of the mesh primitives in the buffers are then
+    /// validated (see [Gltf::validate_index_values]), returning an
+    /// [Error::Validation] if any are out of range
     pub fn gen_buffers<B, BP>(
         &mut self,
         gltf: &mut Gltf,
@@ -379,6 +545,7 @@ impl ObjectData {
         opt_buffer_0: Option<B>,
     ) -> Result<Vec<B>>
     where
+        B: ByteBuffer + From<Vec<u8>>,
         BP: Fn(&str, usize) -> Result<B>,
     {
         let mut result = vec![];
@@ -390,6 +557,7 @@ impl ObjectData {
                 b.set_buffer_index(0.into());
             }
         }
+        let mut compressed = vec![];
         for i in 0..self.buffer_usage.len() {
             let bi: BufferIndex = i.into();
             let buffer = gltf.take_buffer_data(bi);
@@ -397,13 +565,137 @@ impl ObjectData {
                 continue;
             }
             if i > 0 || !used_opt_0 {
+                if gltf.has_compressed_views(bi) {
+                    compressed.push((bi, buffer));
+                    continue;
+                }
                 self[bi].set_buffer_index(result.len().into());
                 result.push(buf_parse(buffer.uri(), buffer.byte_length())?);
             }
         }
+        for (bi, buffer) in compressed {
+            self.decode_compressed_views(gltf, bi, buffer, &mut result, buf_parse)?;
+        }
+        if !self.materialized_accessors.is_empty() {
+            self.materialize_accessors(gltf, &mut result)?;
+        }
+
+        let mut report = ValidationReport::default();
+        let buffer_data = |bi: BufferIndex| {
+            let usage = self.buffer_usage.get(bi.as_usize())?;
+            if !usage.is_used() {
+                return None;
+            }
+            let b: &B = result.get(usage.buffer_index().as_usize())?;
+            Some(b.borrow_bytes())
+        };
+        gltf.validate_index_values(&mut report, &buffer_data);
+        report.into_result()?;
         Ok(result)
     }
 
+    //mi decode_compressed_views
+    /// Generate a buffer by decoding all the buffer views of the Gltf
+    /// that are compressed with EXT_meshopt_compression into it
+    ///
+    /// If the buffer has a URI then its contents are used for the
+    /// rest of the buffer; otherwise (for a 'fallback' buffer) the
+    /// rest of it is zero
+    fn decode_compressed_views<B, BP>(
+        &mut self,
+        gltf: &Gltf,
+        bi: BufferIndex,
+        buffer: GltfBuffer,
+        buffers: &mut Vec<B>,
+        buf_parse: &BP,
+    ) -> Result<()>
+    where
+        B: ByteBuffer + From<Vec<u8>>,
+        BP: Fn(&str, usize) -> Result<B>,
+    {
+        let mut data = {
+            if buffer.uri().is_empty() {
+                vec![0; buffer.byte_length()]
+            } else {
+                buf_parse(buffer.uri(), buffer.byte_length())?
+                    .borrow_bytes()
+                    .to_vec()
+            }
+        };
+        for bv in gltf.buffer_views() {
+            let Some(compression) = bv.meshopt_compression() else {
+                continue;
+            };
+            if bv.buffer() != bi {
+                continue;
+            }
+            if gltf.has_compressed_views(compression.buffer) {
+                return Err(Error::BadJson(format!(
+                    "Meshopt compressed data in buffer {} is itself compressed",
+                    compression.buffer
+                )));
+            }
+            let source = buffers[self[compression.buffer].buffer_index().as_usize()].borrow_bytes();
+            let Some(source) = source.get(compression.byte_offset..compression.byte_end()) else {
+                return Err(Error::BufferTooShort);
+            };
+            compression.decode(source, &mut data[bv.byte_offset()..bv.byte_end()])?;
+        }
+        self[bi].set_buffer_index(buffers.len().into());
+        buffers.push(data.into());
+        Ok(())
+    }
+
+    //mi materialize_accessors
+    /// Generate a buffer containing the dense data of all the
+    /// accessors that require it, adding a buffer view for each to the
+    /// Gltf
+    fn materialize_accessors<B>(&mut self, gltf: &mut Gltf, buffers: &mut Vec<B>) -> Result<()>
+    where
+        B: ByteBuffer + From<Vec<u8>>,
+    {
+        let mut data = vec![];
+        let mut placements = vec![];
+        let view_data = |view: ViewIndex| {
+            let bv = &gltf[view];
+            let buffer = self.buffer_usage[bv.buffer().as_usize()].buffer_index();
+            Ok((bv, buffers[buffer.as_usize()].borrow_bytes()))
+        };
+        for (use_as, acc) in &self.materialized_accessors {
+            let ba = &gltf[*acc];
+            let ele_size = ba.ele_byte_size();
+            // Vertex attributes must be 4-byte aligned
+            let byte_stride = {
+                if *use_as == Some(true) {
+                    ele_size
+                } else {
+                    ele_size.next_multiple_of(4)
+                }
+            };
+            let byte_offset = data.len();
+            data.extend(ba.materialize(byte_stride, &view_data)?);
+            let byte_length = data.len() - byte_offset;
+            data.resize(data.len().next_multiple_of(4), 0);
+            let opt_stride = (byte_stride != ele_size).then_some(byte_stride);
+            placements.push((*use_as, *acc, byte_offset, byte_length, opt_stride));
+        }
+
+        let buffer = gltf.add_buffer(GltfBuffer::of_uri("", data.len()));
+        self.buffer_usage.push(BufferUsage::default());
+        self[buffer].set_buffer_index(buffers.len().into());
+        for (use_as, acc, byte_offset, byte_length, opt_stride) in placements {
+            let view = gltf.add_view(buffer, byte_offset, byte_length, opt_stride);
+            self.buffer_descriptors.push(None);
+            gltf[acc].set_dense_view(view, 0);
+            match use_as {
+                Some(as_index) => self.use_buffer(as_index, buffer, byte_offset, byte_length),
+                None => self[buffer].use_cpu_data(byte_offset, byte_length),
+            }
+        }
+        buffers.push(data.into());
+        Ok(())
+    }
+
     //mp gen_byte_buffers
     /// Generate a Vec of all the Vec<u8> buffers required for the objects used
     /// in the Gltf
@@ -501,6 +793,85 @@ impl ObjectData {
         Ok(result)
     }
 
+    //mp gen_resolved_buffers
+    /// Generate a Vec of all the Vec<u8> buffers required for the
+    /// objects used in the Gltf, using a [UriResolver] for the buffer
+    /// URIs
+    ///
+    /// This is the same as [gen_byte_buffers] except that the URIs
+    /// are resolved by the resolver (which may then also be used for
+    /// [gen_resolved_images])
+    pub fn gen_resolved_buffers<U: UriResolver>(
+        &mut self,
+        gltf: &mut Gltf,
+        resolver: &U,
+        opt_buffer_0: Option<Vec<u8>>,
+    ) -> Result<Vec<Vec<u8>>> {
+        let bp = |uri: &str, byte_length: usize| {
+            let buf = resolver.resolve(uri)?;
+            if buf.len() < byte_length {
+                Err(Error::BufferTooShort)
+            } else {
+                Ok(buf)
+            }
+        };
+        self.gen_buffers(gltf, &bp, opt_buffer_0)
+    }
+
+    //mp gen_resolved_images
+    /// Generate a Vec of all the images, using a [UriResolver] for
+    /// images with URIs, and the buffers (from [gen_resolved_buffers]
+    /// for example) for images with buffer views
+    ///
+    /// The decode function is invoked with the bytes of each image
+    /// and its MIME type; this is the 'mimeType' of the image if
+    /// given, otherwise derived from the URI
+    pub fn gen_resolved_images<'buffers, Image, U, B, D>(
+        &mut self,
+        gltf: &Gltf,
+        resolver: &U,
+        buffer: &B,
+        decode: &D,
+    ) -> Result<Vec<Image>>
+    where
+        U: UriResolver,
+        B: Fn(usize) -> &'buffers [u8],
+        D: Fn(&[u8], &str) -> std::result::Result<Image, String>,
+    {
+        let mut result = vec![];
+        for (ii, image_use) in self.images_used.iter_mut_required() {
+            let image = &gltf[ii];
+            let od_image = {
+                if let Some(uri) = image.uri() {
+                    let mime_type = {
+                        if image.mime_type().is_empty() {
+                            uri_mime_type(uri).unwrap_or("")
+                        } else {
+                            image.mime_type()
+                        }
+                    };
+                    decode(&resolver.resolve(uri)?, mime_type)
+                } else {
+                    let bv = &gltf[image.buffer_view()];
+                    // As with gen_images, use self.buffer_usage directly
+                    let bi = self.buffer_usage[bv.buffer().as_usize()].buffer_index();
+                    let byte_offset = bv.byte_offset();
+                    let byte_length = bv.byte_length();
+                    let data = buffer(bi.as_usize());
+                    let Some(data) = data.get(byte_offset..byte_offset + byte_length) else {
+                        return Err(Error::BufferTooShort);
+                    };
+                    decode(data, image.mime_type())
+                }
+            }
+            .map_err(|e| Error::ImageLoad { reason: e })?;
+            let n = result.len();
+            result.push(od_image);
+            image_use.set_use(n.into());
+        }
+        Ok(result)
+    }
+
     //mi make_index_accessor
     fn make_index_accessor<'buffers, F, R>(
         &self,
@@ -513,7 +884,9 @@ impl ObjectData {
         R: Renderable,
     {
         let ba = &gltf[acc];
-        let bv = ba.buffer_view().unwrap();
+        let bv = ba
+            .buffer_view()
+            .expect("Accessors without views are materialized by gen_buffers");
         let bv = &gltf[bv];
         let buffer = &self[bv.buffer()];
         let data = buffer.index_bd();
@@ -581,12 +954,14 @@ impl ObjectData {
             }
             let mesh = &gltf[mi];
             for p in mesh.primitives() {
-                for (vertex_attr, va) in p.attributes() {
+                for (vertex_attr, va) in p.attributes().iter().chain(p.targets().iter().flatten()) {
                     if self[*va].is_some() {
                         continue;
                     }
                     let ba = &gltf[*va];
-                    let bv = ba.buffer_view().unwrap();
+                    let bv = ba
+                        .buffer_view()
+                        .expect("Accessors without views are materialized by gen_buffers");
                     if self[bv].is_none() {
                         let bd = self.make_descriptor(gltf, buffer_data, bv);
                         self.buffer_descriptors[*bv] = Some(buffer_descriptors.len().into());
@@ -601,12 +976,13 @@ impl ObjectData {
                             n => [n as u8, 0],
                         }
                     };
-                    let vertex_desc = VertexDesc::new(
+                    let mut vertex_desc = VertexDesc::new(
                         *vertex_attr,
                         ba.component_type(),
                         dims,
                         ba.byte_offset() as u16,
                     );
+                    vertex_desc.set_normalized(ba.is_normalized());
                     eprintln!(
                         "add_data_accessor {} {vertex_desc:?}",
                         buffer_descriptors[n.as_usize()]
@@ -705,6 +1081,13 @@ impl ObjectData {
                 let Some(pa) = pa else {
                     continue;
                 };
+                // Use the min and max of the position accessor as the bounds, if given
+                let bounds = gltf[*pa].bounds().and_then(|(min, max)| {
+                    Some(mod3d_base::Bounds::of_box(
+                        min.try_into().ok()?,
+                        max.try_into().ok()?,
+                    ))
+                });
                 let Some(ia) = self[ia] else {
                     continue;
                 };
@@ -714,6 +1097,7 @@ impl ObjectData {
                 let indices = buffer_index_accessor(ia.as_usize());
                 let positions = buffer_data_accessor(pa.as_usize());
                 let mut v = mod3d_base::Vertices::new(Some(indices), positions);
+                v.set_bounds(bounds);
                 for (va, vpa) in p.attributes() {
                     if *va == mod3d_base::VertexAttr::Position {
                         continue;
@@ -722,6 +1106,16 @@ impl ObjectData {
                         v.add_attr(buffer_data_accessor(vpa.as_usize()));
                     }
                 }
+                for target in p.targets() {
+                    let mut morph_target = mod3d_base::MorphTarget::new();
+                    for (va, vta) in target {
+                        if let Some(vta) = self[*vta] {
+                            morph_target.add_attr(*va, buffer_data_accessor(vta.as_usize()));
+                        }
+                    }
+                    v.add_morph_target(morph_target);
+                }
+                v.set_morph_weights(self.morph_weights_of_mesh(gltf, mi));
                 let primitve_v = vertices.len();
                 vertices.push(v);
                 self[mi].as_mut().unwrap()[pi] = Some(primitve_v.into());
@@ -731,6 +1125,40 @@ impl ObjectData {
         vertices
     }
 
+    //mi morph_weights_of_mesh
+    /// Get the default morph target weights for a mesh
+    ///
+    /// The vertices of a mesh are shared by all the nodes that use it,
+    /// so the weights of the first such node with weights override
+    /// those of the mesh
+    fn morph_weights_of_mesh<'a>(&self, gltf: &'a Gltf, mesh: MeshIndex) -> &'a [f32] {
+        self.nodes_used
+            .iter()
+            .map(|n| &gltf[*n])
+            .filter(|n| n.mesh() == Some(mesh))
+            .find_map(|n| n.weights())
+            .unwrap_or(gltf[mesh].weights())
+    }
+
+    //ap vertices_of_node
+    /// Get the indices of the Vertices generated by gen_vertices for
+    /// the primitives of the mesh of a node
+    ///
+    /// These are the indices into the morph weights of an
+    /// [mod3d_base::Instance] that animations of the weights of the
+    /// node should update
+    pub fn vertices_of_node(&self, gltf: &Gltf, node: NodeIndex) -> Vec<usize> {
+        let Some(mi) = gltf[node].mesh() else {
+            return vec![];
+        };
+        self[mi]
+            .iter()
+            .flatten()
+            .flatten()
+            .map(|v| v.as_usize())
+            .collect()
+    }
+
     //mp gen_textures
     /// Generate textures from the objects in the Gltf, given images
     /// that have been generated already
@@ -750,7 +1178,10 @@ impl ObjectData {
         for (ti, texture_use) in self.textures_used.iter_mut_required() {
             let texture = &gltf[ti];
             let image = image(self.images_used[texture.image].data().unwrap().as_usize());
-            let model_texture = texture_of_image(image);
+            let mut model_texture = texture_of_image(image);
+            if let Some(sampler) = texture.sampler() {
+                model_texture.set_sampler(gltf[sampler].texture_sampler());
+            }
             let n = textures.len();
             textures.push(model_texture);
             texture_use.set_use(n.into());
@@ -763,37 +1194,34 @@ impl ObjectData {
     pub fn gen_materials(&mut self, gltf: &Gltf) -> Vec<mod3d_base::PbrMaterial> {
         let mut materials = vec![];
 
+        let textures_used = &self.textures_used;
         for (mi, material_use) in self.materials_used.iter_mut_required() {
             let material = &gltf[mi];
             let mut pbr_mat = mod3d_base::PbrMaterial::of_rgba(0xff112233);
-            if let Some(ti) = material.normal_texture() {
-                if let Some(ti) = self.textures_used[ti.index()].data() {
-                    pbr_mat.set_texture(mod3d_base::MaterialAspect::Normal, ti.into());
-                }
-            }
-            if let Some(ti) = material.occlusion_texture() {
-                if let Some(ti) = self.textures_used[ti.index()].data() {
-                    pbr_mat.set_texture(mod3d_base::MaterialAspect::Occlusion, ti.into());
+            for (aspect, ti) in material.iter_textures() {
+                let Some(t) = textures_used[ti.index()].data() else {
+                    continue;
+                };
+                pbr_mat.set_texture(aspect, t.into());
+                pbr_mat.set_tex_coord(aspect, ti.transformed_tex_coord());
+                if let Some(tt) = ti.texture_transform() {
+                    pbr_mat.set_uv_transform(aspect, tt.uv_matrix());
                 }
-            }
-            if let Some(ti) = material.emissive_texture() {
-                if let Some(ti) = self.textures_used[ti.index()].data() {
-                    pbr_mat.set_texture(mod3d_base::MaterialAspect::Emission, ti.into());
+                match aspect {
+                    mod3d_base::MaterialAspect::Normal => pbr_mat.set_normal_scale(ti.scale()),
+                    mod3d_base::MaterialAspect::Occlusion => {
+                        pbr_mat.set_occlusion_strength(ti.strength())
+                    }
+                    _ => (),
                 }
             }
             pbr_mat.set_rgba((255, 255, 255, 255));
-            if let Some(pbr) = material.pbr_metallic_roughness() {
-                if let Some(ti) = pbr.base_color_texture() {
-                    if let Some(ti) = self.textures_used[ti.index()].data() {
-                        pbr_mat.set_texture(mod3d_base::MaterialAspect::Color, ti.into());
-                    }
-                }
-                if let Some(ti) = pbr.metallic_roughness_texture() {
-                    if let Some(ti) = self.textures_used[ti.index()].data() {
-                        pbr_mat
-                            .set_texture(mod3d_base::MaterialAspect::MetallicRoughness, ti.into());
-                    }
-                }
+            if let Some(sg) = material.specular_glossiness() {
+                let (color, metallic, roughness) = sg.metallic_roughness();
+                let [r, g, b, a] = color.map(|c| (c * 255.0) as u8);
+                pbr_mat.set_rgba((r, g, b, a));
+                pbr_mat.set_mr(metallic, roughness);
+            } else if let Some(pbr) = material.pbr_metallic_roughness() {
                 if let Some(color) = pbr.base_color_factor.as_ref() {
                     // by spec there must be 4 entries of 0.0 to 1.0 inclusive
                     if color.len() == 4 {
@@ -812,6 +1240,20 @@ impl ObjectData {
                 let b = (material.emissive_factor[2] * 255.0) as u8;
                 pbr_mat.set_emissive_rgb((r, g, b));
             }
+            pbr_mat.set_alpha_mode(material.alpha_mode(), material.alpha_cutoff());
+            pbr_mat.set_double_sided(material.double_sided());
+            pbr_mat.set_unlit(material.unlit());
+            pbr_mat.set_emissive_strength(material.emissive_strength());
+            if let Some(clearcoat) = material.clearcoat() {
+                pbr_mat.set_clearcoat(
+                    clearcoat.clearcoat_factor,
+                    clearcoat.clearcoat_roughness_factor,
+                );
+            }
+            if let Some(transmission) = material.transmission() {
+                pbr_mat.set_transmission(transmission.transmission_factor);
+            }
+            pbr_mat.set_ior(material.ior());
             let n = materials.len();
             materials.push(pbr_mat);
             material_use.set_use(n.into());
@@ -820,8 +1262,347 @@ impl ObjectData {
         materials
     }
 
+    //mp gen_skins
+    /// Read the inverse bind matrices of the joints of the skins used
+    /// by the objects, for use by [ObjectData::gen_skeleton]
+    ///
+    /// Each skin has its own bones, with the inverse bind matrices of
+    /// that skin; a joint of a skin without inverse bind matrices has
+    /// the identity
+    ///
+    /// Should be invoked after gen_buffers has returned a Vec<> of the
+    /// buffers used by the data
+    pub fn gen_skins<'a, B, F>(&mut self, gltf: &Gltf, buffer: &F) -> Result<()>
+    where
+        B: ByteBuffer + ?Sized + 'a,
+        F: Fn(usize) -> &'a B,
+    {
+        let mut inverse_bind_matrices = Vec::with_capacity(self.joints_used.len());
+        for skin in &self.skins_used {
+            let floats = match gltf[*skin].inverse_bind_matrices() {
+                Some(acc) => self.read_accessor_floats(gltf, buffer, acc)?,
+                None => vec![],
+            };
+            for i in 0..gltf[*skin].joints().len() {
+                let mut ibm: Mat4 = matrix::identity4();
+                if let Some(f) = floats.get(i * 16..(i + 1) * 16) {
+                    ibm.copy_from_slice(f);
+                }
+                inverse_bind_matrices.push(ibm);
+            }
+        }
+        self.inverse_bind_matrices = inverse_bind_matrices;
+        Ok(())
+    }
+
+    //mp gen_skeleton
+    /// Generate a resolved [Skeleton] from the joints of the skins used by
+    /// the objects, if there are any
+    ///
+    /// Each skin has a bone for each of its joints; the matrix index of
+    /// a bone is the position of its joint in its skin, after the bones
+    /// of the preceding skins (see [ObjectData::skin_bones]). The bones
+    /// of a skin are related as their joint nodes are in the Gltf node
+    /// hierarchy; each bone uses the local transformation of its joint
+    /// node. A bone whose joint has no joint of the same skin as a
+    /// parent is a root of the skeleton, and is placed by the global
+    /// transformation of the parent node of the joint (if it has one),
+    /// so that animating the local transformation of a root joint
+    /// keeps it below its parent
+    ///
+    /// If [ObjectData::gen_skins] has been invoked then the bind pose of
+    /// each bone is given by the inverse bind matrix of its joint in
+    /// its skin; else the rest pose is the bind pose
+    pub fn gen_skeleton(&self, gltf: &Gltf) -> Option<Skeleton> {
+        if self.joints_used.is_empty() {
+            return None;
+        }
+        let mut skeleton = Skeleton::new();
+        for skin in &self.skins_used {
+            let bones = self.skin_bones(gltf, *skin)?;
+            let joints = &self.joints_used[bones.clone()];
+            let bone_of_joint = |node: &NodeIndex| {
+                joints
+                    .iter()
+                    .position(|j| j == node)
+                    .map(|j| bones.start + j)
+            };
+            let mut is_child = vec![false; joints.len()];
+            for j in joints {
+                for c in gltf[*j].iter_children() {
+                    if let Some(c) = bone_of_joint(c) {
+                        is_child[c - bones.start] = true;
+                    }
+                }
+            }
+            for (i, j) in bones.clone().zip(joints.iter()) {
+                let bone = skeleton.add_bone(*gltf[*j].local_transformation(), i);
+                assert_eq!(bone, i);
+                if !is_child[i - bones.start] {
+                    if let Some(parent) = gltf
+                        .nodes()
+                        .iter()
+                        .find(|n| n.iter_children().any(|c| c == j))
+                    {
+                        skeleton.set_root_ptm(bone, parent.global_transformation().mat4());
+                    }
+                }
+                if let Some(ibm) = self.inverse_bind_matrices.get(i) {
+                    skeleton.set_bind_mtb(bone, *ibm);
+                }
+            }
+            for (i, j) in bones.clone().zip(joints.iter()) {
+                for c in gltf[*j].iter_children() {
+                    if let Some(c) = bone_of_joint(c) {
+                        skeleton.relate(i, c);
+                    }
+                }
+            }
+        }
+        skeleton.resolve();
+        skeleton.derive_matrices();
+        Some(skeleton)
+    }
+
+    //ap skin_bones
+    /// Get the range of bones (by matrix index) in the [Skeleton]
+    /// generated by [ObjectData::gen_skeleton] of a skin used by the
+    /// objects; the joint indices of a primitive skinned by the skin
+    /// index this range of the bone matrices
+    pub fn skin_bones(&self, gltf: &Gltf, skin: SkinIndex) -> Option<std::ops::Range<usize>> {
+        let mut start = 0;
+        for s in &self.skins_used {
+            let end = start + gltf[*s].joints().len();
+            if *s == skin {
+                return Some(start..end);
+            }
+            start = end;
+        }
+        None
+    }
+
+    //ap bone_of_node
+    /// Get the bone index in the [Skeleton] generated by
+    /// [ObjectData::gen_skeleton] of a joint node, if it is one
+    ///
+    /// If the node is a joint of more than one skin then this is its
+    /// bone in the first of them; see [ObjectData::bones_of_node]
+    pub fn bone_of_node(&self, node: NodeIndex) -> Option<usize> {
+        self.joints_used.iter().position(|j| *j == node)
+    }
+
+    //ap bones_of_node
+    /// Get the bone indices in the [Skeleton] generated by
+    /// [ObjectData::gen_skeleton] of a joint node, one for each skin
+    /// that it is a joint of
+    pub fn bones_of_node(&self, node: NodeIndex) -> Vec<usize> {
+        self.joints_used
+            .iter()
+            .enumerate()
+            .filter(|(_, j)| **j == node)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    //ap bone_of_ancestor
+    /// Get the bone index in the [Skeleton] generated by
+    /// [ObjectData::gen_skeleton] of the nearest joint node that is
+    /// the node itself or one of its ancestors, if there is one
+    pub fn bone_of_ancestor(&self, gltf: &Gltf, node: NodeIndex) -> Option<usize> {
+        let mut node = node;
+        for _ in 0..gltf.nodes().len() {
+            if let Some(bone) = self.bone_of_node(node) {
+                return Some(bone);
+            }
+            let parent = gltf
+                .nodes()
+                .iter()
+                .position(|n| n.iter_children().any(|c| *c == node))?;
+            node = parent.into();
+        }
+        None
+    }
+
+    //mi read_accessor_floats
+    /// Read the contents of an accessor as floats from the buffers
+    fn read_accessor_floats<'a, B, F>(
+        &self,
+        gltf: &Gltf,
+        buffer: &F,
+        acc: AccessorIndex,
+    ) -> Result<Vec<f32>>
+    where
+        B: ByteBuffer + ?Sized + 'a,
+        F: Fn(usize) -> &'a B,
+    {
+        let ba = &gltf[acc];
+        let Some(bv) = ba.buffer_view() else {
+            return Ok(vec![0.; ba.count() * ba.elements_per_data()]);
+        };
+        let bv = &gltf[bv];
+        let b = buffer(self[bv.buffer()].buffer_index().as_usize());
+        ba.read_floats(bv, b.borrow_bytes())
+    }
+
+    //mp gen_animations
+    /// Generate an [AnimationClip] for each of the animations that target the
+    /// nodes or joints of the objects
+    ///
+    /// Should be invoked after gen_buffers has returned a Vec<> of the
+    /// buffers used by the data
+    ///
+    /// The targets of the channels in the clips are given by
+    /// 'target_of_node', which maps a node to any number of targets (such
+    /// as an Option); a channel is added for each target, and channels
+    /// whose node it maps to no targets are dropped. To animate the
+    /// [SkeletonPose] of the skeleton generated by
+    /// [ObjectData::gen_skeleton] use [ObjectData::bones_of_node]
+    pub fn gen_animations<'a, B, F, T, I>(
+        &self,
+        gltf: &Gltf,
+        buffer: &F,
+        target_of_node: &T,
+    ) -> Result<Vec<AnimationClip>>
+    where
+        B: ByteBuffer + ?Sized + 'a,
+        F: Fn(usize) -> &'a B,
+        T: Fn(NodeIndex) -> I,
+        I: IntoIterator<Item = usize>,
+    {
+        let mut clips = vec![];
+        for ai in &self.animations_used {
+            let animation = &gltf[*ai];
+            let mut clip = AnimationClip::new(&animation.name);
+            for c in animation.channels() {
+                let Some(node) = c.node() else {
+                    continue;
+                };
+                let targets: Vec<usize> = target_of_node(node).into_iter().collect();
+                if targets.is_empty() {
+                    continue;
+                }
+                let sampler = &animation.samplers()[c.sampler()];
+                let times = self.read_accessor_floats(gltf, buffer, sampler.input())?;
+                let values = self.read_accessor_floats(gltf, buffer, sampler.output())?;
+                for target in targets {
+                    clip.add_channel(AnimationChannel::new(
+                        target,
+                        c.path(),
+                        sampler.interpolation(),
+                        times.clone(),
+                        values.clone(),
+                    ));
+                }
+            }
+            clips.push(clip);
+        }
+        Ok(clips)
+    }
+
+    //mp gen_instances
+    /// Generate the instance matrices of the nodes used by the object
+    /// whose meshes are instanced (EXT_mesh_gpu_instancing), for use
+    /// by [ObjectData::gen_object]
+    ///
+    /// Should be invoked after gen_buffers has returned a Vec<> of the
+    /// buffers used by the data
+    pub fn gen_instances<'a, B, F>(&mut self, gltf: &Gltf, buffer: &F) -> Result<()>
+    where
+        B: ByteBuffer + ?Sized + 'a,
+        F: Fn(usize) -> &'a B,
+    {
+        let mut instance_matrices = vec![];
+        for n in &self.nodes_used {
+            let Some(instancing) = gltf[*n].gpu_instancing() else {
+                continue;
+            };
+            let read = |acc: Option<AccessorIndex>| {
+                acc.map(|acc| self.read_accessor_floats(gltf, buffer, acc))
+                    .transpose()
+            };
+            let translations = read(instancing.translation)?;
+            let rotations = read(instancing.rotation)?;
+            let scales = read(instancing.scale)?;
+            let count = instancing
+                .iter()
+                .map(|(acc, _)| gltf[acc].count())
+                .next()
+                .unwrap_or(0);
+            let mut matrices = Vec::with_capacity(count);
+            for i in 0..count {
+                let mut transformation = mod3d_base::Transformation::new();
+                if let Some(t) = &translations {
+                    transformation.set_translation([t[i * 3], t[i * 3 + 1], t[i * 3 + 2]]);
+                }
+                if let Some(r) = &rotations {
+                    transformation.set_rotation([
+                        r[i * 4],
+                        r[i * 4 + 1],
+                        r[i * 4 + 2],
+                        r[i * 4 + 3],
+                    ]);
+                }
+                if let Some(s) = &scales {
+                    transformation.set_scale([s[i * 3], s[i * 3 + 1], s[i * 3 + 2]]);
+                }
+                matrices.push(transformation.mat4());
+            }
+            instance_matrices.push((*n, matrices));
+        }
+        self.instance_matrices = instance_matrices;
+        Ok(())
+    }
+
+    //ap instance_matrices_of_node
+    /// Get the instance matrices of a node generated by
+    /// [ObjectData::gen_instances]; this is None if the mesh of the
+    /// node is not instanced
+    pub fn instance_matrices_of_node(&self, node: NodeIndex) -> Option<&[Mat4]> {
+        self.instance_matrices
+            .iter()
+            .find(|(n, _)| *n == node)
+            .map(|(_, m)| m.as_slice())
+    }
+
+    //mi bone_component_transformation
+    /// Get the transformation of an unskinned mesh node that follows a
+    /// bone, to which the bone matrix is applied when it is drawn
+    ///
+    /// The bone matrix maps the bind pose of the bone to its animated
+    /// pose; the node follows the rest pose of the bone, so if the
+    /// bind pose differs then the node must be mapped from the rest
+    /// pose to the bind pose first
+    fn bone_component_transformation(
+        &self,
+        gltf: &Gltf,
+        bone: usize,
+        node: NodeIndex,
+    ) -> mod3d_base::Transformation {
+        let transformation = *gltf[node].global_transformation();
+        let Some(ibm) = self.inverse_bind_matrices.get(bone) else {
+            return transformation;
+        };
+        let rest_mtb = gltf[self.joints_used[bone]]
+            .global_transformation()
+            .mat4_inverse();
+        let rest_to_bind = matrix::multiply4(&rest_mtb, &matrix::inverse4(ibm));
+        let mut bind_transformation = mod3d_base::Transformation::new();
+        bind_transformation.from_mat4(matrix::multiply4(&transformation.mat4(), &rest_to_bind));
+        bind_transformation
+    }
+
     //mp gen_object
     /// Create object
+    ///
+    /// If the objects use skins then the object has the [Skeleton] created
+    /// by [ObjectData::gen_skeleton], using the inverse bind matrices
+    /// from [ObjectData::gen_skins] if it has been invoked
+    ///
+    /// Nodes with instanced meshes are instanced with the matrices
+    /// from [ObjectData::gen_instances], if it has been invoked
+    ///
+    /// The materials must be those from [ObjectData::gen_materials],
+    /// as the primitives refer to their materials by that order
     pub fn gen_object<'object, M, R>(
         &mut self,
         gltf: &Gltf,
@@ -834,6 +1615,7 @@ impl ObjectData {
         R: Renderable,
     {
         let mut object = mod3d_base::Object::new();
+        object.skeleton = self.gen_skeleton(gltf);
         for v in vertices {
             object.add_vertices(v);
         }
@@ -862,7 +1644,12 @@ impl ObjectData {
                 let gltf_prim = &gltf_mesh[m_pi];
                 let ia = gltf_prim.indices().unwrap();
                 let index_count = gltf[ia].count() as u32;
-                let mat_ind: Option<usize> = Some(0);
+                // The materials of the object are those generated by
+                // gen_materials; a primitive without a material has none
+                let mat_ind: Option<usize> = gltf_prim
+                    .material()
+                    .and_then(|m| self.materials_used[m].data())
+                    .map(|m| m.as_usize());
                 let primitive = mod3d_base::Primitive::new(
                     gltf_prim.primitive_type(),
                     od_vi.into(),
@@ -873,7 +1660,35 @@ impl ObjectData {
                 eprintln!("Add mesh {mesh:?} {m_pi} {primitive:?}");
                 mesh.add_primitive(primitive);
             }
-            object.add_component(None, Some(*node.global_transformation()), mesh);
+            // A skinned mesh is placed by its joints, so the
+            // transformation of its node is ignored (as per the Gltf
+            // spec); its joints index the bones of its own skin
+            //
+            // An unskinned mesh below a joint is placed at its rest
+            // position, and follows the animated pose of the joint
+            if let Some(skin) = node.skin() {
+                let transformation = mod3d_base::Transformation::new();
+                match self.skin_bones(gltf, skin) {
+                    Some(bones) => {
+                        object.add_skinned_component(None, bones, Some(transformation), mesh)
+                    }
+                    None => object.add_component(None, Some(transformation), mesh),
+                };
+            } else if let Some(instances) = self.instance_matrices_of_node(*n) {
+                let transformation = *node.global_transformation();
+                object.add_instanced_component(
+                    None,
+                    Some(transformation),
+                    instances.to_vec(),
+                    mesh,
+                );
+            } else if let Some(bone) = self.bone_of_ancestor(gltf, *n) {
+                let transformation = self.bone_component_transformation(gltf, bone, *n);
+                object.add_bone_component(None, bone, Some(transformation), mesh);
+            } else {
+                let transformation = *node.global_transformation();
+                object.add_component(None, Some(transformation), mesh);
+            }
         }
         object
     }
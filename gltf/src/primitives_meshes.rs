@@ -7,13 +7,32 @@ use crate::{deserialize, serialize};
 
 use crate::{AccessorIndex, Indexable, MaterialIndex, PrimitiveIndex};
 
+//a GltfAttributes
+//tp GltfAttributes
+/// The attributes of a Gltf primitive
+///
+/// Attributes that have no mod3d_base::VertexAttr (such as further
+/// texture coordinate or color sets, or application-specific
+/// attributes whose names start with '_') are valid Gltf that is
+/// ignored; they are kept so that they can be reported by validation,
+/// and serialized again
+#[derive(Debug, Default)]
+pub(crate) struct GltfAttributes {
+    /// The attributes used, mapping a VertexAttr to an AccessorIndex
+    pub(crate) attributes: Vec<(mod3d_base::VertexAttr, AccessorIndex)>,
+    /// The attributes ignored, mapping the attribute name to an
+    /// AccessorIndex
+    pub(crate) ignored: Vec<(String, AccessorIndex)>,
+}
+
 //a GltfPrimitive
 //tp GltfPrimitive
 /// A Gltf primitive, as deserialized from the Gltf Json
 #[derive(Debug, Default)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct GltfPrimitive {
-    /// Attributes mapping a VertexAttr to an AccessorIndex
+    /// Attributes mapping a VertexAttr to an AccessorIndex, and
+    /// those that are ignored
     // This must be a map from attribute name to accessor index
     //
     // attribute name - corresponds to mod3d_base::VertexAttr
@@ -22,7 +41,7 @@ pub struct GltfPrimitive {
         serde(deserialize_with = "deserialize::attr_to_attr")
     )]
     #[cfg_attr(feature = "serde", serde(serialize_with = "serialize::attr_to_attr"))]
-    attributes: Vec<(mod3d_base::VertexAttr, AccessorIndex)>,
+    attributes: GltfAttributes,
 
     /// Mode for drawing the primitive
     // 0-6: POINTS, LINES, LINE_LOOP, LINE_STRIP, TRIANGLES, TRIANGLE_STRIP,
@@ -38,13 +57,28 @@ pub struct GltfPrimitive {
     /// Material index to use when drawing the primitive
     // optional
     #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     material: Option<MaterialIndex>,
 
     /// Indices to use (AccessorIndex) if indexed (otherwise use 0..N)
     // optional - if not present then drawArrays should be used
     #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     indices: Option<AccessorIndex>,
-    // optional: targets
+
+    /// Morph targets of the primitive, each mapping a VertexAttr
+    /// (position, normal or tangent) to the AccessorIndex of its
+    /// displacements
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "deserialize::targets"))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "serialize::targets",
+            skip_serializing_if = "Vec::is_empty"
+        )
+    )]
+    targets: Vec<Vec<(mod3d_base::VertexAttr, AccessorIndex)>>,
     // optional: extensions, extras
 }
 
@@ -81,7 +115,15 @@ impl GltfPrimitive {
     /// Return a slice of tuples of mod3d_base::VertexAttr and
     /// AccessorIndex from the Gltf for the primitive
     pub fn attributes(&self) -> &[(mod3d_base::VertexAttr, AccessorIndex)] {
-        &self.attributes
+        &self.attributes.attributes
+    }
+
+    //ap ignored_attributes
+    /// Return a slice of tuples of the Gltf attribute name and
+    /// AccessorIndex of the attributes of the primitive that have no
+    /// mod3d_base::VertexAttr, and so are ignored
+    pub fn ignored_attributes(&self) -> &[(String, AccessorIndex)] {
+        &self.attributes.ignored
     }
 
     //ap material
@@ -91,7 +133,73 @@ impl GltfPrimitive {
         self.material
     }
     pub fn add_attribute(&mut self, attr: mod3d_base::VertexAttr, accessor: AccessorIndex) {
-        self.attributes.push((attr, accessor))
+        self.attributes.attributes.push((attr, accessor))
+    }
+
+    //ap targets
+    /// Return the morph targets of the primitive; each is a slice of
+    /// tuples of the mod3d_base::VertexAttr it displaces and the
+    /// AccessorIndex of the displacements
+    pub fn targets(&self) -> &[Vec<(mod3d_base::VertexAttr, AccessorIndex)>] {
+        &self.targets
+    }
+
+    //mp add_target
+    /// Add a morph target to the primitive
+    pub fn add_target(&mut self, target: Vec<(mod3d_base::VertexAttr, AccessorIndex)>) {
+        self.targets.push(target)
+    }
+
+    //fp attribute_name
+    /// Get the Gltf attribute name of a vertex attribute
+    pub fn attribute_name(attr: mod3d_base::VertexAttr) -> &'static str {
+        use mod3d_base::VertexAttr::*;
+        match attr {
+            Position => "POSITION",
+            Normal => "NORMAL",
+            Color => "COLOR_0",
+            Color1 => "COLOR_1",
+            Tangent => "TANGENT",
+            Joints => "JOINTS_0",
+            Joints1 => "JOINTS_1",
+            Weights => "WEIGHTS_0",
+            Weights1 => "WEIGHTS_1",
+            TexCoords0 => "TEXCOORD_0",
+            TexCoords1 => "TEXCOORD_1",
+            TexCoords2 => "TEXCOORD_2",
+            TexCoords3 => "TEXCOORD_3",
+        }
+    }
+
+    //fp attribute_type_permitted
+    /// Return true if an accessor of the component type (and
+    /// normalization) may be used for the vertex attribute, or for a
+    /// morph target displacement of it if 'is_target' is true
+    ///
+    /// This is the table of the glTF specification extended by
+    /// KHR_mesh_quantization, which permits 8- and 16-bit integer
+    /// positions, normals, tangents and texture coordinates
+    pub fn attribute_type_permitted(
+        attr: mod3d_base::VertexAttr,
+        is_target: bool,
+        ele_type: mod3d_base::BufferElementType,
+        normalized: bool,
+    ) -> bool {
+        use mod3d_base::BufferElementType::*;
+        use mod3d_base::VertexAttr::*;
+        match (attr, ele_type) {
+            (Joints | Joints1, UInt8 | UInt16) => !normalized && !is_target,
+            (Joints | Joints1, _) => false,
+            (_, Float32) => true,
+            (Position, SInt8 | SInt16) => true,
+            (Position | TexCoords0 | TexCoords1 | TexCoords2 | TexCoords3, UInt8 | UInt16) => {
+                !is_target
+            }
+            (TexCoords0 | TexCoords1 | TexCoords2 | TexCoords3, SInt8 | SInt16) => !is_target,
+            (Normal | Tangent, SInt8 | SInt16) => normalized,
+            (Color | Color1 | Weights | Weights1, UInt8 | UInt16) => normalized && !is_target,
+            _ => false,
+        }
     }
 }
 
@@ -105,8 +213,10 @@ pub struct GltfMesh {
     name: String,
     /// The primitives that make up the mesh
     primitives: Vec<GltfPrimitive>,
-    // optional: weights (ignored as morph targets are not supported)
-    // optional: name, extensions, extras
+    /// Default weights of the morph targets of the primitives
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    weights: Vec<f32>,
+    // optional: extensions, extras
 }
 
 impl GltfMesh {
@@ -127,6 +237,18 @@ impl GltfMesh {
     pub fn primitives(&self) -> &[GltfPrimitive] {
         &self.primitives
     }
+    /// The default weights of the morph targets of the mesh
+    pub fn weights(&self) -> &[f32] {
+        &self.weights
+    }
+    pub fn set_weights(&mut self, weights: Vec<f32>) {
+        self.weights = weights;
+    }
+    /// The number of morph targets of the mesh - every primitive
+    /// must have the same number
+    pub fn num_targets(&self) -> usize {
+        self.primitives.first().map_or(0, |p| p.targets().len())
+    }
 }
 
 //ip Index<PrimitiveIndex> for GltfMesh
@@ -23,6 +23,13 @@ impl GltfScene {
     pub fn add_node(&mut self, node: NodeIndex) {
         self.nodes.push(node);
     }
+
+    //ap iter_roots
+    /// Iterate through the root nodes of the scene; each is the root
+    /// of a distinct tree of nodes in the scene
+    pub fn iter_roots(&self) -> impl Iterator<Item = NodeIndex> + '_ {
+        self.nodes.iter().copied()
+    }
 }
 
 //ip Named for GltfScene
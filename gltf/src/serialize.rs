@@ -1,45 +1,64 @@
 //a Imports
+use std::collections::HashMap;
+
 use serde::{Serialize, Serializer};
 
-use crate::AccessorIndex;
+use crate::{AccessorIndex, GltfAttributes, GltfPrimitive};
 
 //a Useful functions
-//fi attr_to_attr
-/// Map an array of Gltf string attribute name/value pairs to a Vec of
-/// tuples of mod3d_base::VertexAttr and AccessorIndex
-pub fn attr_to_attr<S>(
+//fi map_of_attrs
+/// Map a slice of tuples of mod3d_base::VertexAttr and AccessorIndex
+/// to Gltf string attribute name/value pairs
+fn map_of_attrs<E: serde::ser::Error>(
     attr: &[(mod3d_base::VertexAttr, AccessorIndex)],
-    ser: S,
-) -> Result<S::Ok, S::Error>
-where
-    S: Serializer,
-{
-    use std::collections::HashMap;
+) -> Result<HashMap<String, usize>, E> {
     let mut m = HashMap::<String, usize>::default();
     for (k, v) in attr.iter() {
-        use mod3d_base::VertexAttr::*;
-        let k = {
-            match k {
-                Position => "POSITION",
-                Normal => "NORMAL",
-                Color => "COLOR_0",
-                Tangent => "TANGENT",
-                Joints => "JOINTS_0",
-                Weights => "WEIGHTS_0",
-                TexCoords0 => "TEXCOORD_0",
-                TexCoords1 => "TEXCOORD_1",
-                _ => {
-                    return Err(serde::ser::Error::custom(format!(
-                        "Unknown Gltf attribute {k:?}"
-                    )));
-                }
-            }
-        };
+        let k = GltfPrimitive::attribute_name(*k);
         m.insert(k.to_string(), (*v).into());
     }
+    Ok(m)
+}
+
+//fi is_one
+/// Return true if a value is 1, for properties that default to 1 and
+/// which need not be serialized in that case
+pub fn is_one(value: &f32) -> bool {
+    *value == 1.0
+}
+
+//fi attr_to_attr
+/// Map the [GltfAttributes] of a primitive, including those that are
+/// ignored, to Gltf string attribute name/value pairs
+pub(crate) fn attr_to_attr<S>(attr: &GltfAttributes, ser: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let mut m = map_of_attrs(&attr.attributes)?;
+    for (k, v) in attr.ignored.iter() {
+        m.insert(k.clone(), (*v).into());
+    }
     m.serialize(ser)
 }
 
+//fi targets
+/// Map an array of morph targets, each a Vec of tuples of
+/// mod3d_base::VertexAttr and AccessorIndex, to an array of Gltf
+/// string attribute name/value pairs
+pub fn targets<S>(
+    targets: &[Vec<(mod3d_base::VertexAttr, AccessorIndex)>],
+    ser: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    targets
+        .iter()
+        .map(|t| map_of_attrs(t))
+        .collect::<Result<Vec<_>, _>>()?
+        .serialize(ser)
+}
+
 //fi primitive_type
 /// Map a Gltf primitive type specified by an integer to a mod3d_base::PrimitiveType
 pub fn primitive_type<S>(
@@ -124,3 +143,77 @@ where
     };
     c.serialize(ser)
 }
+
+//fi interpolation
+/// Map a mod3d_base::Interpolation to a Gltf animation sampler
+/// interpolation name
+pub fn interpolation<S>(
+    interpolation: &mod3d_base::Interpolation,
+    ser: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    use mod3d_base::Interpolation::*;
+    match interpolation {
+        Step => "STEP",
+        Linear => "LINEAR",
+        CubicSpline => "CUBICSPLINE",
+    }
+    .serialize(ser)
+}
+
+//fi texture_wrap
+/// Map a mod3d_base::TextureWrap to a Gltf sampler wrap mode (a GL
+/// enumeration)
+pub fn texture_wrap<S>(wrap: &mod3d_base::TextureWrap, ser: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    use mod3d_base::TextureWrap::*;
+    match wrap {
+        ClampToEdge => 33071_usize,
+        MirroredRepeat => 33648,
+        Repeat => 10497,
+    }
+    .serialize(ser)
+}
+
+//fi texture_filter
+/// Map a mod3d_base::TextureFilter to a Gltf sampler filter (a GL
+/// enumeration)
+pub fn texture_filter<S>(filter: &mod3d_base::TextureFilter, ser: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    use mod3d_base::TextureFilter::*;
+    match filter {
+        Nearest => 9728_usize,
+        Linear => 9729,
+        NearestMipmapNearest => 9984,
+        LinearMipmapNearest => 9985,
+        NearestMipmapLinear => 9986,
+        LinearMipmapLinear => 9987,
+    }
+    .serialize(ser)
+}
+
+//fi animation_path
+/// Map a mod3d_base::AnimationProperty to a Gltf animation channel target
+/// path
+pub fn animation_path<S>(
+    property: &mod3d_base::AnimationProperty,
+    ser: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    use mod3d_base::AnimationProperty::*;
+    match property {
+        Translation => "translation",
+        Rotation => "rotation",
+        Scale => "scale",
+        Weights => "weights",
+    }
+    .serialize(ser)
+}
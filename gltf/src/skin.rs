@@ -0,0 +1,69 @@
+//a Imports
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Named;
+use crate::{AccessorIndex, NodeIndex, SkinIndex};
+
+//a GltfSkin
+//tp GltfSkin
+/// A type that contains the data from a Gltf Json 'Skin'
+///
+/// A skin is a set of joints (nodes in the Gltf node hierarchy); a
+/// skinned mesh node refers to a skin, and the JOINTS_n attributes of
+/// its primitives are indices into the 'joints' array of the skin
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GltfSkin {
+    /// Optional name of the skin
+    pub name: String,
+    /// Optional accessor of MAT4 that contains the inverse bind matrix for
+    /// each joint; if not present then each is the identity matrix
+    #[cfg_attr(feature = "serde", serde(rename = "inverseBindMatrices"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub inverse_bind_matrices: Option<AccessorIndex>,
+    /// Optional node that is the common root of the joints
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub skeleton: Option<NodeIndex>,
+    /// The nodes used as joints in the skin, in the order that the
+    /// JOINTS_n attributes index them
+    pub joints: Vec<NodeIndex>,
+}
+
+//ip GltfSkin
+impl GltfSkin {
+    //ap inverse_bind_matrices
+    /// Get the accessor of the inverse bind matrices, if any
+    pub fn inverse_bind_matrices(&self) -> Option<AccessorIndex> {
+        self.inverse_bind_matrices
+    }
+
+    //ap skeleton
+    /// Get the node that is the skeleton root, if specified
+    pub fn skeleton(&self) -> Option<NodeIndex> {
+        self.skeleton
+    }
+
+    //ap joints
+    /// Get the joint nodes of the skin
+    pub fn joints(&self) -> &[NodeIndex] {
+        &self.joints
+    }
+
+    //mp add_joint
+    /// Add a joint node to the skin, returning its joint number
+    pub fn add_joint(&mut self, node: NodeIndex) -> usize {
+        let n = self.joints.len();
+        self.joints.push(node);
+        n
+    }
+}
+
+//ip Named for GltfSkin
+impl Named for GltfSkin {
+    type Index = SkinIndex;
+    fn is_name(&self, name: &str) -> bool {
+        self.name == name
+    }
+}
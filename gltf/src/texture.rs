@@ -2,6 +2,12 @@
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "serde")]
+use crate::{deserialize, serialize};
+
+use mod3d_base::Mat3;
+
+use crate::GltfTextureInfoExtensions;
 use crate::Named;
 use crate::{ImageIndex, SamplerIndex, TextureIndex};
 
@@ -19,16 +25,127 @@ pub struct GltfTextureInfo {
     /// Image index (source)
     #[cfg_attr(feature = "serde", serde(rename = "texCoord"))]
     pub tex_coord: usize,
-    /// Scale - for normal textures only
+    /// Scale - for normal textures only, and not serialized if 1
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default = "deserialize::f32_one",
+            skip_serializing_if = "serialize::is_one"
+        )
+    )]
     pub scale: f32,
-    /// Strength - for occlusion textures only
+    /// Strength - for occlusion textures only, and not serialized if 1
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default = "deserialize::f32_one",
+            skip_serializing_if = "serialize::is_one"
+        )
+    )]
     pub strength: f32,
+    /// The supported extensions of the texture info, such as
+    /// KHR_texture_transform
+    pub extensions: GltfTextureInfoExtensions,
 }
 
 impl GltfTextureInfo {
     pub fn index(&self) -> TextureIndex {
         self.index
     }
+    pub fn tex_coord(&self) -> usize {
+        self.tex_coord
+    }
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+    pub fn strength(&self) -> f32 {
+        self.strength
+    }
+    /// Get the texture transform (from the KHR_texture_transform
+    /// extension), if there is one
+    pub fn texture_transform(&self) -> Option<&GltfTextureTransform> {
+        self.extensions.texture_transform.as_ref()
+    }
+    /// Get the texture coordinate set to use, which the texture
+    /// transform may override
+    pub fn transformed_tex_coord(&self) -> usize {
+        self.texture_transform()
+            .and_then(|t| t.tex_coord)
+            .unwrap_or(self.tex_coord)
+    }
+}
+
+//a GltfTextureTransform
+//tp GltfTextureTransform
+/// The KHR_texture_transform extension of a texture info, which
+/// transforms the texture coordinates before the texture is sampled
+///
+/// The transformed coordinates are the coordinates scaled, then
+/// rotated, then offset
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GltfTextureTransform {
+    /// Offset of the texture coordinates
+    pub offset: [f32; 2],
+    /// Rotation of the texture coordinates, counter-clockwise in radians
+    pub rotation: f32,
+    /// Scale of the texture coordinates
+    pub scale: [f32; 2],
+    /// Texture coordinate set to use in place of that of the texture info
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "texCoord", skip_serializing_if = "Option::is_none")
+    )]
+    pub tex_coord: Option<usize>,
+}
+
+//ip Default for GltfTextureTransform
+impl Default for GltfTextureTransform {
+    fn default() -> Self {
+        Self {
+            offset: [0., 0.],
+            rotation: 0.,
+            scale: [1., 1.],
+            tex_coord: None,
+        }
+    }
+}
+
+//ip GltfTextureTransform
+impl GltfTextureTransform {
+    //fp of_uv_matrix
+    /// Create a texture transform from a UV matrix, which should be
+    /// a scale, rotation and offset
+    pub fn of_uv_matrix(m: &Mat3) -> Self {
+        let scale = [m[0].hypot(m[1]), m[3].hypot(m[4])];
+        let rotation = (-m[1]).atan2(m[0]);
+        Self {
+            offset: [m[6], m[7]],
+            rotation,
+            scale,
+            tex_coord: None,
+        }
+    }
+
+    //ap uv_matrix
+    /// Get the transform as a (column-major) 3x3 matrix to apply to
+    /// texture coordinates (u, v, 1)
+    pub fn uv_matrix(&self) -> Mat3 {
+        let (s, c) = self.rotation.sin_cos();
+        let [sx, sy] = self.scale;
+        [
+            c * sx,
+            -s * sx,
+            0.,
+            s * sy,
+            c * sy,
+            0.,
+            self.offset[0],
+            self.offset[1],
+            1.,
+        ]
+    }
 }
 
 //a GltfTexture
@@ -43,15 +160,17 @@ pub struct GltfTexture {
     /// Image index (source)
     #[cfg_attr(feature = "serde", serde(rename = "source"))]
     pub image: ImageIndex,
-    /// Sampler index
-    pub sampler: SamplerIndex,
+    /// Optional sampler index; if not present then the texture
+    /// repeats and is filtered linearly
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub sampler: Option<SamplerIndex>,
 }
 
 impl GltfTexture {
     pub fn image(&self) -> ImageIndex {
         self.image
     }
-    pub fn sampler(&self) -> SamplerIndex {
+    pub fn sampler(&self) -> Option<SamplerIndex> {
         self.sampler
     }
 }
@@ -63,3 +182,85 @@ impl Named for GltfTexture {
         self.name == name
     }
 }
+
+//a GltfSampler
+//tp GltfSampler
+/// A type representing a Gltf Sampler, which specifies the wrapping
+/// and filtering of the textures that use it
+///
+/// If the filters are not specified then linear filtering is used
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GltfSampler {
+    /// Optional name of the sampler
+    pub name: String,
+    /// Magnification filter
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            rename = "magFilter",
+            deserialize_with = "deserialize::texture_mag_filter"
+        )
+    )]
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize::texture_filter"))]
+    pub mag_filter: mod3d_base::TextureFilter,
+    /// Minification filter
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "minFilter", deserialize_with = "deserialize::texture_filter")
+    )]
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize::texture_filter"))]
+    pub min_filter: mod3d_base::TextureFilter,
+    /// Wrapping for the S (U) coordinate
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "wrapS", deserialize_with = "deserialize::texture_wrap")
+    )]
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize::texture_wrap"))]
+    pub wrap_s: mod3d_base::TextureWrap,
+    /// Wrapping for the T (V) coordinate
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "wrapT", deserialize_with = "deserialize::texture_wrap")
+    )]
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize::texture_wrap"))]
+    pub wrap_t: mod3d_base::TextureWrap,
+}
+
+//ip GltfSampler
+impl GltfSampler {
+    //cp of_texture_sampler
+    /// Create a sampler from a [mod3d_base::TextureSampler]
+    ///
+    /// Mipmap filtering is dropped from the magnification filter, as
+    /// Gltf does not permit it
+    pub fn of_texture_sampler(sampler: &mod3d_base::TextureSampler) -> Self {
+        Self {
+            name: String::new(),
+            mag_filter: sampler.mag_filter.without_mipmaps(),
+            min_filter: sampler.min_filter,
+            wrap_s: sampler.wrap_s,
+            wrap_t: sampler.wrap_t,
+        }
+    }
+
+    //ap texture_sampler
+    /// Get the [mod3d_base::TextureSampler] for the sampler
+    pub fn texture_sampler(&self) -> mod3d_base::TextureSampler {
+        mod3d_base::TextureSampler {
+            wrap_s: self.wrap_s,
+            wrap_t: self.wrap_t,
+            mag_filter: self.mag_filter,
+            min_filter: self.min_filter,
+        }
+    }
+}
+
+//ip Named for GltfSampler
+impl Named for GltfSampler {
+    type Index = SamplerIndex;
+    fn is_name(&self, name: &str) -> bool {
+        self.name == name
+    }
+}
@@ -77,6 +77,8 @@ index_type!(TextureIndex);
 index_type!(MaterialIndex);
 index_type!(SamplerIndex);
 index_type!(PrimitiveIndex);
+index_type!(AnimationIndex);
+index_type!(LightIndex);
 
 index_type!(ODBufIndex);
 index_type!(ODBufDataIndex);
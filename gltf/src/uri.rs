@@ -0,0 +1,127 @@
+//a Imports
+use std::path::{Path, PathBuf};
+
+use crate::utils::{parse_data_uri, percent_decode};
+use crate::{Error, Result};
+
+//a UriResolver
+//tt UriResolver
+/// A trait for resolving the URIs of buffers and images in a glTF
+/// file to their data
+///
+/// This is used by [crate::ObjectData::gen_resolved_buffers] and
+/// [crate::ObjectData::gen_resolved_images], so that the same
+/// resolver serves both
+pub trait UriResolver {
+    /// Resolve a URI to its bytes
+    fn resolve(&self, uri: &str) -> Result<Vec<u8>>;
+}
+
+//a DataUriResolver
+//tp DataUriResolver
+/// A [UriResolver] that only supports data: URIs (of any MIME type);
+/// any other URI is an error
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DataUriResolver;
+
+//ip UriResolver for DataUriResolver
+impl UriResolver for DataUriResolver {
+    fn resolve(&self, uri: &str) -> Result<Vec<u8>> {
+        match parse_data_uri(uri)? {
+            Some((_, bytes)) => Ok(bytes),
+            None => Err(Error::Uri {
+                uri: uri.into(),
+                reason: "only data URIs are supported".into(),
+            }),
+        }
+    }
+}
+
+//a FileUriResolver
+//tp FileUriResolver
+/// A [UriResolver] that reads relative (percent-encoded) URIs from
+/// the filesystem, relative to a root directory - usually that
+/// containing the .gltf file
+///
+/// data: URIs are also supported
+#[derive(Debug, Clone)]
+pub struct FileUriResolver {
+    root: PathBuf,
+}
+
+//ip FileUriResolver
+impl FileUriResolver {
+    //cp new
+    /// Create a new resolver for URIs relative to a root directory
+    pub fn new<P: Into<PathBuf>>(root: P) -> Self {
+        let root = root.into();
+        Self { root }
+    }
+
+    //cp of_gltf_path
+    /// Create a new resolver for URIs relative to the directory
+    /// containing a .gltf file
+    pub fn of_gltf_path<P: AsRef<Path>>(path: P) -> Self {
+        let root = path
+            .as_ref()
+            .parent()
+            .map(|p| p.to_path_buf())
+            .unwrap_or_default();
+        Self { root }
+    }
+
+    //ap root
+    /// Get the root directory of the resolver
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    //mp path_of_uri
+    /// Get the path of a (non-data:) URI
+    ///
+    /// The URI is percent-decoded; absolute file: URIs are permitted,
+    /// but any other scheme is not
+    pub fn path_of_uri(&self, uri: &str) -> Result<PathBuf> {
+        let uri_err = |reason: &str| Error::Uri {
+            uri: uri.into(),
+            reason: reason.into(),
+        };
+        let (is_absolute, path) = {
+            if let Some(path) = uri.strip_prefix("file://") {
+                (true, path)
+            } else if let Some((scheme, _)) = uri.split_once(':') {
+                if scheme.len() > 1
+                    && scheme
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+                {
+                    return Err(uri_err("unsupported URI scheme"));
+                }
+                (false, uri)
+            } else {
+                (false, uri)
+            }
+        };
+        let path = String::from_utf8(percent_decode(path)?)
+            .map_err(|_| uri_err("URI is not UTF-8 once decoded"))?;
+        if is_absolute {
+            Ok(path.into())
+        } else {
+            Ok(self.root.join(path))
+        }
+    }
+}
+
+//ip UriResolver for FileUriResolver
+impl UriResolver for FileUriResolver {
+    fn resolve(&self, uri: &str) -> Result<Vec<u8>> {
+        if let Some((_, bytes)) = parse_data_uri(uri)? {
+            return Ok(bytes);
+        }
+        let path = self.path_of_uri(uri)?;
+        std::fs::read(&path).map_err(|e| Error::Uri {
+            uri: uri.into(),
+            reason: format!("failed to read {}: {e}", path.display()),
+        })
+    }
+}
@@ -4,17 +4,99 @@ use base64::Engine;
 
 use crate::{Error, Result};
 
+//a URI functions
+//fp percent_decode
+/// Decode a percent-encoded string (such as a URI) to its bytes
+pub fn percent_decode(s: &str) -> Result<Vec<u8>> {
+    let bytes = s.as_bytes();
+    let mut result = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'%' {
+            result.push(bytes[i]);
+            i += 1;
+            continue;
+        }
+        let Some(b) = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok())
+        else {
+            return Err(Error::Uri {
+                uri: s.into(),
+                reason: "bad percent encoding".into(),
+            });
+        };
+        result.push(b);
+        i += 3;
+    }
+    Ok(result)
+}
+
+//fp parse_data_uri
+/// Attempt to parse a URI as a data: URI, of any MIME type
+///
+/// If it is not a data: URI then return Ok(None); if it is, then
+/// return the MIME type (without any parameters) and the data, which
+/// is either base64 or percent encoded
+pub fn parse_data_uri(uri: &str) -> Result<Option<(&str, Vec<u8>)>> {
+    let Some(rest) = uri.strip_prefix("data:") else {
+        return Ok(None);
+    };
+    let Some((header, data)) = rest.split_once(',') else {
+        return Err(Error::Uri {
+            uri: uri.chars().take(64).collect(),
+            reason: "data URI has no data".into(),
+        });
+    };
+    let (header, is_base64) = {
+        if let Some(header) = header.strip_suffix(";base64") {
+            (header, true)
+        } else {
+            (header, false)
+        }
+    };
+    let mime_type = header.split(';').next().unwrap_or("");
+    let bytes = {
+        if is_base64 {
+            base64_decoder::STANDARD.decode(data)?
+        } else {
+            percent_decode(data)?
+        }
+    };
+    Ok(Some((mime_type, bytes)))
+}
+
+//fp uri_mime_type
+/// Get the MIME type of the data referred to by a URI, from the MIME
+/// type of a data: URI or the extension of a file
+pub fn uri_mime_type(uri: &str) -> Option<&str> {
+    if let Some(rest) = uri.strip_prefix("data:") {
+        let header = rest.split([',', ';']).next()?;
+        return Some(header);
+    }
+    let (_, extension) = uri.rsplit_once('.')?;
+    match extension.to_ascii_lowercase().as_str() {
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "webp" => Some("image/webp"),
+        "ktx2" => Some("image/ktx2"),
+        "bin" => Some("application/octet-stream"),
+        _ => None,
+    }
+}
+
 //a Buffer parsing functions
 //fp try_buf_parse_base64
-/// Attempt to parse a URI as a data::base64 octet stream
+/// Attempt to parse a URI as a data: URI (such as a base64
+/// application/octet-stream)
 ///
 /// If it is not such a URI then return Ok(None); if it is, then parse
 /// it and return Ok(Some(Vec u8))) or Err()
 pub fn try_buf_parse_base64(uri: &str, byte_length: usize) -> Result<Option<Vec<u8>>> {
-    let Some(data) = uri.strip_prefix("data:application/octet-stream;base64,") else {
+    let Some((_, bytes)) = parse_data_uri(uri)? else {
         return Ok(None);
     };
-    let bytes = base64_decoder::STANDARD.decode(data)?;
     if bytes.len() < byte_length {
         Err(Error::BufferTooShort)
     } else {
@@ -0,0 +1,122 @@
+//a Imports
+use crate::{Error, Result};
+
+//a ValidationIssue
+//tp ValidationIssue
+/// A single failure found when validating a Gltf
+///
+/// The pointer is a JSON pointer (RFC 6901) to the offending property
+/// of the Gltf Json, such as "/meshes/0/primitives/1/indices"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationIssue {
+    /// JSON pointer to the property that failed validation
+    pub pointer: String,
+    /// Description of the failure
+    pub message: String,
+}
+
+//ip Display for ValidationIssue
+impl std::fmt::Display for ValidationIssue {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(fmt, "{}: {}", self.pointer, self.message)
+    }
+}
+
+//a ValidationReport
+//tp ValidationReport
+/// The result of validating a Gltf - every failure found, rather
+/// than just the first
+///
+/// The report also has warnings, for valid Gltf that is not used
+/// (such as unsupported attributes); these are not failures
+#[derive(Debug, Default, Clone)]
+pub struct ValidationReport {
+    issues: Vec<ValidationIssue>,
+    warnings: Vec<ValidationIssue>,
+}
+
+//ip ValidationReport
+impl ValidationReport {
+    //mp add
+    /// Add an issue at a JSON pointer to the report
+    pub fn add<P: Into<String>, M: Into<String>>(&mut self, pointer: P, message: M) {
+        self.issues.push(ValidationIssue {
+            pointer: pointer.into(),
+            message: message.into(),
+        });
+    }
+
+    //mp add_warning
+    /// Add a warning at a JSON pointer to the report
+    pub fn add_warning<P: Into<String>, M: Into<String>>(&mut self, pointer: P, message: M) {
+        self.warnings.push(ValidationIssue {
+            pointer: pointer.into(),
+            message: message.into(),
+        });
+    }
+
+    //mp add_result
+    /// Add an issue at a JSON pointer to the report if the result of
+    /// a validation method is an error
+    pub fn add_result<P: Into<String>>(&mut self, pointer: P, result: Result<()>) {
+        match result {
+            Ok(()) => (),
+            Err(Error::BadJson(message)) => self.add(pointer, message),
+            Err(e) => self.add(pointer, e.to_string()),
+        }
+    }
+
+    //ap is_empty
+    /// Return true if no issues were found
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    //ap issues
+    /// Get the issues found, in the order they were found
+    pub fn issues(&self) -> &[ValidationIssue] {
+        &self.issues
+    }
+
+    //ap warnings
+    /// Get the warnings found, in the order they were found
+    pub fn warnings(&self) -> &[ValidationIssue] {
+        &self.warnings
+    }
+
+    //ap has_issue_at
+    /// Return true if an issue was found at the JSON pointer
+    pub fn has_issue_at(&self, pointer: &str) -> bool {
+        self.issues.iter().any(|i| i.pointer == pointer)
+    }
+
+    //cp into_result
+    /// Convert the report into an Ok if it has no issues, or an
+    /// [Error::Validation] otherwise; warnings are not issues
+    pub fn into_result(self) -> Result<()> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Validation(self))
+        }
+    }
+}
+
+//ip Display for ValidationReport
+impl std::fmt::Display for ValidationReport {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for (i, issue) in self.issues.iter().enumerate() {
+            if i > 0 {
+                writeln!(fmt)?;
+            }
+            write!(fmt, "{issue}")?;
+        }
+        for (i, warning) in self.warnings.iter().enumerate() {
+            if i > 0 || !self.issues.is_empty() {
+                writeln!(fmt)?;
+            }
+            write!(fmt, "{}: warning: {}", warning.pointer, warning.message)?;
+        }
+        Ok(())
+    }
+}
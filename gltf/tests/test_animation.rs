@@ -0,0 +1,73 @@
+use mod3d_gltf::{Error, Gltf, GltfBuffer};
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn animation_clip() -> Result<(), Error> {
+    let mut data = vec![];
+    for f in [0.0_f32, 1.0, 0., 0., 0., 2., 4., 6.] {
+        data.extend_from_slice(&f.to_le_bytes());
+    }
+    let buffer = GltfBuffer::of_base64(&data);
+    let json = format!(
+        r##"
+{{
+    "asset" : {{ "version" : "2.0" }},
+    "nodes" : [
+        {{ "name" : "Body", "mesh" : 0, "skin" : 0 }},
+        {{ "name" : "Root", "children" : [ 2 ] }},
+        {{ "name" : "Arm" }},
+        {{ "name" : "Other" }}
+    ],
+    "meshes" : [ {{ "primitives" : [] }} ],
+    "skins" : [ {{ "joints" : [ 1, 2 ] }} ],
+    "buffers" : [ {{ "uri" : "{}", "byteLength" : 32 }} ],
+    "bufferViews" : [ {{ "buffer" : 0, "byteLength" : 32 }} ],
+    "accessors" : [
+        {{ "bufferView" : 0, "componentType" : 5126, "count" : 2, "type" : "SCALAR" }},
+        {{ "bufferView" : 0, "byteOffset" : 8, "componentType" : 5126, "count" : 2, "type" : "VEC3" }}
+    ],
+    "animations" : [
+        {{
+            "name" : "Wave",
+            "channels" : [
+                {{ "sampler" : 0, "target" : {{ "node" : 2, "path" : "translation" }} }},
+                {{ "sampler" : 0, "target" : {{ "node" : 3, "path" : "translation" }} }}
+            ],
+            "samplers" : [ {{ "input" : 0, "output" : 1 }} ]
+        }},
+        {{
+            "name" : "Unused",
+            "channels" : [ {{ "sampler" : 0, "target" : {{ "node" : 3, "path" : "scale" }} }} ],
+            "samplers" : [ {{ "input" : 0, "interpolation" : "STEP", "output" : 1 }} ]
+        }}
+    ]
+}}
+"##,
+        buffer.uri()
+    );
+    let json_value: serde_json::Value = serde_json::from_str(&json)?;
+    let mut gltf = Gltf::of_json_value(json_value)?;
+    assert_eq!(gltf.animations().len(), 2);
+    assert_eq!(
+        gltf.animations()[1].samplers()[0].interpolation(),
+        mod3d_base::Interpolation::Step
+    );
+
+    let mut od = mod3d_gltf::ObjectData::new(&gltf);
+    od.add_object(&gltf, 0.into());
+    od.derive_uses(&gltf);
+    let buffers = od.gen_byte_buffers(&mut gltf, &mod3d_gltf::buf_parse_fail, None)?;
+    let clips = od.gen_animations(&gltf, &|i| &buffers[i], &|n| od.bones_of_node(n))?;
+
+    assert_eq!(clips.len(), 1, "Only 'Wave' targets the object's joints");
+    let clip = &clips[0];
+    assert_eq!(clip.name(), "Wave");
+    assert_eq!(clip.duration(), 1.0);
+    assert_eq!(clip.channels().len(), 1, "Channel for 'Other' is dropped");
+    assert_eq!(clip.channels()[0].target(), 1);
+
+    let mut transformations = [mod3d_base::Transformation::new(); 2];
+    clip.apply_to_transformations(0.5, &mut transformations);
+    assert_eq!(transformations[1].translation(), [1., 2., 3.]);
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use mod3d_base::VertexAttr;
+use mod3d_gltf::{Error, Gltf};
+
+//fi attributes_json
+/// A mesh with a primitive with the given attributes, using accessors
+/// (without buffer views) of positions, texture coordinates, colors,
+/// joints and weights
+#[cfg(feature = "serde_json")]
+fn attributes_json(attributes: &str) -> String {
+    format!(
+        r##"
+{{
+    "asset" : {{ "version" : "2.0" }},
+    "meshes" : [ {{ "primitives" : [ {{ "attributes" : {{ {attributes} }} }} ] }} ],
+    "accessors" : [
+        {{ "componentType" : 5126, "count" : 3, "type" : "VEC3" }},
+        {{ "componentType" : 5126, "count" : 3, "type" : "VEC2" }},
+        {{ "componentType" : 5126, "count" : 3, "type" : "VEC4" }},
+        {{ "componentType" : 5121, "count" : 3, "type" : "VEC4" }}
+    ]
+}}
+"##
+    )
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn attribute_sets() -> Result<(), Error> {
+    let json = attributes_json(
+        r#""POSITION" : 0, "TEXCOORD_2" : 1, "TEXCOORD_3" : 1, "COLOR_1" : 2,
+           "JOINTS_0" : 3, "JOINTS_1" : 3, "WEIGHTS_0" : 2, "WEIGHTS_1" : 2"#,
+    );
+    let json_value: serde_json::Value = serde_json::from_str(&json)?;
+    let gltf = Gltf::of_json_value(json_value)?;
+    let mut attrs: Vec<VertexAttr> = gltf.meshes()[0].primitives()[0]
+        .attributes()
+        .iter()
+        .map(|(attr, _)| *attr)
+        .collect();
+    attrs.sort();
+    assert_eq!(
+        attrs,
+        [
+            VertexAttr::Position,
+            VertexAttr::Joints,
+            VertexAttr::Weights,
+            VertexAttr::TexCoords2,
+            VertexAttr::TexCoords3,
+            VertexAttr::Color1,
+            VertexAttr::Joints1,
+            VertexAttr::Weights1,
+        ]
+    );
+
+    let json_value = serde_json::to_value(&gltf)?;
+    let attributes = &json_value["meshes"][0]["primitives"][0]["attributes"];
+    assert_eq!(attributes["TEXCOORD_3"], 1);
+    assert_eq!(attributes["COLOR_1"], 2);
+    assert_eq!(attributes["JOINTS_1"], 3);
+    assert_eq!(attributes["WEIGHTS_1"], 2);
+
+    // Valid attributes without a VertexAttr (further texture
+    // coordinate sets, and application-specific attributes) are
+    // ignored, with warnings in the validation report, and are kept
+    // when serialized
+    let json = attributes_json(r#""POSITION" : 0, "TEXCOORD_4" : 1, "_TEMPERATURE" : 1"#);
+    let json_value: serde_json::Value = serde_json::from_str(&json)?;
+    let gltf = Gltf::of_json_value(json_value)?;
+    let primitive = &gltf.meshes()[0].primitives()[0];
+    let attrs: Vec<VertexAttr> = primitive
+        .attributes()
+        .iter()
+        .map(|(attr, _)| *attr)
+        .collect();
+    assert_eq!(attrs, [VertexAttr::Position]);
+    let ignored: Vec<&str> = primitive
+        .ignored_attributes()
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect();
+    assert_eq!(ignored, ["TEXCOORD_4", "_TEMPERATURE"]);
+    let report = gltf.validation_report();
+    assert!(report.is_empty());
+    let warnings: Vec<&str> = report
+        .warnings()
+        .iter()
+        .map(|w| w.pointer.as_str())
+        .collect();
+    assert_eq!(
+        warnings,
+        [
+            "/meshes/0/primitives/0/attributes/TEXCOORD_4",
+            "/meshes/0/primitives/0/attributes/_TEMPERATURE"
+        ]
+    );
+    let json_value = serde_json::to_value(&gltf)?;
+    let attributes = &json_value["meshes"][0]["primitives"][0]["attributes"];
+    assert_eq!(attributes["TEXCOORD_4"], 1);
+    assert_eq!(attributes["_TEMPERATURE"], 1);
+
+    let json = attributes_json(r#""POSITION" : 0, "JOINTS_1" : 2"#);
+    let json_value: serde_json::Value = serde_json::from_str(&json)?;
+    assert!(
+        Gltf::of_json_value(json_value).is_err(),
+        "Joints must be unsigned integers"
+    );
+    Ok(())
+}
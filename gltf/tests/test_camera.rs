@@ -0,0 +1,65 @@
+use mod3d_gltf::{Error, Gltf};
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn scene_camera() -> Result<(), Error> {
+    const JSON: &str = r##"
+{
+    "asset" : { "version" : "2.0" },
+    "scene" : 0,
+    "scenes" : [ { "nodes" : [ 0 ] } ],
+    "nodes" : [
+        { "name" : "Rig", "translation" : [ 0.0, 0.0, 10.0 ], "scale" : [ 2.0, 2.0, 2.0 ], "children" : [ 1 ] },
+        { "name" : "Eye", "camera" : 1, "translation" : [ 0.0, 1.0, 0.0 ] }
+    ],
+    "cameras" : [
+        { "type" : "orthographic", "orthographic" : { "xmag" : 1.0, "ymag" : 1.0, "znear" : 0.01, "zfar" : 100.0 } },
+        { "name" : "Main", "type" : "perspective", "perspective" : { "yfov" : 0.8, "znear" : 0.1 } }
+    ]
+}
+"##;
+    let json_value: serde_json::Value = serde_json::from_str(JSON)?;
+    let gltf = Gltf::of_json_value(json_value)?;
+
+    assert_eq!(gltf.cameras().len(), 2);
+    let nodes = gltf.scene_camera_nodes(gltf.scene().unwrap());
+    assert_eq!(nodes, vec![1.into()]);
+
+    let camera = gltf.camera_of_node(nodes[0]).unwrap();
+    assert_eq!(camera.name(), "Main");
+    assert!(matches!(
+        camera.projection(),
+        mod3d_base::Projection::Perspective { zfar: None, .. }
+    ));
+    // The camera is at (0,2,10) in the world; the scale of its parent
+    // does not apply to the view
+    let view = camera.view_matrix();
+    assert_eq!(&view[12..16], &[0., -2., -10., 1.]);
+
+    // A point straight ahead of the camera is at the center of clip space
+    let m = camera.view_projection_matrix(1.);
+    let p = [0., 2., 9., 1.];
+    let clip: Vec<f32> = (0..4)
+        .map(|i| (0..4).map(|j| m[i + 4 * j] * p[j]).sum())
+        .collect();
+    assert!(
+        clip[3] > 0.,
+        "Point must be in front of the camera {clip:?}"
+    );
+    assert!(clip[0].abs() < 1E-5 && clip[1].abs() < 1E-5, "{clip:?}");
+    Ok(())
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn bad_camera() -> Result<(), Error> {
+    const JSON: &str = r##"
+{
+    "asset" : { "version" : "2.0" },
+    "cameras" : [ { "type" : "perspective" } ]
+}
+"##;
+    let json_value: serde_json::Value = serde_json::from_str(JSON)?;
+    assert!(Gltf::of_json_value(json_value).is_err());
+    Ok(())
+}
@@ -0,0 +1,298 @@
+use mod3d_base::example_client::Renderable;
+use mod3d_base::{BaseMaterial, Object, VertexAttr};
+use mod3d_gltf::{AccessorIndex, Error, Gltf, GltfAsset, GltfBufferUri, GltfExporter};
+
+//fi position_accessor
+/// Get the position accessor of the first primitive of the first mesh
+fn position_accessor(gltf: &Gltf) -> AccessorIndex {
+    let primitive = &gltf.meshes()[0].primitives()[0];
+    let (_, position) = primitive
+        .attributes()
+        .iter()
+        .find(|(attr, _)| *attr == VertexAttr::Position)
+        .unwrap();
+    *position
+}
+
+//fi triangle_positions
+/// Read the positions of the first primitive of the first mesh
+fn triangle_positions(gltf: &Gltf, data: &[u8]) -> Result<Vec<f32>, Error> {
+    let accessor = &gltf[position_accessor(gltf)];
+    accessor.read_floats(&gltf[accessor.buffer_view().unwrap()], data)
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn export_triangle() -> Result<(), Error> {
+    let mut triangle = mod3d_base::ExampleVertices::new();
+    mod3d_base::example_objects::triangle::new::<Renderable>(&mut triangle, 0.5);
+    let material = BaseMaterial::of_rgba(0xff0000ff);
+    let mut obj: Object<BaseMaterial, Renderable> = Object::new();
+    let v_id = obj.add_vertices(triangle.borrow_vertices(0.into()));
+    let m_id = obj.add_material(&material);
+    let mesh = mod3d_base::example_objects::triangle::mesh(v_id, m_id);
+    let mut transformation = mod3d_base::Transformation::new();
+    transformation.set_translation([1., 2., 3.]);
+    obj.add_component(None, Some(transformation), mesh);
+
+    let mut exporter = GltfExporter::new(GltfAsset::new("".into()));
+    let scene = exporter.add_object(&obj, &|_| None)?;
+    let mut json = vec![];
+    let data = exporter.write_gltf(&mut json, GltfBufferUri::Base64)?;
+    assert!(data.len().is_multiple_of(4));
+
+    let json_value: serde_json::Value = serde_json::from_slice(&json)?;
+    let mut gltf = Gltf::of_json_value(json_value)?;
+    assert_eq!(gltf.scene(), Some(scene));
+    assert_eq!(gltf.nodes().len(), 1);
+    assert_eq!(
+        gltf.nodes()[0].local_transformation().translation(),
+        [1., 2., 3.]
+    );
+    assert_eq!(gltf.materials().len(), 1);
+    let pbr = gltf.materials()[0]
+        .pbr_metallic_roughness()
+        .as_ref()
+        .unwrap();
+    assert_eq!(pbr.base_color_factor, Some(vec![1., 0., 0., 1.]));
+
+    let primitive = &gltf.meshes()[0].primitives()[0];
+    assert_eq!(primitive.attributes().len(), 2);
+    let indices = &gltf[primitive.indices().unwrap()];
+    assert_eq!(indices.count(), 3);
+    assert_eq!(
+        indices.component_type(),
+        mod3d_base::BufferElementType::UInt8
+    );
+
+    let mut od = mod3d_gltf::ObjectData::new(&gltf);
+    od.add_object(&gltf, 0.into());
+    od.derive_uses(&gltf);
+    let buffers = od.gen_byte_buffers(&mut gltf, &mod3d_gltf::buf_parse_fail, None)?;
+    assert_eq!(buffers[0], data);
+
+    let positions = triangle_positions(&gltf, &data)?;
+    assert_eq!(positions, vec![-0.5, -0.5, 0., 0.5, -0.5, 0., 0., 0.5, 0.]);
+    assert_eq!(
+        gltf[position_accessor(&gltf)].bounds(),
+        Some(([-0.5, -0.5, 0.].as_slice(), [0.5, 0.5, 0.].as_slice()))
+    );
+
+    // The accessor bounds become the bounds of the vertices
+    let buffer_data = od.gen_buffer_data::<_, _, Renderable>(&|x| &buffers[x]);
+    let buffer_descriptors = od.gen_descriptors(&gltf, &|x| &buffer_data[x]);
+    let (index_accessors, data_accessors) =
+        od.gen_accessors(&gltf, &|x| &buffer_data[x], &|x| &buffer_descriptors[x]);
+    let vertices = od.gen_vertices(&gltf, &|x| &index_accessors[x], &|x| &data_accessors[x]);
+    let bounds = mod3d_base::Bounds::of_box([-0.5, -0.5, 0.], [0.5, 0.5, 0.]);
+    assert_eq!(vertices[0].bounds(), Some(bounds));
+    assert_eq!(vertices[0].calc_bounds(0, 3), Some(bounds));
+    Ok(())
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn export_glb() -> Result<(), Error> {
+    let mut triangle = mod3d_base::ExampleVertices::new();
+    mod3d_base::example_objects::triangle::new::<Renderable>(&mut triangle, 1.0);
+    let material = BaseMaterial::of_rgba(0xff00ff00);
+    let mut obj: Object<BaseMaterial, Renderable> = Object::new();
+    let v_id = obj.add_vertices(triangle.borrow_vertices(0.into()));
+    let m_id = obj.add_material(&material);
+    let parent = obj.add_component(
+        None,
+        None,
+        mod3d_base::example_objects::triangle::mesh(v_id, m_id),
+    );
+    obj.add_component(
+        Some(parent),
+        None,
+        mod3d_base::example_objects::triangle::mesh(v_id, m_id),
+    );
+
+    let mut exporter = GltfExporter::new(GltfAsset::new("".into()));
+    exporter.add_object(&obj, &|_| None)?;
+    let mut glb = vec![];
+    exporter.write_glb(&mut glb)?;
+    assert!(glb.len().is_multiple_of(4));
+    assert_eq!(
+        u32::from_le_bytes([glb[8], glb[9], glb[10], glb[11]]) as usize,
+        glb.len()
+    );
+
+    let read_bin = |file: &mut std::io::Take<&mut std::io::Cursor<&[u8]>>, byte_length: usize| {
+        use std::io::Read;
+        let mut buffer = vec![0; byte_length];
+        file.read_exact(&mut buffer)?;
+        Ok(Some(buffer))
+    };
+    // The Json chunk starts after the 12-byte header and 8-byte chunk
+    // header; the buffer of the binary chunk must not have a URI
+    let json_length = u32::from_le_bytes([glb[12], glb[13], glb[14], glb[15]]) as usize;
+    let json_value: serde_json::Value = serde_json::from_slice(&glb[20..20 + json_length])?;
+    assert!(json_value["buffers"][0].get("uri").is_none());
+
+    let (gltf, data) = mod3d_gltf::glb_load(&mut std::io::Cursor::new(&glb[..]), &read_bin, 65536)?;
+    let data = data.unwrap();
+    assert_eq!(gltf.buffers()[0].uri(), "");
+    assert_eq!(gltf.buffers()[0].byte_length(), data.len());
+    assert_eq!(gltf.nodes().len(), 2);
+    assert_eq!(gltf.scenes()[0].nodes, vec![0.into()]);
+    assert_eq!(gltf.nodes()[0].iter_children().count(), 1);
+    assert_eq!(gltf.meshes().len(), 2);
+    assert_eq!(
+        gltf.buffer_views().len(),
+        3,
+        "Index, position and normal views shared by both meshes"
+    );
+
+    let positions = triangle_positions(&gltf, &data)?;
+    assert_eq!(positions, vec![-1., -1., 0., 1., -1., 0., 0., 1., 0.]);
+    Ok(())
+}
+
+//fi assert_no_nulls
+/// Assert that no property of some Json is null, as Gltf does not
+/// permit null values
+#[cfg(feature = "serde_json")]
+fn assert_no_nulls(json_value: &serde_json::Value, path: &str) {
+    match json_value {
+        serde_json::Value::Null => panic!("{path} is null"),
+        serde_json::Value::Array(a) => {
+            for (i, v) in a.iter().enumerate() {
+                assert_no_nulls(v, &format!("{path}/{i}"));
+            }
+        }
+        serde_json::Value::Object(o) => {
+            for (k, v) in o {
+                assert_no_nulls(v, &format!("{path}/{k}"));
+            }
+        }
+        _ => (),
+    }
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn export_textured_material() -> Result<(), Error> {
+    use mod3d_base::{MaterialAspect, PbrMaterial};
+    let mut triangle = mod3d_base::ExampleVertices::new();
+    mod3d_base::example_objects::triangle::new::<Renderable>(&mut triangle, 0.5);
+    let texture_data = [0_u8; 4];
+    let texture = mod3d_base::Texture::new(
+        &texture_data,
+        (1, 1, 0),
+        mod3d_base::BufferElementType::new_int(false, 8),
+        4,
+    );
+    let mut obj: Object<PbrMaterial, Renderable> = Object::new();
+    let t_id = obj.add_texture(&texture);
+    let mut material = PbrMaterial::of_rgba(0xff0000ff);
+    material.set_texture(MaterialAspect::Color, t_id);
+    material.set_texture(MaterialAspect::Normal, t_id);
+    material.set_texture(MaterialAspect::Occlusion, t_id);
+    material.set_normal_scale(0.5);
+    material.set_occlusion_strength(0.25);
+    material.set_emissive_strength(2.0);
+    let v_id = obj.add_vertices(triangle.borrow_vertices(0.into()));
+    let m_id = obj.add_material(&material);
+    obj.add_component(
+        None,
+        None,
+        mod3d_base::example_objects::triangle::mesh(v_id, m_id),
+    );
+
+    let mut exporter = GltfExporter::new(GltfAsset::new("".into()));
+    exporter.add_object(&obj, &|_| Some(("image/png".into(), vec![0; 8])))?;
+    let mut json = vec![];
+    exporter.write_gltf(&mut json, GltfBufferUri::External("model.bin".into()))?;
+    let json_value: serde_json::Value = serde_json::from_slice(&json)?;
+    assert_no_nulls(&json_value, "");
+
+    // Only normal textures have a scale, and only occlusion textures
+    // have a strength
+    let material = &json_value["materials"][0];
+    assert_eq!(material["normalTexture"]["scale"], 0.5);
+    assert!(material["normalTexture"].get("strength").is_none());
+    assert_eq!(material["occlusionTexture"]["strength"], 0.25);
+    assert!(material["occlusionTexture"].get("scale").is_none());
+    let base_color = &material["pbrMetallicRoughness"]["baseColorTexture"];
+    assert!(base_color.get("scale").is_none() && base_color.get("strength").is_none());
+
+    assert_eq!(
+        json_value["extensionsUsed"],
+        serde_json::json!(["KHR_materials_emissive_strength"])
+    );
+    let gltf = Gltf::of_json_value(json_value)?;
+    assert_eq!(gltf.extensions_used(), ["KHR_materials_emissive_strength"]);
+    assert_eq!(
+        gltf.materials()[0]
+            .normal_texture()
+            .as_ref()
+            .unwrap()
+            .scale(),
+        0.5
+    );
+    Ok(())
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn export_byte_attribute() -> Result<(), Error> {
+    use mod3d_base::{BufferElementType, VertexDesc};
+    let mut eg = mod3d_base::ExampleVertices::<Renderable>::new();
+    let position_data = [-0.5_f32, -0.5, 0., 0.5, -0.5, 0., 0., 0.5, 0.];
+    let color_data = [255_u8, 0, 0, 0, 255, 0, 0, 0, 255];
+    let index_data = [0_u8, 1, 2];
+    let data_positions = eg.push_byte_buffer(Box::new(position_data));
+    let data_colors = eg.push_byte_buffer(Box::new(color_data));
+    let data_indices = eg.push_byte_buffer(Box::new(index_data));
+    let indices = eg.push_index_accessor(data_indices, 3, BufferElementType::UInt8, 0);
+    let position_desc = eg.push_descriptor(data_positions, 0, 9 * 4, 12);
+    let positions = eg.push_data_accessor(
+        position_desc,
+        VertexDesc::vec(VertexAttr::Position, BufferElementType::Float32, 3, 0),
+    );
+    // Tightly packed 3-byte colors, which Gltf requires to be padded
+    // to a 4-byte stride
+    let color_desc = eg.push_descriptor(data_colors, 0, 9, 3);
+    let mut color_vertex_desc = VertexDesc::vec(VertexAttr::Color, BufferElementType::UInt8, 3, 0);
+    color_vertex_desc.set_normalized(true);
+    let colors = eg.push_data_accessor(color_desc, color_vertex_desc);
+    eg.push_vertices(Some(indices), positions, &[colors]);
+
+    let material = BaseMaterial::of_rgba(0xff0000ff);
+    let mut obj: Object<BaseMaterial, Renderable> = Object::new();
+    let v_id = obj.add_vertices(eg.borrow_vertices(0.into()));
+    let m_id = obj.add_material(&material);
+    obj.add_component(
+        None,
+        None,
+        mod3d_base::example_objects::triangle::mesh(v_id, m_id),
+    );
+
+    let mut exporter = GltfExporter::new(GltfAsset::new("".into()));
+    exporter.add_object(&obj, &|_| None)?;
+    let mut json = vec![];
+    let data = exporter.write_gltf(&mut json, GltfBufferUri::Base64)?;
+    let json_value: serde_json::Value = serde_json::from_slice(&json)?;
+    let gltf = Gltf::of_json_value(json_value)?;
+
+    let primitive = &gltf.meshes()[0].primitives()[0];
+    let (_, color) = primitive
+        .attributes()
+        .iter()
+        .find(|(attr, _)| *attr == VertexAttr::Color)
+        .unwrap();
+    let accessor = &gltf[*color];
+    let view = &gltf[accessor.buffer_view().unwrap()];
+    assert_eq!(view.byte_offset() % 4, 0);
+    assert_eq!(accessor.byte_offset() % 4, 0);
+    assert_eq!(view.byte_stride(0), 4);
+    assert_eq!(
+        accessor.read_floats(view, &data)?,
+        vec![1., 0., 0., 0., 1., 0., 0., 0., 1.]
+    );
+    assert_eq!(triangle_positions(&gltf, &data)?, position_data);
+    Ok(())
+}
@@ -0,0 +1,207 @@
+use std::io::Read;
+
+use mod3d_gltf::{Error, Gltf, GltfAsset, GltfBuffer};
+
+//fi read_u32
+fn read_u32(data: &[u8], offset: usize) -> usize {
+    u32::from_le_bytes([
+        data[offset],
+        data[offset + 1],
+        data[offset + 2],
+        data[offset + 3],
+    ]) as usize
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn glb_save_padding() -> Result<(), Error> {
+    let bin = [1_u8, 2, 3, 4, 5];
+    let mut gltf = Gltf::default();
+    gltf.set_asset(GltfAsset::new("Padding".into()));
+    let buffer = gltf.add_buffer(GltfBuffer::of_uri("", bin.len()));
+    gltf.add_view(buffer, 0, bin.len(), None);
+
+    let mut glb = vec![];
+    mod3d_gltf::glb_save(&mut glb, &gltf, Some(&bin))?;
+    assert_eq!(&glb[0..8], b"glTF\x02\x00\x00\x00");
+    assert_eq!(read_u32(&glb, 8), glb.len());
+
+    let json_length = read_u32(&glb, 12);
+    assert!(json_length.is_multiple_of(4));
+    assert_eq!(&glb[16..20], b"JSON");
+    let json = &glb[20..20 + json_length];
+    let json_value: serde_json::Value = serde_json::from_slice(json)?;
+    assert_eq!(json_value["asset"]["copyright"], "Padding");
+    assert!(
+        json_value["buffers"][0].get("uri").is_none(),
+        "The binary chunk buffer has no URI"
+    );
+
+    let bin_start = 20 + json_length;
+    assert_eq!(read_u32(&glb, bin_start), 8, "Binary padded to 8 bytes");
+    assert_eq!(&glb[bin_start + 4..bin_start + 8], b"BIN\x00");
+    assert_eq!(&glb[bin_start + 8..], &[1, 2, 3, 4, 5, 0, 0, 0]);
+
+    let (gltf, data) = load(&mut std::io::Cursor::new(&glb[..]))?;
+    assert_eq!(gltf.buffer_views().len(), 1);
+    assert_eq!(&data.unwrap()[0..5], &bin);
+    Ok(())
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn glb_save_json_only() -> Result<(), Error> {
+    let mut gltf = Gltf::default();
+    gltf.set_asset(GltfAsset::new("No binary".into()));
+
+    let mut glb = vec![];
+    mod3d_gltf::glb_save(&mut glb, &gltf, None)?;
+    let json_length = read_u32(&glb, 12);
+    assert_eq!(glb.len(), 20 + json_length, "No binary chunk");
+    assert_eq!(read_u32(&glb, 8), glb.len());
+    let json = std::str::from_utf8(&glb[20..])?;
+    assert!(json.trim_end().ends_with('}'));
+    Ok(())
+}
+
+//fi glb_of_chunks
+/// Build a GLB file from chunks, with a header byte length adjusted by
+/// 'extra'
+fn glb_of_chunks(chunks: &[(&[u8; 4], &[u8])], extra: isize) -> Vec<u8> {
+    let mut glb = vec![];
+    for (chunk_type, data) in chunks {
+        glb.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        glb.extend_from_slice(*chunk_type);
+        glb.extend_from_slice(data);
+    }
+    let byte_length = (glb.len() as isize + 12 + extra) as u32;
+    let mut hdr = b"glTF\x02\x00\x00\x00".to_vec();
+    hdr.extend_from_slice(&byte_length.to_le_bytes());
+    hdr.extend(glb);
+    hdr
+}
+
+//tp TrickleReader
+/// A reader that returns at most one byte per read, as a pipe might
+struct TrickleReader<'a>(&'a [u8]);
+impl std::io::Read for TrickleReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.0.is_empty() || buf.is_empty() {
+            return Ok(0);
+        }
+        buf[0] = self.0[0];
+        self.0 = &self.0[1..];
+        Ok(1)
+    }
+}
+
+//fi load
+fn load<F: std::io::Read>(file: &mut F) -> Result<(Gltf, Option<Vec<u8>>), Error> {
+    let read_bin = |file: &mut std::io::Take<&mut F>, byte_length: usize| {
+        let mut buffer = vec![0; byte_length];
+        file.read_exact(&mut buffer)?;
+        Ok(Some(buffer))
+    };
+    mod3d_gltf::glb_load(file, &read_bin, 65536)
+}
+
+const JSON: &[u8; 28] = br#"{"asset":{"version":"2.0"}} "#;
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn glb_load_chunks() -> Result<(), Error> {
+    // An extension chunk with no binary chunk
+    let glb = glb_of_chunks(&[(b"JSON", JSON), (b"EXT\x00", &[1, 2, 3, 4])], 0);
+    let (_, data) = load(&mut std::io::Cursor::new(&glb[..]))?;
+    assert!(data.is_none());
+
+    // Binary chunk followed by an extension chunk, read in single bytes
+    let glb = glb_of_chunks(
+        &[
+            (b"JSON", JSON),
+            (b"BIN\x00", &[5, 6, 7, 8]),
+            (b"EXT\x00", &[1, 2, 3, 4]),
+        ],
+        0,
+    );
+    let (_, data) = load(&mut TrickleReader(&glb))?;
+    assert_eq!(data, Some(vec![5, 6, 7, 8]));
+
+    // A buffer reader that reads only part of the binary chunk, and
+    // one that tries to read beyond it
+    let glb = glb_of_chunks(
+        &[
+            (b"JSON", JSON),
+            (b"BIN\x00", &[5, 6, 7, 8]),
+            (b"EXT\x00", &[1, 2, 3, 4]),
+        ],
+        0,
+    );
+    let read_part = |file: &mut std::io::Take<&mut std::io::Cursor<&[u8]>>, _| {
+        let mut buffer = vec![0; 2];
+        file.read_exact(&mut buffer)?;
+        Ok(Some(buffer))
+    };
+    let (_, data) = mod3d_gltf::glb_load(&mut std::io::Cursor::new(&glb[..]), &read_part, 65536)?;
+    assert_eq!(data, Some(vec![5, 6]));
+    let read_all = |file: &mut std::io::Take<&mut std::io::Cursor<&[u8]>>, _| {
+        let mut buffer = vec![];
+        file.read_to_end(&mut buffer)?;
+        Ok(Some(buffer))
+    };
+    let (_, data) = mod3d_gltf::glb_load(&mut std::io::Cursor::new(&glb[..]), &read_all, 65536)?;
+    assert_eq!(
+        data,
+        Some(vec![5, 6, 7, 8]),
+        "Reads are limited to the chunk"
+    );
+    Ok(())
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn glb_load_errors() {
+    let glb = glb_of_chunks(&[(b"JSON", JSON)], 4);
+    assert!(matches!(
+        load(&mut std::io::Cursor::new(&glb[..])),
+        Err(Error::GlbLength)
+    ));
+
+    let glb = glb_of_chunks(&[(b"JSON", JSON)], -4);
+    assert!(matches!(
+        load(&mut std::io::Cursor::new(&glb[..])),
+        Err(Error::GlbLength)
+    ));
+
+    let glb = glb_of_chunks(&[(b"BIN\x00", &[0; 4]), (b"JSON", JSON)], 0);
+    assert!(matches!(
+        load(&mut std::io::Cursor::new(&glb[..])),
+        Err(Error::GlbJsonHdr)
+    ));
+
+    let glb = glb_of_chunks(
+        &[
+            (b"JSON", JSON),
+            (b"EXT\x00", &[0; 4]),
+            (b"BIN\x00", &[0; 4]),
+        ],
+        0,
+    );
+    assert!(matches!(
+        load(&mut std::io::Cursor::new(&glb[..])),
+        Err(Error::GlbChunkOrder)
+    ));
+
+    let glb = glb_of_chunks(&[(b"JSON", JSON), (b"EXT\x00", &[0; 8])], 0);
+    assert!(matches!(
+        load(&mut std::io::Cursor::new(&glb[..glb.len() - 4])),
+        Err(Error::GlbChunkIo(_))
+    ));
+
+    let glb = glb_of_chunks(&[(b"JSON", JSON)], 0);
+    let read_bin = |_: &mut std::io::Take<&mut std::io::Cursor<&[u8]>>, _| Ok(Some(()));
+    assert!(matches!(
+        mod3d_gltf::glb_load(&mut std::io::Cursor::new(&glb[..]), &read_bin, 16),
+        Err(Error::GlbJsonTooLong)
+    ));
+}
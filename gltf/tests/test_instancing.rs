@@ -0,0 +1,88 @@
+use mod3d_base::example_client::Renderable;
+use mod3d_gltf::{Error, Gltf, GltfBuffer};
+
+//fi instancing_json
+/// A node with an instanced (empty) mesh, with two instances given by
+/// translation and scale accessors
+#[cfg(feature = "serde_json")]
+fn instancing_json(attributes: &str) -> String {
+    let mut data = vec![];
+    for f in [1.0_f32, 0., 0., 2., 0., 0.] {
+        data.extend_from_slice(&f.to_le_bytes());
+    }
+    for f in [1.0_f32, 1., 1., 2., 2., 2.] {
+        data.extend_from_slice(&f.to_le_bytes());
+    }
+    let buffer = GltfBuffer::of_base64(&data);
+    format!(
+        r##"
+{{
+    "asset" : {{ "version" : "2.0" }},
+    "nodes" : [ {{
+        "mesh" : 0,
+        "translation" : [ 0, 0, 5 ],
+        "extensions" : {{ "EXT_mesh_gpu_instancing" : {{ "attributes" : {{ {attributes} }} }} }}
+    }} ],
+    "meshes" : [ {{ "primitives" : [] }} ],
+    "buffers" : [ {{ "uri" : "{}", "byteLength" : 48 }} ],
+    "bufferViews" : [ {{ "buffer" : 0, "byteLength" : 48 }} ],
+    "accessors" : [
+        {{ "bufferView" : 0, "componentType" : 5126, "count" : 2, "type" : "VEC3" }},
+        {{ "bufferView" : 0, "byteOffset" : 24, "componentType" : 5126, "count" : 2, "type" : "VEC3" }},
+        {{ "bufferView" : 0, "componentType" : 5126, "count" : 1, "type" : "VEC3" }}
+    ]
+}}
+"##,
+        buffer.uri()
+    )
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn mesh_gpu_instancing() -> Result<(), Error> {
+    let json = instancing_json(r#""TRANSLATION" : 0, "SCALE" : 1"#);
+    let json_value: serde_json::Value = serde_json::from_str(&json)?;
+    let mut gltf = Gltf::of_json_value(json_value)?;
+    let instancing = gltf.nodes()[0].gpu_instancing().unwrap();
+    assert_eq!(instancing.translation, Some(0.into()));
+    assert_eq!(instancing.rotation, None);
+    assert_eq!(instancing.scale, Some(1.into()));
+
+    let mut od = mod3d_gltf::ObjectData::new(&gltf);
+    od.add_object(&gltf, 0.into());
+    od.derive_uses(&gltf);
+    let buffers = od.gen_byte_buffers(&mut gltf, &mod3d_gltf::buf_parse_fail, None)?;
+    od.gen_instances(&gltf, &|i| &buffers[i])?;
+    let matrices = od.instance_matrices_of_node(0.into()).unwrap().to_vec();
+    assert_eq!(matrices.len(), 2);
+    assert_eq!(matrices[0][12..15], [1., 0., 0.]);
+    assert_eq!(matrices[1][0], 2.);
+    assert_eq!(matrices[1][12..15], [2., 0., 0.]);
+
+    let object = od.gen_object::<mod3d_base::BaseMaterial, Renderable>(&gltf, &[], &[], &[]);
+    let components = object.components.borrow_elements();
+    assert_eq!(components.len(), 1);
+    assert_eq!(components[0].data.instance_matrices, matrices);
+    let transformation = components[0].data.transformation.unwrap();
+    assert_eq!(transformation.mat4()[12..15], [0., 0., 5.]);
+    Ok(())
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn mesh_gpu_instancing_invalid() -> Result<(), Error> {
+    let json = instancing_json(r#""TRANSLATION" : 0, "SCALE" : 2"#);
+    let json_value: serde_json::Value = serde_json::from_str(&json)?;
+    assert!(
+        Gltf::of_json_value(json_value).is_err(),
+        "Instance attributes must have the same count"
+    );
+
+    let json = instancing_json(r#""ROTATION" : 0"#);
+    let json_value: serde_json::Value = serde_json::from_str(&json)?;
+    assert!(
+        Gltf::of_json_value(json_value).is_err(),
+        "Instance rotations must be VEC4"
+    );
+    Ok(())
+}
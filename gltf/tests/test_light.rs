@@ -0,0 +1,91 @@
+use mod3d_gltf::{Error, Gltf};
+
+fn assert_near(a: &[f32], b: &[f32]) {
+    for (a, b) in a.iter().zip(b.iter()) {
+        assert!((a - b).abs() < 1e-5, "{a:?} != {b:?}");
+    }
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn scene_lights() -> Result<(), Error> {
+    const JSON: &str = r##"
+{
+    "asset" : { "version" : "2.0" },
+    "extensionsUsed" : [ "KHR_lights_punctual" ],
+    "extensions" : {
+        "KHR_lights_punctual" : {
+            "lights" : [
+                { "name" : "Sun", "type" : "directional", "color" : [ 1.0, 0.9, 0.8 ], "intensity" : 2.0 },
+                { "name" : "Bulb", "type" : "point", "range" : 5.0 },
+                { "name" : "Torch", "type" : "spot", "spot" : { "outerConeAngle" : 0.5 } }
+            ]
+        }
+    },
+    "scene" : 0,
+    "scenes" : [ { "nodes" : [ 0, 1 ] } ],
+    "nodes" : [
+        { "name" : "SunNode", "rotation" : [ 0.0, 0.7071068, 0.0, 0.7071068 ],
+          "extensions" : { "KHR_lights_punctual" : { "light" : 0 } } },
+        { "name" : "Room", "translation" : [ 1.0, 2.0, 3.0 ], "children" : [ 2 ] },
+        { "name" : "BulbNode", "translation" : [ 0.0, 1.0, 0.0 ],
+          "extensions" : { "KHR_lights_punctual" : { "light" : 1 } } }
+    ]
+}
+"##;
+    let json_value: serde_json::Value = serde_json::from_str(JSON)?;
+    let gltf = Gltf::of_json_value(json_value)?;
+
+    assert_eq!(gltf.lights().len(), 3);
+    assert_eq!(
+        gltf.lights()[2].kind(),
+        mod3d_base::LightKind::Spot {
+            inner_cone_angle: 0.,
+            outer_cone_angle: 0.5
+        }
+    );
+
+    let lights = gltf.scene_lights(gltf.scene().unwrap());
+    assert_eq!(lights.len(), 2);
+    assert_eq!(lights[0].name(), "Sun");
+    assert_eq!(lights[0].intensity(), 2.0);
+    // Rotated by 90 degrees about Y, so -Z becomes -X
+    assert_near(&lights[0].direction(), &[-1., 0., 0.]);
+    assert_eq!(lights[1].name(), "Bulb");
+    assert_eq!(lights[1].range(), Some(5.0));
+    assert_near(&lights[1].position(), &[1., 3., 3.]);
+
+    // Nodes without lights do not serialize a null light
+    let json_value = serde_json::to_value(&gltf)?;
+    assert_eq!(
+        json_value.pointer("/nodes/2/extensions/KHR_lights_punctual/light"),
+        Some(&1.into())
+    );
+    assert!(json_value
+        .pointer("/nodes/1/extensions/KHR_lights_punctual")
+        .is_none());
+
+    // Nor does a file without lights
+    let json_value: serde_json::Value =
+        serde_json::from_str(r#"{ "asset" : { "version" : "2.0" } }"#)?;
+    let gltf = Gltf::of_json_value(json_value)?;
+    let json_value = serde_json::to_value(&gltf)?;
+    assert!(json_value
+        .pointer("/extensions/KHR_lights_punctual")
+        .is_none());
+    Ok(())
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn bad_light() -> Result<(), Error> {
+    const JSON: &str = r##"
+{
+    "asset" : { "version" : "2.0" },
+    "extensions" : { "KHR_lights_punctual" : { "lights" : [ { "type" : "area" } ] } }
+}
+"##;
+    let json_value: serde_json::Value = serde_json::from_str(JSON)?;
+    assert!(Gltf::of_json_value(json_value).is_err());
+    Ok(())
+}
@@ -0,0 +1,393 @@
+use mod3d_base::example_client::Renderable;
+use mod3d_base::{AlphaMode, BufferElementType, Material, MaterialAspect, Texture};
+use mod3d_gltf::{Error, Gltf};
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn material_properties() -> Result<(), Error> {
+    const JSON: &str = r##"
+{
+    "asset" : { "version" : "2.0" },
+    "nodes" : [ { "mesh" : 0 } ],
+    "meshes" : [ { "primitives" : [
+        { "attributes" : { }, "material" : 0 },
+        { "attributes" : { }, "material" : 1 }
+    ] } ],
+    "images" : [ { "uri" : "atlas.png" } ],
+    "textures" : [ { "source" : 0 } ],
+    "materials" : [
+        {
+            "alphaMode" : "MASK",
+            "alphaCutoff" : 0.25,
+            "doubleSided" : true,
+            "normalTexture" : { "index" : 0, "texCoord" : 1, "scale" : 0.5 },
+            "occlusionTexture" : { "index" : 0, "strength" : 0.75 },
+            "pbrMetallicRoughness" : { "baseColorTexture" : { "index" : 0, "texCoord" : 1 } }
+        },
+        {
+            "normalTexture" : { "index" : 0 }
+        }
+    ]
+}
+"##;
+    let json_value: serde_json::Value = serde_json::from_str(JSON)?;
+    let gltf = Gltf::of_json_value(json_value)?;
+    let m = &gltf.materials()[1];
+    assert_eq!(m.alpha_mode(), AlphaMode::Opaque);
+    assert_eq!(m.alpha_cutoff(), 0.5, "Alpha cutoff defaults to 0.5");
+    assert!(!m.double_sided());
+    assert_eq!(m.normal_texture().as_ref().unwrap().scale(), 1.0);
+
+    let mut od = mod3d_gltf::ObjectData::new(&gltf);
+    od.add_object(&gltf, 0.into());
+    od.derive_uses(&gltf);
+    let images = od.gen_images(&gltf, &|_, _| Ok(()))?;
+    let _textures = od.gen_textures(
+        &gltf,
+        |i| &images[i],
+        |_| Texture::<Renderable>::new(&[], (1, 1, 1), BufferElementType::UInt8, 4),
+    );
+    let materials = od.gen_materials(&gltf);
+    assert_eq!(materials.len(), 2);
+
+    let m = &materials[0];
+    let base_data = m.base_data();
+    assert_eq!(base_data.alpha_mode(), AlphaMode::Mask);
+    assert_eq!(base_data.alpha_cutoff(), 0.25);
+    assert!(base_data.double_sided());
+    assert_eq!(m.normal_scale(), 0.5);
+    assert_eq!(m.occlusion_strength(), 0.75);
+    assert_eq!(m.tex_coord(MaterialAspect::Normal), 1);
+    assert_eq!(m.tex_coord(MaterialAspect::Color), 1);
+    assert_eq!(m.tex_coord(MaterialAspect::Occlusion), 0);
+
+    let m = &materials[1];
+    assert_eq!(m.base_data().alpha_mode(), AlphaMode::Opaque);
+    assert_eq!(m.base_data().alpha_cutoff(), 0.5);
+    assert!(!m.base_data().double_sided());
+    assert_eq!(m.normal_scale(), 1.0);
+    assert_eq!(m.occlusion_strength(), 1.0);
+    Ok(())
+}
+
+#[test]
+fn base_data_alpha() {
+    let mut base_data: mod3d_base::MaterialBaseData = 0x80ffffff.into();
+    assert_eq!(base_data.alpha_mode(), AlphaMode::Blend);
+    assert_eq!(base_data.alpha_cutoff(), 0.5);
+    base_data.set_alpha_mode(AlphaMode::Mask);
+    base_data.set_double_sided(true);
+    base_data.set_alpha_cutoff(1.0);
+    assert_eq!(base_data.alpha_mode(), AlphaMode::Mask);
+    assert!(base_data.double_sided());
+    assert!(base_data.alpha_cutoff() > 0.999);
+    base_data.set_double_sided(false);
+    assert_eq!(base_data.alpha_mode(), AlphaMode::Mask);
+    assert!(!base_data.double_sided());
+
+    let base_data = mod3d_base::MaterialBaseData::default();
+    assert_eq!(base_data.alpha_mode(), AlphaMode::Opaque);
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn texture_transform() -> Result<(), Error> {
+    const JSON: &str = r##"
+{
+    "asset" : { "version" : "2.0" },
+    "nodes" : [ { "mesh" : 0 } ],
+    "meshes" : [ { "primitives" : [ { "attributes" : { }, "material" : 0 } ] } ],
+    "images" : [ { "uri" : "atlas.png" } ],
+    "textures" : [ { "source" : 0 } ],
+    "materials" : [ {
+        "emissiveTexture" : { "index" : 0 },
+        "pbrMetallicRoughness" : { "baseColorTexture" : {
+            "index" : 0,
+            "extensions" : { "KHR_texture_transform" : {
+                "offset" : [ 0.5, 0.25 ],
+                "rotation" : 1.5707964,
+                "scale" : [ 0.5, 0.5 ],
+                "texCoord" : 1
+            } }
+        } }
+    } ]
+}
+"##;
+    let json_value: serde_json::Value = serde_json::from_str(JSON)?;
+    let gltf = Gltf::of_json_value(json_value)?;
+    let pbr = gltf.materials()[0]
+        .pbr_metallic_roughness()
+        .as_ref()
+        .unwrap();
+    let ti = pbr.base_color_texture().as_ref().unwrap();
+    assert_eq!(ti.tex_coord(), 0);
+    assert_eq!(ti.transformed_tex_coord(), 1);
+    let tt = ti.texture_transform().unwrap();
+    assert_eq!(tt.offset, [0.5, 0.25]);
+
+    // Rotating by 90 degrees maps (1, 0) to (0, -1) before scaling
+    let m = tt.uv_matrix();
+    let uv = [m[0] + m[6], m[1] + m[7]];
+    assert!((uv[0] - 0.5).abs() < 1E-6 && (uv[1] + 0.25).abs() < 1E-6);
+    let tt2 = mod3d_gltf::GltfTextureTransform::of_uv_matrix(&m);
+    assert!((tt2.rotation - tt.rotation).abs() < 1E-6);
+    assert!((tt2.scale[0] - 0.5).abs() < 1E-6 && (tt2.scale[1] - 0.5).abs() < 1E-6);
+    assert_eq!(tt2.offset, tt.offset);
+
+    let serialized = serde_json::to_string(&gltf)?;
+    assert_eq!(serialized.matches("KHR_texture_transform").count(), 1);
+
+    let mut od = mod3d_gltf::ObjectData::new(&gltf);
+    od.add_object(&gltf, 0.into());
+    od.derive_uses(&gltf);
+    let images = od.gen_images(&gltf, &|_, _| Ok(()))?;
+    let _textures = od.gen_textures(
+        &gltf,
+        |i| &images[i],
+        |_| Texture::<Renderable>::new(&[], (1, 1, 1), BufferElementType::UInt8, 4),
+    );
+    let materials = od.gen_materials(&gltf);
+    let m = &materials[0];
+    assert_eq!(m.tex_coord(MaterialAspect::Color), 1);
+    assert_eq!(m.uv_transform(MaterialAspect::Color), tt.uv_matrix());
+    assert_eq!(
+        m.uv_transform(MaterialAspect::Emission),
+        [1., 0., 0., 0., 1., 0., 0., 0., 1.]
+    );
+    Ok(())
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn material_extensions() -> Result<(), Error> {
+    const JSON: &str = r##"
+{
+    "asset" : { "version" : "2.0" },
+    "nodes" : [ { "mesh" : 0 } ],
+    "meshes" : [ { "primitives" : [
+        { "attributes" : { }, "material" : 0 },
+        { "attributes" : { }, "material" : 1 }
+    ] } ],
+    "images" : [ { "uri" : "atlas.png" } ],
+    "textures" : [ { "source" : 0 }, { "source" : 0 } ],
+    "materials" : [
+        {
+            "extensions" : {
+                "KHR_materials_emissive_strength" : { "emissiveStrength" : 4.0 },
+                "KHR_materials_clearcoat" : {
+                    "clearcoatFactor" : 0.5,
+                    "clearcoatRoughnessFactor" : 0.25,
+                    "clearcoatNormalTexture" : { "index" : 1, "scale" : 2.0 }
+                },
+                "KHR_materials_transmission" : { "transmissionFactor" : 0.75 },
+                "KHR_materials_ior" : { }
+            }
+        },
+        {
+            "extensions" : {
+                "KHR_materials_unlit" : { },
+                "KHR_materials_ior" : { "ior" : 1.33 }
+            }
+        }
+    ]
+}
+"##;
+    let json_value: serde_json::Value = serde_json::from_str(JSON)?;
+    let gltf = Gltf::of_json_value(json_value)?;
+    let m = &gltf.materials()[0];
+    assert_eq!(m.emissive_strength(), 4.0);
+    assert!(!m.unlit());
+    assert_eq!(m.clearcoat().unwrap().clearcoat_factor, 0.5);
+    assert_eq!(m.transmission().unwrap().transmission_factor, 0.75);
+    assert_eq!(m.ior(), 1.5, "IOR defaults to 1.5");
+    let textures: Vec<_> = m.iter_textures().map(|(a, ti)| (a, ti.index())).collect();
+    assert_eq!(textures, vec![(MaterialAspect::ClearcoatNormal, 1.into())]);
+    let m = &gltf.materials()[1];
+    assert!(m.unlit());
+    assert_eq!(m.emissive_strength(), 1.0);
+    assert_eq!(m.ior(), 1.33);
+
+    let serialized = serde_json::to_string(&gltf)?;
+    assert!(serialized.contains(r#""KHR_materials_unlit":{}"#));
+    assert!(serialized.contains(r#""KHR_materials_ior":{"ior":1.33}"#));
+    assert!(!serialized.contains("clearcoatTexture"));
+
+    let mut od = mod3d_gltf::ObjectData::new(&gltf);
+    od.add_object(&gltf, 0.into());
+    od.derive_uses(&gltf);
+    let images = od.gen_images(&gltf, &|_, _| Ok(()))?;
+    let textures = od.gen_textures(
+        &gltf,
+        |i| &images[i],
+        |_| Texture::<Renderable>::new(&[], (1, 1, 1), BufferElementType::UInt8, 4),
+    );
+    assert_eq!(
+        textures.len(),
+        1,
+        "Only the clearcoat normal texture is used"
+    );
+    let materials = od.gen_materials(&gltf);
+    let m = &materials[0];
+    assert_eq!(m.emissive_strength(), 4.0);
+    assert_eq!(m.clearcoat(), (0.5, 0.25));
+    assert_eq!(m.transmission(), 0.75);
+    assert_eq!(m.ior(), 1.5);
+    assert!(m.texture(MaterialAspect::ClearcoatNormal).is_some());
+    assert!(m.texture(MaterialAspect::Normal).is_none());
+    assert!(!m.base_data().unlit());
+    let m = &materials[1];
+    assert!(m.base_data().unlit());
+    assert_eq!(m.ior(), 1.33);
+    Ok(())
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn specular_glossiness() -> Result<(), Error> {
+    const JSON: &str = r##"
+{
+    "asset" : { "version" : "2.0" },
+    "nodes" : [ { "mesh" : 0 } ],
+    "meshes" : [ { "primitives" : [
+        { "attributes" : { }, "material" : 0 },
+        { "attributes" : { }, "material" : 1 }
+    ] } ],
+    "images" : [ { "uri" : "atlas.png" } ],
+    "textures" : [ { "source" : 0 }, { "source" : 0 } ],
+    "materials" : [
+        {
+            "pbrMetallicRoughness" : { "baseColorTexture" : { "index" : 1 } },
+            "extensions" : { "KHR_materials_pbrSpecularGlossiness" : {
+                "diffuseFactor" : [ 0.5, 0.5, 0.5, 1.0 ],
+                "diffuseTexture" : { "index" : 0 },
+                "specularFactor" : [ 0.04, 0.04, 0.04 ],
+                "glossinessFactor" : 0.75,
+                "specularGlossinessTexture" : { "index" : 1 }
+            } }
+        },
+        {
+            "extensions" : { "KHR_materials_pbrSpecularGlossiness" : {
+                "diffuseFactor" : [ 0.0, 0.0, 0.0, 1.0 ],
+                "specularFactor" : [ 1.0, 0.766, 0.336 ]
+            } }
+        }
+    ]
+}
+"##;
+    let json_value: serde_json::Value = serde_json::from_str(JSON)?;
+    let gltf = Gltf::of_json_value(json_value)?;
+
+    let sg = gltf.materials()[0].specular_glossiness().unwrap();
+    let (color, metallic, roughness) = sg.metallic_roughness();
+    assert!(metallic.abs() < 1E-6, "A 4% specular is dielectric");
+    assert!((color[0] - 0.5).abs() < 1E-6 && color[3] == 1.0);
+    assert_eq!(roughness, 0.25);
+    let textures: Vec<_> = gltf.materials()[0]
+        .iter_textures()
+        .map(|(a, ti)| (a, ti.index()))
+        .collect();
+    assert_eq!(
+        textures,
+        vec![(MaterialAspect::Color, 0.into())],
+        "The diffuse texture replaces the base color texture"
+    );
+
+    let sg = gltf.materials()[1].specular_glossiness().unwrap();
+    let (color, metallic, roughness) = sg.metallic_roughness();
+    assert!(metallic > 0.95, "A gold specular is metallic");
+    assert!((color[0] - 1.0).abs() < 0.05 && (color[2] - 0.336).abs() < 0.05);
+    assert_eq!(roughness, 0.);
+
+    let mut od = mod3d_gltf::ObjectData::new(&gltf);
+    od.add_object(&gltf, 0.into());
+    od.derive_uses(&gltf);
+    let images = od.gen_images(&gltf, &|_, _| Ok(()))?;
+    let textures = od.gen_textures(
+        &gltf,
+        |i| &images[i],
+        |_| Texture::<Renderable>::new(&[], (1, 1, 1), BufferElementType::UInt8, 4),
+    );
+    assert_eq!(textures.len(), 1, "Only the diffuse texture is used");
+    let materials = od.gen_materials(&gltf);
+    let base_data = materials[0].base_data();
+    assert_eq!(base_data.rgba_tuple(), (127, 127, 127, 255));
+    let (metallic, roughness) = base_data.metallic_roughness();
+    assert!(metallic < 0.01 && (roughness - 0.25).abs() < 0.01);
+    assert!(materials[0].texture(MaterialAspect::Color).is_some());
+    assert!(materials[0]
+        .texture(MaterialAspect::MetallicRoughness)
+        .is_none());
+    let (metallic, _) = materials[1].base_data().metallic_roughness();
+    assert!(metallic > 0.95);
+    Ok(())
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn primitive_materials() -> Result<(), Error> {
+    // Three positions and three (padded) u8 indices
+    let mut data = vec![];
+    for f in [0.0_f32, 0., 0., 1., 0., 0., 0., 1., 0.] {
+        data.extend_from_slice(&f.to_le_bytes());
+    }
+    data.extend_from_slice(&[0, 1, 2, 0]);
+    let buffer = mod3d_gltf::GltfBuffer::of_base64(&data);
+    let json = format!(
+        r##"
+{{
+    "asset" : {{ "version" : "2.0" }},
+    "nodes" : [ {{ "mesh" : 0 }} ],
+    "meshes" : [ {{ "primitives" : [
+        {{ "attributes" : {{ "POSITION" : 0 }}, "indices" : 1, "material" : 2 }},
+        {{ "attributes" : {{ "POSITION" : 0 }}, "indices" : 1 }},
+        {{ "attributes" : {{ "POSITION" : 0 }}, "indices" : 1, "material" : 1 }}
+    ] }} ],
+    "materials" : [
+        {{ "pbrMetallicRoughness" : {{ "baseColorFactor" : [ 1.0, 0.0, 0.0, 1.0 ] }} }},
+        {{ "pbrMetallicRoughness" : {{ "baseColorFactor" : [ 0.0, 1.0, 0.0, 1.0 ] }} }},
+        {{ "pbrMetallicRoughness" : {{ "baseColorFactor" : [ 0.0, 0.0, 1.0, 1.0 ] }} }}
+    ],
+    "buffers" : [ {{ "uri" : "{}", "byteLength" : 40 }} ],
+    "bufferViews" : [
+        {{ "buffer" : 0, "byteLength" : 36 }},
+        {{ "buffer" : 0, "byteOffset" : 36, "byteLength" : 3 }}
+    ],
+    "accessors" : [
+        {{ "bufferView" : 0, "componentType" : 5126, "count" : 3, "type" : "VEC3",
+           "min" : [ 0.0, 0.0, 0.0 ], "max" : [ 1.0, 1.0, 0.0 ] }},
+        {{ "bufferView" : 1, "componentType" : 5121, "count" : 3, "type" : "SCALAR" }}
+    ]
+}}
+"##,
+        buffer.uri()
+    );
+    let json_value: serde_json::Value = serde_json::from_str(&json)?;
+    let mut gltf = Gltf::of_json_value(json_value)?;
+    let mut od = mod3d_gltf::ObjectData::new(&gltf);
+    od.add_object(&gltf, 0.into());
+    od.derive_uses(&gltf);
+    let buffers = od.gen_byte_buffers(&mut gltf, &mod3d_gltf::buf_parse_fail, None)?;
+    let buffer_data = od.gen_buffer_data::<_, _, Renderable>(&|x| &buffers[x]);
+    let buffer_descriptors = od.gen_descriptors(&gltf, &|x| &buffer_data[x]);
+    let (index_accessors, data_accessors) =
+        od.gen_accessors(&gltf, &|x| &buffer_data[x], &|x| &buffer_descriptors[x]);
+    let vertices = od.gen_vertices(&gltf, &|x| &index_accessors[x], &|x| &data_accessors[x]);
+    let materials = od.gen_materials(&gltf);
+    assert_eq!(materials.len(), 2, "Only the used materials are generated");
+    let object = od.gen_object(&gltf, &vertices, &[], &materials);
+
+    // Each primitive is drawn with its own material (or none)
+    let primitives = &object.components.borrow_elements()[0].data.mesh.primitives;
+    let colors: Vec<Option<(u8, u8, u8, u8)>> = primitives
+        .iter()
+        .map(|p| {
+            Option::<usize>::from(p.material_index())
+                .map(|m| object.material(m.into()).base_data().rgba_tuple())
+        })
+        .collect();
+    assert_eq!(
+        colors,
+        vec![Some((0, 0, 255, 255)), None, Some((0, 255, 0, 255))]
+    );
+    Ok(())
+}
@@ -0,0 +1,203 @@
+use mod3d_gltf::{Error, GltfMeshoptCompression};
+
+//fi compression
+fn compression(
+    mode: &str,
+    filter: Option<&str>,
+    byte_stride: usize,
+    count: usize,
+) -> GltfMeshoptCompression {
+    GltfMeshoptCompression {
+        mode: mode.into(),
+        filter: filter.map(|f| f.into()),
+        byte_stride,
+        count,
+        ..Default::default()
+    }
+}
+
+//fi vertex_data
+/// Two vertices [1,0,3,4] and [5,0,7,8] compressed with the vertex
+/// codec; each byte of the vertices uses a different byte group
+/// encoding
+fn vertex_data() -> Vec<u8> {
+    let mut data = vec![0xa0];
+    // Byte 0: deltas 1, 4 (zigzag 2, 8) as 2-bit values, 8 as an extra byte
+    data.extend([0b01, 0xb0, 0, 0, 0, 8]);
+    // Byte 1: no deltas
+    data.extend([0b00]);
+    // Byte 2: deltas 3, 4 (zigzag 6, 8) as 4-bit values
+    data.extend([0b10, 0x68, 0, 0, 0, 0, 0, 0, 0]);
+    // Byte 3: deltas 4, 4 (zigzag 8, 8) as raw bytes
+    data.extend([0b11, 8, 8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    // Tail, ending with the base vertex
+    data.extend([0; 32]);
+    data
+}
+
+#[test]
+fn vertex_codec() -> Result<(), Error> {
+    let mut dest = [0_u8; 8];
+    compression("ATTRIBUTES", None, 4, 2).decode(&vertex_data(), &mut dest)?;
+    assert_eq!(dest, [1, 0, 3, 4, 5, 0, 7, 8]);
+
+    let mut data = vertex_data();
+    data.pop();
+    assert!(compression("ATTRIBUTES", None, 4, 2)
+        .decode(&data, &mut dest)
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn index_codecs() -> Result<(), Error> {
+    // Triangles [0,1,2] (new vertices), [2,1,3] (an edge and a new
+    // vertex) and [2,3,10] (an edge and a free index)
+    let mut data = vec![0xe1, 0xf0, 0x10, 0x0f, 0x14];
+    data.extend([
+        0x00, 0x76, 0x87, 0x56, 0x67, 0x78, 0xa9, 0x86, 0x65, 0x89, 0x68, 0x98, 0x01, 0x69, 0, 0,
+    ]);
+    let mut dest = [0_u8; 18];
+    compression("TRIANGLES", None, 2, 9).decode(&data, &mut dest)?;
+    let indices: Vec<u16> = dest
+        .chunks_exact(2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .collect();
+    assert_eq!(indices, [0, 1, 2, 2, 1, 3, 2, 3, 10]);
+
+    // Sequence [0,1,100,2], with 100 a delta from the second baseline
+    let data = [0xd1, 0, 4, 0x91, 0x03, 4, 0, 0, 0, 0];
+    let mut dest = [0_u8; 16];
+    compression("INDICES", None, 4, 4).decode(&data, &mut dest)?;
+    let indices: Vec<u32> = dest
+        .chunks_exact(4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect();
+    assert_eq!(indices, [0, 1, 100, 2]);
+    Ok(())
+}
+
+#[test]
+fn filters() {
+    let mut data = [0_u8; 4];
+    decode_unfiltered(
+        &compression("ATTRIBUTES", Some("EXPONENTIAL"), 4, 1),
+        &mut data,
+        |v| v.copy_from_slice(&0xff000003_u32.to_le_bytes()),
+    );
+    assert_eq!(f32::from_le_bytes(data), 1.5);
+
+    let mut data = [0_u8; 8];
+    decode_unfiltered(
+        &compression("ATTRIBUTES", Some("OCTAHEDRAL"), 4, 2),
+        &mut data,
+        |v| v.copy_from_slice(&[0, 0, 127, 0, 127, 0, 127, 0]),
+    );
+    assert_eq!(data, [0, 0, 127, 0, 127, 0, 0, 0]);
+
+    let mut data = [0_u8; 8];
+    decode_unfiltered(
+        &compression("ATTRIBUTES", Some("QUATERNION"), 8, 1),
+        &mut data,
+        |v| v.copy_from_slice(&[0, 0, 0, 0, 0, 0, 0xff, 0x7f]),
+    );
+    assert_eq!(data, [0, 0, 0, 0, 0, 0, 0xff, 0x7f]);
+}
+
+#[test]
+fn attribute_strides() {
+    let buffers = [mod3d_gltf::GltfBuffer::of_uri("data.bin", 1024)];
+    for filter in [None, Some("EXPONENTIAL")] {
+        for (stride, ok) in [(0, false), (6, false), (4, true), (256, true), (260, false)] {
+            let c = compression("ATTRIBUTES", filter, stride, 1);
+            assert_eq!(
+                c.validate(0.into(), 1024, &buffers).is_ok(),
+                ok,
+                "Stride {stride} with filter {filter:?}"
+            );
+        }
+    }
+}
+
+//fi decode_unfiltered
+/// Decode vertex data whose unfiltered contents are set by 'fill',
+/// applying the filter of the compression
+fn decode_unfiltered<F: Fn(&mut [u8])>(c: &GltfMeshoptCompression, dest: &mut [u8], fill: F) {
+    // Encode the raw bytes with the vertex codec, using raw byte groups
+    let mut raw = vec![0; dest.len()];
+    fill(&mut raw);
+    let stride = c.byte_stride;
+    let mut data = vec![0xa0];
+    let mut last = vec![0_u8; stride];
+    for k in 0..stride {
+        data.push(0b11);
+        let mut group = [0_u8; 16];
+        for (i, v) in raw.chunks_exact(stride).enumerate() {
+            let delta = v[k].wrapping_sub(last[k]) as i8;
+            group[i] = ((delta << 1) ^ (delta >> 7)) as u8;
+            last[k] = v[k];
+        }
+        data.extend(group);
+    }
+    data.extend(vec![0; stride.max(32)]);
+    c.decode(&data, dest).unwrap();
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn meshopt_buffer_views() -> Result<(), Error> {
+    use mod3d_gltf::{Gltf, GltfBuffer};
+    let indices = [0xd1_u8, 0, 4, 4, 0, 0, 0, 0];
+    let mut compressed = indices.to_vec();
+    compressed.extend(vertex_data());
+    let buffer = GltfBuffer::of_base64(&compressed);
+    let json = |stride: usize| {
+        format!(
+            r##"
+{{
+    "asset" : {{ "version" : "2.0" }},
+    "extensionsUsed" : [ "EXT_meshopt_compression" ],
+    "extensionsRequired" : [ "EXT_meshopt_compression" ],
+    "nodes" : [ {{ "mesh" : 0 }} ],
+    "meshes" : [ {{ "primitives" : [ {{ "attributes" : {{ "COLOR_0" : 1 }}, "indices" : 0 }} ] }} ],
+    "buffers" : [
+        {{ "uri" : "{}", "byteLength" : {} }},
+        {{ "byteLength" : 16, "extensions" : {{ "EXT_meshopt_compression" : {{ "fallback" : true }} }} }}
+    ],
+    "bufferViews" : [
+        {{ "buffer" : 1, "byteLength" : 8, "extensions" : {{ "EXT_meshopt_compression" : {{
+            "buffer" : 0, "byteLength" : 8, "byteStride" : {stride}, "count" : 3, "mode" : "INDICES" }} }} }},
+        {{ "buffer" : 1, "byteOffset" : 8, "byteLength" : 8, "byteStride" : 4, "extensions" : {{ "EXT_meshopt_compression" : {{
+            "buffer" : 0, "byteOffset" : 8, "byteLength" : {}, "byteStride" : 4, "count" : 2, "mode" : "ATTRIBUTES" }} }} }}
+    ],
+    "accessors" : [
+        {{ "bufferView" : 0, "componentType" : 5123, "count" : 3, "type" : "SCALAR" }},
+        {{ "bufferView" : 1, "componentType" : 5121, "normalized" : true, "count" : 2, "type" : "VEC4" }}
+    ]
+}}
+"##,
+            buffer.uri(),
+            compressed.len(),
+            compressed.len() - 8,
+        )
+    };
+    let json_value: serde_json::Value = serde_json::from_str(&json(2))?;
+    let mut gltf = Gltf::of_json_value(json_value)?;
+    let compression = gltf.buffer_views()[1].meshopt_compression().unwrap();
+    assert_eq!(compression.mode, "ATTRIBUTES");
+    assert_eq!(compression.filter(), "NONE");
+
+    let mut od = mod3d_gltf::ObjectData::new(&gltf);
+    od.add_object(&gltf, 0.into());
+    od.derive_uses(&gltf);
+    let buffers = od.gen_byte_buffers(&mut gltf, &mod3d_gltf::buf_parse_fail, None)?;
+    assert_eq!(buffers.len(), 2);
+    assert_eq!(buffers[1], [0, 0, 1, 0, 2, 0, 0, 0, 1, 0, 3, 4, 5, 0, 7, 8]);
+
+    let json_value: serde_json::Value = serde_json::from_str(&json(3))?;
+    assert!(
+        Gltf::of_json_value(json_value).is_err(),
+        "Meshopt index data must have a stride of 2 or 4"
+    );
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use mod3d_base::example_client::Renderable;
+use mod3d_base::VertexAttr;
+use mod3d_gltf::{Error, Gltf, GltfBuffer};
+
+//fi morph_json
+/// A triangle with one morph target that moves every vertex by +1 in z
+#[cfg(feature = "serde_json")]
+fn morph_json(node_weights: &str) -> String {
+    let mut data = vec![0_u8, 1, 2, 0];
+    for f in [0.0_f32, 0., 0., 1., 0., 0., 0., 1., 0.] {
+        data.extend_from_slice(&f.to_le_bytes());
+    }
+    for f in [0.0_f32, 0., 1., 0., 0., 1., 0., 0., 1.] {
+        data.extend_from_slice(&f.to_le_bytes());
+    }
+    assert_eq!(data.len(), 76);
+    let buffer = GltfBuffer::of_base64(&data);
+    format!(
+        r##"
+{{
+    "asset" : {{ "version" : "2.0" }},
+    "nodes" : [ {{ "mesh" : 0 {node_weights} }} ],
+    "meshes" : [ {{
+        "primitives" : [ {{
+            "attributes" : {{ "POSITION" : 1 }},
+            "targets" : [ {{ "POSITION" : 2 }} ],
+            "indices" : 0
+        }} ],
+        "weights" : [ 0.5 ]
+    }} ],
+    "buffers" : [ {{ "uri" : "{}", "byteLength" : 76 }} ],
+    "bufferViews" : [
+        {{ "buffer" : 0, "byteLength" : 3 }},
+        {{ "buffer" : 0, "byteOffset" : 4, "byteLength" : 36 }},
+        {{ "buffer" : 0, "byteOffset" : 40, "byteLength" : 36 }}
+    ],
+    "accessors" : [
+        {{ "bufferView" : 0, "componentType" : 5121, "count" : 3, "type" : "SCALAR" }},
+        {{ "bufferView" : 1, "componentType" : 5126, "count" : 3, "type" : "VEC3" }},
+        {{ "bufferView" : 2, "componentType" : 5126, "count" : 3, "type" : "VEC3" }}
+    ]
+}}
+"##,
+        buffer.uri()
+    )
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn morph_targets() -> Result<(), Error> {
+    let json = morph_json("");
+    let json_value: serde_json::Value = serde_json::from_str(&json)?;
+    let mut gltf = Gltf::of_json_value(json_value)?;
+    assert_eq!(gltf.meshes()[0].num_targets(), 1);
+    assert_eq!(gltf.meshes()[0].weights(), &[0.5]);
+    assert_eq!(
+        gltf.meshes()[0].primitives()[0].targets(),
+        &[vec![(VertexAttr::Position, 2.into())]]
+    );
+    let serialized = serde_json::to_string(&gltf)?;
+    assert!(serialized.contains(r#""targets":[{"POSITION":2}]"#));
+
+    let mut od = mod3d_gltf::ObjectData::new(&gltf);
+    od.add_object(&gltf, 0.into());
+    od.derive_uses(&gltf);
+    let buffers = od.gen_byte_buffers(&mut gltf, &mod3d_gltf::buf_parse_fail, None)?;
+    let buffer_data = od.gen_buffer_data::<_, _, Renderable>(&|x| &buffers[x]);
+    let buffer_descriptors = od.gen_descriptors(&gltf, &|x| &buffer_data[x]);
+    let (index_accessors, data_accessors) =
+        od.gen_accessors(&gltf, &|x| &buffer_data[x], &|x| &buffer_descriptors[x]);
+    let vertices = od.gen_vertices(&gltf, &|x| &index_accessors[x], &|x| &data_accessors[x]);
+    assert_eq!(vertices.len(), 1);
+    assert_eq!(od.vertices_of_node(&gltf, 0.into()), vec![0]);
+    let v = &vertices[0];
+    assert_eq!(v.morph_targets().len(), 1);
+    assert_eq!(v.morph_weights(), &[0.5]);
+
+    let morphed = v.morphed_attrs();
+    assert_eq!(morphed.len(), 1, "Only positions are displaced");
+    assert_eq!(morphed[0].vertex_attr(), VertexAttr::Position);
+    assert_eq!(morphed[0].num_targets(), 1);
+    let mut blended = vec![];
+    morphed[0].blend(v.morph_weights(), &mut blended);
+    assert_eq!(blended, vec![0., 0., 0.5, 1., 0., 0.5, 0., 1., 0.5]);
+    morphed[0].blend(&[], &mut blended);
+    assert_eq!(blended, morphed[0].base());
+    Ok(())
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn node_weights() -> Result<(), Error> {
+    let json = morph_json(r#", "weights" : [ 0.25 ] "#);
+    let json_value: serde_json::Value = serde_json::from_str(&json)?;
+    let mut gltf = Gltf::of_json_value(json_value)?;
+    assert_eq!(gltf.nodes()[0].weights(), Some([0.25].as_slice()));
+
+    let mut od = mod3d_gltf::ObjectData::new(&gltf);
+    od.add_object(&gltf, 0.into());
+    od.derive_uses(&gltf);
+    let buffers = od.gen_byte_buffers(&mut gltf, &mod3d_gltf::buf_parse_fail, None)?;
+    let buffer_data = od.gen_buffer_data::<_, _, Renderable>(&|x| &buffers[x]);
+    let buffer_descriptors = od.gen_descriptors(&gltf, &|x| &buffer_data[x]);
+    let (index_accessors, data_accessors) =
+        od.gen_accessors(&gltf, &|x| &buffer_data[x], &|x| &buffer_descriptors[x]);
+    let vertices = od.gen_vertices(&gltf, &|x| &index_accessors[x], &|x| &data_accessors[x]);
+    assert_eq!(vertices[0].morph_weights(), &[0.25]);
+
+    let json = morph_json(r#", "weights" : [ 0.25, 0.5 ] "#);
+    let json_value: serde_json::Value = serde_json::from_str(&json)?;
+    assert!(
+        Gltf::of_json_value(json_value).is_err(),
+        "Node weights must match the morph targets of its mesh"
+    );
+    Ok(())
+}
+
+#[test]
+fn morph_weights_animation() {
+    use mod3d_base::{AnimationChannel, AnimationClip, AnimationProperty, Interpolation};
+    let mut clip = AnimationClip::new("blink");
+    clip.add_channel(AnimationChannel::new(
+        3,
+        AnimationProperty::Weights,
+        Interpolation::Linear,
+        vec![0., 1.],
+        vec![0., 1., 1., 0.],
+    ));
+    let mut weights = [0.; 2];
+    clip.apply_to_morph_weights(0.25, 3, &mut weights);
+    assert_eq!(weights, [0.25, 0.75]);
+    let mut weights = [0.; 2];
+    clip.apply_to_morph_weights(0.25, 2, &mut weights);
+    assert_eq!(weights, [0., 0.], "Other targets are not affected");
+}
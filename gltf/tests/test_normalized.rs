@@ -0,0 +1,88 @@
+use mod3d_base::example_client::Renderable;
+use mod3d_base::{BufferElementType, VertexAttr};
+use mod3d_gltf::{Error, Gltf, GltfBuffer};
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn normalized_attributes() -> Result<(), Error> {
+    let mut data = vec![0_u8, 1, 2, 0];
+    for f in [0.0_f32, 0., 0., 1., 0., 0., 0., 1., 0.] {
+        data.extend_from_slice(&f.to_le_bytes());
+    }
+    data.extend_from_slice(&[255, 0, 0, 255, 0, 255, 0, 255, 0, 0, 255, 128]);
+    data.extend_from_slice(&[0, 0, 0, 0x80, 0xff, 0xff]);
+    assert_eq!(data.len(), 58);
+    let buffer = GltfBuffer::of_base64(&data);
+    let json = format!(
+        r##"
+{{
+    "asset" : {{ "version" : "2.0" }},
+    "nodes" : [ {{ "mesh" : 0 }} ],
+    "meshes" : [ {{ "primitives" : [ {{
+        "attributes" : {{ "POSITION" : 1, "COLOR_0" : 2, "TEXCOORD_0" : 3 }},
+        "indices" : 0
+    }} ] }} ],
+    "buffers" : [ {{ "uri" : "{}", "byteLength" : 58 }} ],
+    "bufferViews" : [
+        {{ "buffer" : 0, "byteLength" : 3 }},
+        {{ "buffer" : 0, "byteOffset" : 4, "byteLength" : 36 }},
+        {{ "buffer" : 0, "byteOffset" : 40, "byteLength" : 12 }},
+        {{ "buffer" : 0, "byteOffset" : 52, "byteLength" : 6 }}
+    ],
+    "accessors" : [
+        {{ "bufferView" : 0, "componentType" : 5121, "count" : 3, "type" : "SCALAR" }},
+        {{ "bufferView" : 1, "componentType" : 5126, "count" : 3, "type" : "VEC3" }},
+        {{ "bufferView" : 2, "componentType" : 5121, "normalized" : true, "count" : 3, "type" : "VEC4" }},
+        {{ "bufferView" : 3, "componentType" : 5123, "count" : 3, "type" : "SCALAR" }}
+    ]
+}}
+"##,
+        buffer.uri()
+    );
+    let json_value: serde_json::Value = serde_json::from_str(&json)?;
+    let mut gltf = Gltf::of_json_value(json_value)?;
+    assert!(gltf.accessors()[2].is_normalized());
+    assert!(!gltf.accessors()[3].is_normalized());
+
+    let colors = gltf.accessors()[2].read_floats(&gltf.buffer_views()[2], &data)?;
+    assert_eq!(&colors[0..4], &[1., 0., 0., 1.]);
+    assert_eq!(colors[11], 128. / 255.);
+    let uvs = gltf.accessors()[3].read_floats(&gltf.buffer_views()[3], &data)?;
+    assert_eq!(uvs, vec![0., 32768., 65535.]);
+
+    let mut od = mod3d_gltf::ObjectData::new(&gltf);
+    od.add_object(&gltf, 0.into());
+    od.derive_uses(&gltf);
+    let buffers = od.gen_byte_buffers(&mut gltf, &mod3d_gltf::buf_parse_fail, None)?;
+    let buffer_data = od.gen_buffer_data::<_, _, Renderable>(&|x| &buffers[x]);
+    let buffer_descriptors = od.gen_descriptors(&gltf, &|x| &buffer_data[x]);
+    let (_, data_accessors) =
+        od.gen_accessors(&gltf, &|x| &buffer_data[x], &|x| &buffer_descriptors[x]);
+    for acc in &data_accessors {
+        assert_eq!(
+            acc.is_normalized(),
+            acc.vertex_attr() == VertexAttr::Color,
+            "Only colors are normalized"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn read_f32() {
+    let t = BufferElementType::SInt16;
+    assert_eq!(t.read_f32(&(-32767_i16).to_le_bytes(), true), -1.);
+    assert_eq!(t.read_f32(&(-32768_i16).to_le_bytes(), true), -1.);
+    assert_eq!(t.read_f32(&(-32768_i16).to_le_bytes(), false), -32768.);
+    let t = BufferElementType::Float16;
+    assert_eq!(t.read_f32(&0x3c00_u16.to_le_bytes(), false), 1.);
+    assert_eq!(t.read_f32(&0xc100_u16.to_le_bytes(), false), -2.5);
+    assert_eq!(
+        t.read_f32(&0x0001_u16.to_le_bytes(), false),
+        2.0_f32.powi(-24)
+    );
+    assert!(t.read_f32(&0x7c00_u16.to_le_bytes(), false).is_infinite());
+    let mut desc = mod3d_base::VertexDesc::vec(VertexAttr::Color, BufferElementType::Float32, 4, 0);
+    desc.set_normalized(true);
+    assert!(!desc.is_normalized(), "Float types are never normalized");
+}
@@ -0,0 +1,163 @@
+use mod3d_base::example_client::Renderable;
+use mod3d_base::{BufferElementType, VertexAttr};
+use mod3d_gltf::{Error, Gltf, GltfBuffer};
+
+//fi quantized_json
+/// A triangle with SInt16 positions (stride 8), normals of the given
+/// component type (stride 4) and UInt16 texture coordinates
+#[cfg(feature = "serde_json")]
+fn quantized_json(normal_type: u32, normalized: bool) -> (Vec<u8>, String) {
+    let mut data = vec![0_u8, 1, 2, 0];
+    for p in [[0_i16, 0, 0], [100, 0, 0], [0, 100, 0]] {
+        for c in p {
+            data.extend_from_slice(&c.to_le_bytes());
+        }
+        data.extend_from_slice(&[0, 0]);
+    }
+    for _ in 0..3 {
+        data.extend_from_slice(&[0, 0, 127, 0]);
+    }
+    for c in [0_u16, 0, 1024, 0, 0, 1024] {
+        data.extend_from_slice(&c.to_le_bytes());
+    }
+    assert_eq!(data.len(), 52);
+    let buffer = GltfBuffer::of_base64(&data);
+    let json = format!(
+        r##"
+{{
+    "asset" : {{ "version" : "2.0" }},
+    "extensionsUsed" : [ "KHR_mesh_quantization" ],
+    "extensionsRequired" : [ "KHR_mesh_quantization" ],
+    "nodes" : [ {{ "mesh" : 0, "scale" : [ 0.01, 0.01, 0.01 ] }} ],
+    "meshes" : [ {{ "primitives" : [ {{
+        "attributes" : {{ "POSITION" : 1, "NORMAL" : 2, "TEXCOORD_0" : 3 }},
+        "indices" : 0
+    }} ] }} ],
+    "buffers" : [ {{ "uri" : "{}", "byteLength" : 52 }} ],
+    "bufferViews" : [
+        {{ "buffer" : 0, "byteLength" : 3 }},
+        {{ "buffer" : 0, "byteOffset" : 4, "byteLength" : 24, "byteStride" : 8 }},
+        {{ "buffer" : 0, "byteOffset" : 28, "byteLength" : 12, "byteStride" : 4 }},
+        {{ "buffer" : 0, "byteOffset" : 40, "byteLength" : 12 }}
+    ],
+    "accessors" : [
+        {{ "bufferView" : 0, "componentType" : 5121, "count" : 3, "type" : "SCALAR" }},
+        {{ "bufferView" : 1, "componentType" : 5122, "count" : 3, "type" : "VEC3",
+           "min" : [ 0, 0, 0 ], "max" : [ 100, 100, 0 ] }},
+        {{ "bufferView" : 2, "componentType" : {normal_type}, "normalized" : {normalized}, "count" : 3, "type" : "VEC3" }},
+        {{ "bufferView" : 3, "componentType" : 5123, "count" : 3, "type" : "VEC2" }}
+    ]
+}}
+"##,
+        buffer.uri()
+    );
+    (data, json)
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn quantized_attributes() -> Result<(), Error> {
+    let (data, json) = quantized_json(5120, true);
+    let json_value: serde_json::Value = serde_json::from_str(&json)?;
+    let mut gltf = Gltf::of_json_value(json_value)?;
+
+    let positions = gltf.accessors()[1].read_floats(&gltf.buffer_views()[1], &data)?;
+    assert_eq!(positions[3..6], [100., 0., 0.]);
+    let normals = gltf.accessors()[2].read_floats(&gltf.buffer_views()[2], &data)?;
+    assert_eq!(normals[0..3], [0., 0., 1.]);
+
+    let mut od = mod3d_gltf::ObjectData::new(&gltf);
+    od.add_object(&gltf, 0.into());
+    od.derive_uses(&gltf);
+    let buffers = od.gen_byte_buffers(&mut gltf, &mod3d_gltf::buf_parse_fail, None)?;
+    let buffer_data = od.gen_buffer_data::<_, _, Renderable>(&|x| &buffers[x]);
+    let buffer_descriptors = od.gen_descriptors(&gltf, &|x| &buffer_data[x]);
+    let (_, data_accessors) =
+        od.gen_accessors(&gltf, &|x| &buffer_data[x], &|x| &buffer_descriptors[x]);
+    assert_eq!(data_accessors.len(), 3);
+    for acc in &data_accessors {
+        let (ele_type, normalized) = match acc.vertex_attr() {
+            VertexAttr::Position => (BufferElementType::SInt16, false),
+            VertexAttr::Normal => (BufferElementType::SInt8, true),
+            _ => (BufferElementType::UInt16, false),
+        };
+        assert_eq!(acc.ele_type(), ele_type, "Quantized data is kept as is");
+        assert_eq!(acc.is_normalized(), normalized);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn quantized_attributes_invalid() -> Result<(), Error> {
+    let (_, json) = quantized_json(5120, false);
+    let json_value: serde_json::Value = serde_json::from_str(&json)?;
+    assert!(
+        Gltf::of_json_value(json_value).is_err(),
+        "SInt8 normals must be normalized"
+    );
+
+    let (_, json) = quantized_json(5121, true);
+    let json_value: serde_json::Value = serde_json::from_str(&json)?;
+    assert!(
+        Gltf::of_json_value(json_value).is_err(),
+        "Normals may not be unsigned"
+    );
+    Ok(())
+}
+
+#[test]
+fn attribute_type_permitted() {
+    use mod3d_gltf::GltfPrimitive;
+    let permitted = |attr, is_target, ele_type, normalized| {
+        GltfPrimitive::attribute_type_permitted(attr, is_target, ele_type, normalized)
+    };
+    assert!(permitted(
+        VertexAttr::Position,
+        false,
+        BufferElementType::UInt8,
+        false
+    ));
+    assert!(!permitted(
+        VertexAttr::Position,
+        true,
+        BufferElementType::UInt8,
+        false
+    ));
+    assert!(permitted(
+        VertexAttr::Tangent,
+        true,
+        BufferElementType::SInt16,
+        true
+    ));
+    assert!(permitted(
+        VertexAttr::Color,
+        false,
+        BufferElementType::UInt16,
+        true
+    ));
+    assert!(!permitted(
+        VertexAttr::Color,
+        false,
+        BufferElementType::SInt16,
+        true
+    ));
+    assert!(permitted(
+        VertexAttr::Joints,
+        false,
+        BufferElementType::UInt8,
+        false
+    ));
+    assert!(!permitted(
+        VertexAttr::Joints,
+        false,
+        BufferElementType::Float32,
+        false
+    ));
+    assert!(!permitted(
+        VertexAttr::Weights,
+        false,
+        BufferElementType::UInt8,
+        false
+    ));
+}
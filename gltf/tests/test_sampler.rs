@@ -0,0 +1,72 @@
+use mod3d_base::{TextureFilter, TextureWrap};
+use mod3d_gltf::{Error, Gltf};
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn samplers() -> Result<(), Error> {
+    const JSON: &str = r##"
+{
+    "asset" : { "version" : "2.0" },
+    "images" : [ { "uri" : "atlas.png" } ],
+    "samplers" : [
+        { "magFilter" : 9728, "minFilter" : 9986, "wrapS" : 33071, "wrapT" : 33648 },
+        { }
+    ],
+    "textures" : [
+        { "source" : 0, "sampler" : 0 },
+        { "source" : 0, "sampler" : 1 },
+        { "source" : 0 }
+    ]
+}
+"##;
+    let json_value: serde_json::Value = serde_json::from_str(JSON)?;
+    let gltf = Gltf::of_json_value(json_value)?;
+
+    assert_eq!(gltf.samplers().len(), 2);
+    let s = gltf.samplers()[0].texture_sampler();
+    assert_eq!(s.mag_filter, TextureFilter::Nearest);
+    assert_eq!(s.min_filter, TextureFilter::NearestMipmapLinear);
+    assert!(s.min_filter.uses_mipmaps());
+    assert_eq!(s.wrap_s, TextureWrap::ClampToEdge);
+    assert_eq!(s.wrap_t, TextureWrap::MirroredRepeat);
+
+    let s = gltf.samplers()[1].texture_sampler();
+    assert_eq!(s, mod3d_base::TextureSampler::default());
+    assert_eq!(s.wrap_s, TextureWrap::Repeat);
+
+    let texture: mod3d_gltf::TextureIndex = 2.into();
+    assert_eq!(gltf[texture].sampler(), None);
+    Ok(())
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn bad_sampler() -> Result<(), Error> {
+    const JSON: &str = r##"
+{
+    "asset" : { "version" : "2.0" },
+    "images" : [ { "uri" : "atlas.png" } ],
+    "textures" : [ { "source" : 0, "sampler" : 0 } ]
+}
+"##;
+    let json_value: serde_json::Value = serde_json::from_str(JSON)?;
+    assert!(Gltf::of_json_value(json_value).is_err());
+
+    // Mipmap filters are only valid for minification
+    const MAG_MIPMAP: &str = r##"
+{
+    "asset" : { "version" : "2.0" },
+    "samplers" : [ { "magFilter" : 9987 } ]
+}
+"##;
+    let json_value: serde_json::Value = serde_json::from_str(MAG_MIPMAP)?;
+    assert!(Gltf::of_json_value(json_value).is_err());
+
+    let sampler = mod3d_base::TextureSampler {
+        mag_filter: TextureFilter::NearestMipmapLinear,
+        ..Default::default()
+    };
+    let sampler = mod3d_gltf::GltfSampler::of_texture_sampler(&sampler);
+    assert_eq!(sampler.mag_filter, TextureFilter::Nearest);
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use mod3d_base::example_client::Renderable;
+use mod3d_gltf::{Error, Gltf, NodeIndex};
+
+//fi scene_json
+/// Two scenes of (empty) meshes; the default scene has two root trees,
+/// one of which has a child, and the other scene has a single root
+#[cfg(feature = "serde_json")]
+fn scene_json(default_scene: &str) -> String {
+    format!(
+        r##"
+{{
+    "asset" : {{ "version" : "2.0" }},
+    {default_scene}
+    "scenes" : [ {{ "nodes" : [ 3 ] }}, {{ "nodes" : [ 0, 2 ] }} ],
+    "nodes" : [
+        {{ "mesh" : 0, "translation" : [ 1, 0, 0 ], "children" : [ 1 ] }},
+        {{ "mesh" : 0, "translation" : [ 0, 1, 0 ] }},
+        {{ "mesh" : 0, "translation" : [ 0, 0, 1 ] }},
+        {{ "mesh" : 0, "translation" : [ 5, 5, 5 ] }}
+    ],
+    "meshes" : [ {{ "primitives" : [] }} ]
+}}
+"##
+    )
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn scene_roots() -> Result<(), Error> {
+    let json_value: serde_json::Value = serde_json::from_str(&scene_json(r#""scene" : 1,"#))?;
+    let gltf = Gltf::of_json_value(json_value)?;
+    assert_eq!(gltf.default_scene(), Some(1.into()));
+    let roots: Vec<NodeIndex> = gltf.scenes()[1].iter_roots().collect();
+    assert_eq!(roots, [0.into(), 2.into()]);
+
+    // Without a default scene the first scene is used
+    let json_value: serde_json::Value = serde_json::from_str(&scene_json(""))?;
+    let gltf = Gltf::of_json_value(json_value)?;
+    assert_eq!(gltf.default_scene(), Some(0.into()));
+    Ok(())
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn scene_object() -> Result<(), Error> {
+    let json_value: serde_json::Value = serde_json::from_str(&scene_json(r#""scene" : 1,"#))?;
+    let gltf = Gltf::of_json_value(json_value)?;
+    let mut od = mod3d_gltf::ObjectData::new(&gltf);
+    assert_eq!(od.add_default_scene(&gltf), Some(1.into()));
+    od.derive_uses(&gltf);
+    let object = od.gen_object::<mod3d_base::BaseMaterial, Renderable>(&gltf, &[], &[], &[]);
+    let components = object.components.borrow_elements();
+    let mut translations: Vec<[f32; 3]> = components
+        .iter()
+        .map(|c| {
+            let m = c.data.transformation.unwrap().mat4();
+            [m[12], m[13], m[14]]
+        })
+        .collect();
+    translations.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(
+        translations,
+        [[0., 0., 1.], [1., 0., 0.], [1., 1., 0.]],
+        "All the trees of the scene are kept, with their global transformations"
+    );
+
+    // A scene whose only node is not node 0, and uses a different mesh
+    const JSON: &str = r##"
+{
+    "asset" : { "version" : "2.0" },
+    "scenes" : [ { "nodes" : [ 2 ] } ],
+    "nodes" : [
+        { "mesh" : 0 },
+        { "mesh" : 0 },
+        { "mesh" : 1, "translation" : [ 0, 2, 0 ] }
+    ],
+    "meshes" : [ { "primitives" : [] }, { "primitives" : [] } ]
+}
+"##;
+    let json_value: serde_json::Value = serde_json::from_str(JSON)?;
+    let gltf = Gltf::of_json_value(json_value)?;
+    let mut od = mod3d_gltf::ObjectData::new(&gltf);
+    assert_eq!(od.add_default_scene(&gltf), Some(0.into()));
+    od.derive_uses(&gltf);
+    let object = od.gen_object::<mod3d_base::BaseMaterial, Renderable>(&gltf, &[], &[], &[]);
+    let components = object.components.borrow_elements();
+    assert_eq!(components.len(), 1);
+    let m = components[0].data.transformation.unwrap().mat4();
+    assert_eq!(m[12..15], [0., 2., 0.]);
+
+    let json_value: serde_json::Value =
+        serde_json::from_str(r#"{ "asset" : { "version" : "2.0" }, "nodes" : [ {} ] }"#)?;
+    let gltf = Gltf::of_json_value(json_value)?;
+    let mut od = mod3d_gltf::ObjectData::new(&gltf);
+    assert_eq!(od.add_default_scene(&gltf), None);
+    Ok(())
+}
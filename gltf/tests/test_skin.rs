@@ -0,0 +1,397 @@
+use mod3d_gltf::{Error, Gltf};
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn skin_skeleton() -> Result<(), Error> {
+    const JSON: &str = r##"
+{
+    "asset" : { "version" : "2.0" },
+    "scene" : 0,
+    "scenes" : [ { "nodes" : [ 0, 1 ] } ],
+    "nodes" : [
+        { "name" : "Body", "mesh" : 0, "skin" : 0 },
+        { "name" : "Root", "translation" : [ 0.0, 1.0, 0.0 ], "children" : [ 2 ] },
+        { "name" : "Spine", "translation" : [ 0.0, 2.0, 0.0 ], "children" : [ 3 ] },
+        { "name" : "Head", "translation" : [ 0.0, 0.5, 0.0 ] }
+    ],
+    "meshes" : [ { "name" : "Body", "primitives" : [] } ],
+    "skins" : [ { "name" : "Armature", "skeleton" : 1, "joints" : [ 2, 1, 3 ] } ]
+}
+"##;
+    let json_value: serde_json::Value = serde_json::from_str(JSON)?;
+    let gltf = Gltf::of_json_value(json_value)?;
+
+    assert_eq!(gltf.skins().len(), 1);
+    let skin = &gltf.skins()[0];
+    assert_eq!(skin.skeleton(), Some(1.into()));
+    assert_eq!(skin.joints(), &[2.into(), 1.into(), 3.into()]);
+    assert!(skin.inverse_bind_matrices().is_none());
+
+    let mut od = mod3d_gltf::ObjectData::new(&gltf);
+    od.add_object(&gltf, gltf.get_node("Body").unwrap());
+    od.derive_uses(&gltf);
+
+    let skeleton = od
+        .gen_skeleton(&gltf)
+        .expect("Skinned object must have a skeleton");
+    assert_eq!(skeleton.max_index, 3);
+    let roots: Vec<usize> = skeleton.iter_roots().collect();
+    assert_eq!(roots, vec![1], "Only the 'Root' joint is a root bone");
+
+    let bones = skeleton.skeleton.borrow_elements();
+    assert_eq!(bones[0].data.matrix_index, 0);
+    assert_eq!(
+        bones[1].data.borrow_transformation().translation(),
+        [0., 1., 0.]
+    );
+    assert_eq!(
+        bones[2].data.borrow_transformation().translation(),
+        [0., 0.5, 0.]
+    );
+    Ok(())
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn unskinned_has_no_skeleton() -> Result<(), Error> {
+    const JSON: &str = r##"
+{
+    "asset" : { "version" : "2.0" },
+    "nodes" : [ { "name" : "Cube", "mesh" : 0 } ],
+    "meshes" : [ { "primitives" : [] } ]
+}
+"##;
+    let json_value: serde_json::Value = serde_json::from_str(JSON)?;
+    let gltf = Gltf::of_json_value(json_value)?;
+    let mut od = mod3d_gltf::ObjectData::new(&gltf);
+    od.add_object(&gltf, 0.into());
+    od.derive_uses(&gltf);
+    assert!(od.gen_skeleton(&gltf).is_none());
+    Ok(())
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn mesh_attached_to_joint() -> Result<(), Error> {
+    use mod3d_base::example_client::Renderable;
+    const JSON: &str = r##"
+{
+    "asset" : { "version" : "2.0" },
+    "nodes" : [
+        { "name" : "Body", "mesh" : 0, "skin" : 0 },
+        { "name" : "Root", "translation" : [ 0.0, 1.0, 0.0 ], "children" : [ 2 ] },
+        { "name" : "Spine", "translation" : [ 0.0, 2.0, 0.0 ], "children" : [ 3 ] },
+        { "name" : "Head", "translation" : [ 0.0, 0.5, 0.0 ], "children" : [ 4 ] },
+        { "name" : "Hat", "mesh" : 1, "translation" : [ 0.0, 0.25, 0.0 ] }
+    ],
+    "meshes" : [ { "primitives" : [] }, { "primitives" : [] } ],
+    "skins" : [ { "skeleton" : 1, "joints" : [ 2, 1, 3 ] } ]
+}
+"##;
+    let json_value: serde_json::Value = serde_json::from_str(JSON)?;
+    let gltf = Gltf::of_json_value(json_value)?;
+
+    let mut od = mod3d_gltf::ObjectData::new(&gltf);
+    od.add_object(&gltf, gltf.get_node("Body").unwrap());
+    od.add_object(&gltf, gltf.get_node("Root").unwrap());
+    od.derive_uses(&gltf);
+    let hat = gltf.get_node("Hat").unwrap();
+    assert_eq!(
+        od.bone_of_ancestor(&gltf, hat),
+        Some(2),
+        "Hat is under Head"
+    );
+    assert_eq!(
+        od.bone_of_ancestor(&gltf, gltf.get_node("Body").unwrap()),
+        None
+    );
+
+    let object = od.gen_object::<mod3d_base::BaseMaterial, Renderable>(&gltf, &[], &[], &[]);
+    let bones: Vec<Option<usize>> = object
+        .components
+        .borrow_elements()
+        .iter()
+        .map(|c| c.data.bone)
+        .collect();
+    assert!(bones.contains(&Some(2)), "Hat must follow the Head bone");
+    Ok(())
+}
+
+//fi ibm_json
+/// A skinned mesh node whose skin has joints 'Root' and 'Spine' with
+/// inverse bind matrices for a bind pose 1 higher than the rest pose,
+/// and a 'Hat' mesh node attached to 'Spine'; the second skin is given
+#[cfg(feature = "serde_json")]
+fn ibm_json(skin_1: &str) -> String {
+    let mut data = vec![];
+    for y in [-2.0_f32, -4.0] {
+        let mut m: [f32; 16] = geo_nd::matrix::identity4();
+        m[13] = y;
+        for f in m {
+            data.extend_from_slice(&f.to_le_bytes());
+        }
+    }
+    let buffer = mod3d_gltf::GltfBuffer::of_base64(&data);
+    format!(
+        r##"
+{{
+    "asset" : {{ "version" : "2.0" }},
+    "nodes" : [
+        {{ "name" : "Body", "mesh" : 0, "skin" : 0 }},
+        {{ "name" : "Root", "translation" : [ 0.0, 1.0, 0.0 ], "children" : [ 2 ] }},
+        {{ "name" : "Spine", "translation" : [ 0.0, 2.0, 0.0 ], "children" : [ 3 ] }},
+        {{ "name" : "Hat", "mesh" : 1, "translation" : [ 0.0, 0.25, 0.0 ] }},
+        {{ "name" : "Legs", "mesh" : 0, "skin" : 1 }}
+    ],
+    "meshes" : [ {{ "primitives" : [] }}, {{ "primitives" : [] }} ],
+    "skins" : [
+        {{ "inverseBindMatrices" : 0, "joints" : [ 1, 2 ] }},
+        {skin_1}
+    ],
+    "buffers" : [ {{ "uri" : "{}", "byteLength" : 128 }} ],
+    "bufferViews" : [ {{ "buffer" : 0, "byteLength" : 128 }} ],
+    "accessors" : [
+        {{ "bufferView" : 0, "componentType" : 5126, "count" : 2, "type" : "MAT4" }},
+        {{ "bufferView" : 0, "componentType" : 5126, "count" : 1, "type" : "MAT4" }}
+    ]
+}}
+"##,
+        buffer.uri()
+    )
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn skin_inverse_bind_matrices() -> Result<(), Error> {
+    use mod3d_base::example_client::Renderable;
+    let json_value: serde_json::Value = serde_json::from_str(&ibm_json(r#"{ "joints" : [ 1 ] }"#))?;
+    let mut gltf = Gltf::of_json_value(json_value)?;
+    let mut od = mod3d_gltf::ObjectData::new(&gltf);
+    od.add_object(&gltf, gltf.get_node("Body").unwrap());
+    od.add_object(&gltf, gltf.get_node("Root").unwrap());
+    od.derive_uses(&gltf);
+    let buffers = od.gen_byte_buffers(&mut gltf, &mod3d_gltf::buf_parse_fail, None)?;
+    od.gen_skins(&gltf, &|i| &buffers[i])?;
+
+    let skeleton = od.gen_skeleton(&gltf).unwrap();
+    let mut pose = mod3d_base::SkeletonPose::new(&skeleton);
+    pose.update(1);
+    for m in pose.bone_matrices() {
+        assert_eq!(
+            m[12..15],
+            [0., -1., 0.],
+            "The rest pose is 1 below the bind pose"
+        );
+    }
+
+    let object = od.gen_object::<mod3d_base::BaseMaterial, Renderable>(&gltf, &[], &[], &[]);
+    let hat = object
+        .components
+        .borrow_elements()
+        .iter()
+        .find(|c| c.data.bone.is_some())
+        .map(|c| c.data.transformation.unwrap().mat4())
+        .unwrap();
+    let m = geo_nd::matrix::multiply4(&hat, &pose.bone_matrices()[1]);
+    assert_eq!(
+        m[12..15],
+        [0., 3.25, 0.],
+        "The hat is drawn at its rest pose"
+    );
+    Ok(())
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn skins_have_their_own_bones() -> Result<(), Error> {
+    // Each second skin, with the bones of its joints 'Root' and 'Spine'
+    let skins = [
+        (
+            r#"{ "inverseBindMatrices" : 1, "joints" : [ 1 ] }"#,
+            vec![2],
+            vec![],
+        ),
+        (
+            r#"{ "inverseBindMatrices" : 0, "joints" : [ 1, 2 ] }"#,
+            vec![2],
+            vec![3],
+        ),
+        (
+            r#"{ "inverseBindMatrices" : 0, "joints" : [ 2, 1 ] }"#,
+            vec![3],
+            vec![2],
+        ),
+        (
+            r#"{ "inverseBindMatrices" : 1, "joints" : [ 2 ] }"#,
+            vec![],
+            vec![2],
+        ),
+        (r#"{ "joints" : [ 1 ] }"#, vec![2], vec![]),
+    ];
+    for (skin, root_bones, spine_bones) in skins {
+        let json_value: serde_json::Value = serde_json::from_str(&ibm_json(skin))?;
+        let mut gltf = Gltf::of_json_value(json_value)?;
+        let mut od = mod3d_gltf::ObjectData::new(&gltf);
+        od.add_object(&gltf, gltf.get_node("Body").unwrap());
+        od.add_object(&gltf, gltf.get_node("Legs").unwrap());
+        od.derive_uses(&gltf);
+        let buffers = od.gen_byte_buffers(&mut gltf, &mod3d_gltf::buf_parse_fail, None)?;
+        od.gen_skins(&gltf, &|i| &buffers[i])?;
+        assert_eq!(od.skin_bones(&gltf, 0.into()), Some(0..2), "Skin {skin}");
+        let root_bones: Vec<usize> = [0].into_iter().chain(root_bones).collect();
+        let spine_bones: Vec<usize> = [1].into_iter().chain(spine_bones).collect();
+        assert_eq!(od.bones_of_node(1.into()), root_bones, "Skin {skin}");
+        assert_eq!(od.bones_of_node(2.into()), spine_bones, "Skin {skin}");
+        let skeleton = od.gen_skeleton(&gltf).unwrap();
+        assert_eq!(
+            skeleton.max_index,
+            2 + od.skin_bones(&gltf, 1.into()).unwrap().len()
+        );
+    }
+    Ok(())
+}
+
+//fi two_skins_json
+/// Two characters, each with a skin of its own joints, whose inverse
+/// bind matrices are those of the rest pose
+#[cfg(feature = "serde_json")]
+fn two_skins_json() -> String {
+    let mut data = vec![];
+    for (x, y) in [(0.0_f32, -1.0_f32), (0., -3.), (-5., -3.), (-5., -1.)] {
+        let mut m: [f32; 16] = geo_nd::matrix::identity4();
+        m[12] = x;
+        m[13] = y;
+        for f in m {
+            data.extend_from_slice(&f.to_le_bytes());
+        }
+    }
+    let buffer = mod3d_gltf::GltfBuffer::of_base64(&data);
+    format!(
+        r##"
+{{
+    "asset" : {{ "version" : "2.0" }},
+    "nodes" : [
+        {{ "name" : "Alice", "mesh" : 0, "skin" : 0 }},
+        {{ "name" : "Bob", "mesh" : 0, "skin" : 1 }},
+        {{ "name" : "AliceRoot", "translation" : [ 0.0, 1.0, 0.0 ], "children" : [ 3 ] }},
+        {{ "name" : "AliceSpine", "translation" : [ 0.0, 2.0, 0.0 ] }},
+        {{ "name" : "BobRoot", "translation" : [ 5.0, 1.0, 0.0 ], "children" : [ 5 ] }},
+        {{ "name" : "BobSpine", "translation" : [ 0.0, 2.0, 0.0 ] }}
+    ],
+    "meshes" : [ {{ "primitives" : [] }} ],
+    "skins" : [
+        {{ "inverseBindMatrices" : 0, "joints" : [ 2, 3 ] }},
+        {{ "inverseBindMatrices" : 1, "joints" : [ 5, 4 ] }}
+    ],
+    "buffers" : [ {{ "uri" : "{}", "byteLength" : 256 }} ],
+    "bufferViews" : [
+        {{ "buffer" : 0, "byteLength" : 128 }},
+        {{ "buffer" : 0, "byteOffset" : 128, "byteLength" : 128 }}
+    ],
+    "accessors" : [
+        {{ "bufferView" : 0, "componentType" : 5126, "count" : 2, "type" : "MAT4" }},
+        {{ "bufferView" : 1, "componentType" : 5126, "count" : 2, "type" : "MAT4" }}
+    ]
+}}
+"##,
+        buffer.uri()
+    )
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn two_skins() -> Result<(), Error> {
+    use mod3d_base::example_client::Renderable;
+    let json_value: serde_json::Value = serde_json::from_str(&two_skins_json())?;
+    let mut gltf = Gltf::of_json_value(json_value)?;
+    let mut od = mod3d_gltf::ObjectData::new(&gltf);
+    od.add_object(&gltf, gltf.get_node("Alice").unwrap());
+    od.add_object(&gltf, gltf.get_node("Bob").unwrap());
+    od.derive_uses(&gltf);
+    let buffers = od.gen_byte_buffers(&mut gltf, &mod3d_gltf::buf_parse_fail, None)?;
+    od.gen_skins(&gltf, &|i| &buffers[i])?;
+    assert_eq!(od.skin_bones(&gltf, 0.into()), Some(0..2));
+    assert_eq!(od.skin_bones(&gltf, 1.into()), Some(2..4));
+    assert_eq!(od.bone_of_node(gltf.get_node("BobRoot").unwrap()), Some(3));
+
+    let skeleton = od.gen_skeleton(&gltf).unwrap();
+    assert_eq!(skeleton.max_index, 4);
+    let mut roots: Vec<usize> = skeleton.iter_roots().collect();
+    roots.sort();
+    assert_eq!(roots, vec![0, 3], "Each skin has its own root bone");
+
+    // At rest every joint is at its bind pose; moving the root of Bob
+    // moves just the bones of Bob
+    let mut pose = mod3d_base::SkeletonPose::new(&skeleton);
+    for m in pose.bone_matrices() {
+        assert_eq!(m[12..15], [0., 0., 0.], "The rest pose is the bind pose");
+    }
+    let bone_pose = pose.bone_pose_mut(3).unwrap();
+    let t = *bone_pose.transformation();
+    bone_pose.set_transformation(t.with_translation([5., 2., 0.]));
+    pose.update(1);
+    let moved: Vec<[f32; 3]> = pose
+        .bone_matrices()
+        .iter()
+        .map(|m| [m[12], m[13], m[14]])
+        .collect();
+    assert_eq!(
+        moved,
+        [[0., 0., 0.], [0., 0., 0.], [0., 1., 0.], [0., 1., 0.]]
+    );
+
+    let object = od.gen_object::<mod3d_base::BaseMaterial, Renderable>(&gltf, &[], &[], &[]);
+    let skins: Vec<Option<std::ops::Range<usize>>> = object
+        .components
+        .borrow_elements()
+        .iter()
+        .map(|c| c.data.skin.clone())
+        .collect();
+    assert_eq!(skins, [Some(0..2), Some(2..4)]);
+    Ok(())
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn root_joint_below_armature() -> Result<(), Error> {
+    // The armature is rotated by -90 degrees about X, so the Y axis of
+    // the joints is the -Z axis of the mesh
+    const JSON: &str = r##"
+{
+    "asset" : { "version" : "2.0" },
+    "nodes" : [
+        { "name" : "Body", "mesh" : 0, "skin" : 0 },
+        { "name" : "Armature", "rotation" : [ -0.70710677, 0.0, 0.0, 0.70710677 ], "children" : [ 2 ] },
+        { "name" : "Root", "translation" : [ 0.0, 1.0, 0.0 ] }
+    ],
+    "meshes" : [ { "primitives" : [] } ],
+    "skins" : [ { "joints" : [ 2 ] } ]
+}
+"##;
+    let json_value: serde_json::Value = serde_json::from_str(JSON)?;
+    let gltf = Gltf::of_json_value(json_value)?;
+    let mut od = mod3d_gltf::ObjectData::new(&gltf);
+    od.add_object(&gltf, gltf.get_node("Body").unwrap());
+    od.derive_uses(&gltf);
+    let skeleton = od.gen_skeleton(&gltf).unwrap();
+    let bones = skeleton.skeleton.borrow_elements();
+    assert_eq!(
+        bones[0].data.borrow_transformation().translation(),
+        [0., 1., 0.],
+        "The root bone keeps the local transformation of its joint"
+    );
+
+    let mut pose = mod3d_base::SkeletonPose::new(&skeleton);
+    let bone_pose = pose.bone_pose_mut(0).unwrap();
+    let t = *bone_pose.transformation();
+    bone_pose.set_transformation(t.with_translation([0., 2., 0.]));
+    pose.update(1);
+    let m = pose.bone_matrices()[0];
+    for (i, e) in [0., 0., -1.].iter().enumerate() {
+        assert!(
+            (m[12 + i] - e).abs() < 1E-5,
+            "Animating the root joint moves it in armature space {m:?}"
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use mod3d_base::example_client::Renderable;
+use mod3d_gltf::{Error, Gltf, GltfBuffer};
+
+//fi sparse_gltf
+/// Create a Gltf with a triangle whose positions are sparse (modifying
+/// a buffer view) and whose normals have no buffer view but are
+/// sparse, with an animation whose output has no buffer view
+#[cfg(feature = "serde_json")]
+fn sparse_gltf() -> Result<Gltf, Error> {
+    let mut data = vec![];
+    for i in [0_u16, 1, 2, 0] {
+        data.extend_from_slice(&i.to_le_bytes());
+    }
+    for f in [0.0_f32, 0., 0., 1., 0., 0., 0., 1., 0.] {
+        data.extend_from_slice(&f.to_le_bytes());
+    }
+    data.extend_from_slice(&[1, 0, 0, 0]);
+    for f in [5.0_f32, 6., 7., 0., 0., 1.] {
+        data.extend_from_slice(&f.to_le_bytes());
+    }
+    assert_eq!(data.len(), 72);
+    let buffer = GltfBuffer::of_base64(&data);
+    let json = format!(
+        r##"
+{{
+    "asset" : {{ "version" : "2.0" }},
+    "nodes" : [ {{ "name" : "Triangle", "mesh" : 0 }} ],
+    "meshes" : [ {{ "primitives" : [ {{
+        "attributes" : {{ "POSITION" : 1, "NORMAL" : 2 }},
+        "indices" : 0
+    }} ] }} ],
+    "buffers" : [ {{ "uri" : "{}", "byteLength" : 72 }} ],
+    "bufferViews" : [
+        {{ "buffer" : 0, "byteLength" : 8 }},
+        {{ "buffer" : 0, "byteOffset" : 8, "byteLength" : 36 }},
+        {{ "buffer" : 0, "byteOffset" : 44, "byteLength" : 4 }},
+        {{ "buffer" : 0, "byteOffset" : 48, "byteLength" : 24 }}
+    ],
+    "accessors" : [
+        {{ "bufferView" : 0, "componentType" : 5123, "count" : 3, "type" : "SCALAR" }},
+        {{ "bufferView" : 1, "componentType" : 5126, "count" : 3, "type" : "VEC3",
+           "sparse" : {{ "count" : 1,
+                         "indices" : {{ "bufferView" : 2, "componentType" : 5121 }},
+                         "values" : {{ "bufferView" : 3 }} }} }},
+        {{ "componentType" : 5126, "count" : 3, "type" : "VEC3",
+           "sparse" : {{ "count" : 1,
+                         "indices" : {{ "bufferView" : 2, "componentType" : 5121 }},
+                         "values" : {{ "bufferView" : 3, "byteOffset" : 12 }} }} }},
+        {{ "bufferView" : 3, "componentType" : 5126, "count" : 2, "type" : "SCALAR" }},
+        {{ "componentType" : 5126, "count" : 2, "type" : "VEC3" }}
+    ],
+    "animations" : [ {{
+        "channels" : [ {{ "sampler" : 0, "target" : {{ "node" : 0, "path" : "translation" }} }} ],
+        "samplers" : [ {{ "input" : 3, "output" : 4 }} ]
+    }} ]
+}}
+"##,
+        buffer.uri()
+    );
+    let json_value: serde_json::Value = serde_json::from_str(&json)?;
+    Gltf::of_json_value(json_value)
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn sparse_accessors() -> Result<(), Error> {
+    let mut gltf = sparse_gltf()?;
+    assert!(gltf.accessors()[1].requires_materializing());
+    assert_eq!(gltf.accessors()[1].sparse().unwrap().count, 1);
+
+    let mut od = mod3d_gltf::ObjectData::new(&gltf);
+    od.add_object(&gltf, 0.into());
+    od.derive_uses(&gltf);
+    let buffers = od.gen_byte_buffers(&mut gltf, &mod3d_gltf::buf_parse_fail, None)?;
+    assert_eq!(
+        buffers.len(),
+        2,
+        "Materialized data is in an additional buffer"
+    );
+    assert_eq!(gltf.buffers().len(), 2);
+
+    let read = |acc: usize| {
+        let acc = &gltf.accessors()[acc];
+        assert!(!acc.requires_materializing());
+        let bv = &gltf[acc.buffer_view().unwrap()];
+        assert_eq!(bv.buffer(), 1.into());
+        acc.read_floats(bv, &buffers[1])
+    };
+    assert_eq!(read(1)?, vec![0., 0., 0., 5., 6., 7., 0., 1., 0.]);
+    assert_eq!(read(2)?, vec![0., 0., 0., 0., 0., 1., 0., 0., 0.]);
+    assert_eq!(read(4)?, vec![0.; 6]);
+
+    let clips = od.gen_animations(&gltf, &|i| &buffers[i], &|_| Some(0))?;
+    assert_eq!(clips.len(), 1);
+
+    let buffer_data = od.gen_buffer_data::<_, _, Renderable>(&|x| &buffers[x]);
+    let buffer_descriptors = od.gen_descriptors(&gltf, &|x| &buffer_data[x]);
+    assert_eq!(buffer_descriptors.len(), 2);
+    let (index_accessors, data_accessors) =
+        od.gen_accessors(&gltf, &|x| &buffer_data[x], &|x| &buffer_descriptors[x]);
+    let vertices = od.gen_vertices(&gltf, &|x| &index_accessors[x], &|x| &data_accessors[x]);
+    assert_eq!(vertices.len(), 1);
+    Ok(())
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn sparse_accessor_errors() -> Result<(), Error> {
+    let mut gltf = sparse_gltf()?;
+    // Make the sparse index out of range of the accessors (keeping the
+    // attribute counts of the primitive the same)
+    let mut json_value = serde_json::to_value(&gltf)?;
+    json_value["accessors"][1]["count"] = 1.into();
+    json_value["accessors"][1]["sparse"]["count"] = 1.into();
+    json_value["accessors"][2]["count"] = 1.into();
+    let mut bad_gltf = Gltf::of_json_value(json_value)?;
+    let mut od = mod3d_gltf::ObjectData::new(&bad_gltf);
+    od.add_object(&bad_gltf, 0.into());
+    od.derive_uses(&bad_gltf);
+    assert!(matches!(
+        od.gen_byte_buffers(&mut bad_gltf, &mod3d_gltf::buf_parse_fail, None),
+        Err(Error::BadJson(_))
+    ));
+
+    // Sparse count larger than the accessor count is invalid
+    let mut json_value = serde_json::to_value(&gltf)?;
+    json_value["accessors"][1]["sparse"]["count"] = 4.into();
+    assert!(Gltf::of_json_value(json_value).is_err());
+
+    let mut od = mod3d_gltf::ObjectData::new(&gltf);
+    od.add_object(&gltf, 0.into());
+    od.derive_uses(&gltf);
+    od.gen_byte_buffers(&mut gltf, &mod3d_gltf::buf_parse_fail, None)?;
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use mod3d_base::example_client::Renderable;
+use mod3d_base::{BaseMaterial, Object};
+use mod3d_gltf::{
+    DataUriResolver, Error, FileUriResolver, Gltf, GltfAsset, GltfBufferUri, GltfExporter,
+    UriResolver,
+};
+
+#[test]
+fn data_uris() -> Result<(), Error> {
+    let (mime_type, data) = mod3d_gltf::parse_data_uri("data:image/png;base64,AQID")?.unwrap();
+    assert_eq!(mime_type, "image/png");
+    assert_eq!(data, vec![1, 2, 3]);
+
+    let (mime_type, data) =
+        mod3d_gltf::parse_data_uri("data:text/plain;charset=utf-8,a%20b")?.unwrap();
+    assert_eq!(mime_type, "text/plain");
+    assert_eq!(data, b"a b");
+
+    assert!(mod3d_gltf::parse_data_uri("image.png")?.is_none());
+    assert!(mod3d_gltf::parse_data_uri("data:image/png;base64").is_err());
+    assert!(mod3d_gltf::percent_decode("a%2").is_err());
+
+    assert_eq!(
+        mod3d_gltf::uri_mime_type("data:image/jpeg;base64,AQID"),
+        Some("image/jpeg")
+    );
+    assert_eq!(
+        mod3d_gltf::uri_mime_type("dir/Image.PNG"),
+        Some("image/png")
+    );
+    assert_eq!(mod3d_gltf::uri_mime_type("image"), None);
+
+    assert_eq!(
+        DataUriResolver.resolve("data:image/webp;base64,AQID")?,
+        [1, 2, 3]
+    );
+    assert!(DataUriResolver.resolve("image.png").is_err());
+    Ok(())
+}
+
+#[test]
+fn file_uris() -> Result<(), Error> {
+    let dir = std::env::temp_dir().join(format!("mod3d_gltf_uri_{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("sub dir"))?;
+    std::fs::write(dir.join("sub dir").join("a+b.bin"), [4, 5, 6])?;
+
+    let resolver = FileUriResolver::of_gltf_path(dir.join("model.gltf"));
+    assert_eq!(resolver.root(), dir);
+    let result = resolver.resolve("sub%20dir/a+b.bin");
+    std::fs::remove_dir_all(&dir)?;
+    assert_eq!(result?, [4, 5, 6]);
+
+    assert_eq!(resolver.resolve("data:,%01%02")?, [1, 2]);
+    assert!(resolver.resolve("sub%20dir/missing.bin").is_err());
+    assert!(resolver.resolve("https://example.com/a.bin").is_err());
+    Ok(())
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn resolve_buffers_and_images() -> Result<(), Error> {
+    let mut triangle = mod3d_base::ExampleVertices::new();
+    mod3d_base::example_objects::triangle::new::<Renderable>(&mut triangle, 0.5);
+    let material = BaseMaterial::of_rgba(0xff0000ff);
+    let mut obj: Object<BaseMaterial, Renderable> = Object::new();
+    let v_id = obj.add_vertices(triangle.borrow_vertices(0.into()));
+    let m_id = obj.add_material(&material);
+    obj.add_component(
+        None,
+        None,
+        mod3d_base::example_objects::triangle::mesh(v_id, m_id),
+    );
+
+    let mut exporter = GltfExporter::new(GltfAsset::new("".into()));
+    exporter.add_object(&obj, &|_| None)?;
+    let mut json = vec![];
+    let data = exporter.write_gltf(&mut json, GltfBufferUri::External("tri%20data.bin".into()))?;
+
+    // Add a data: URI image as the base color texture
+    let mut json_value: serde_json::Value = serde_json::from_slice(&json)?;
+    json_value["images"] = serde_json::json!([{"uri": "data:image/png;base64,AQID"}]);
+    json_value["textures"] = serde_json::json!([{"source": 0}]);
+    json_value["materials"][0]["pbrMetallicRoughness"]["baseColorTexture"] =
+        serde_json::json!({"index": 0});
+    let mut gltf = Gltf::of_json_value(json_value)?;
+
+    let dir = std::env::temp_dir().join(format!("mod3d_gltf_resolve_{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(dir.join("tri data.bin"), &data)?;
+    let resolver = FileUriResolver::new(&dir);
+
+    let mut od = mod3d_gltf::ObjectData::new(&gltf);
+    od.add_object(&gltf, 0.into());
+    od.derive_uses(&gltf);
+    let buffers = od.gen_resolved_buffers(&mut gltf, &resolver, None);
+    std::fs::remove_dir_all(&dir)?;
+    let buffers = buffers?;
+    assert_eq!(buffers[0], data);
+
+    let images = od.gen_resolved_images(
+        &gltf,
+        &resolver,
+        &|n| &buffers[n],
+        &|bytes: &[u8], mime_type: &str| Ok((bytes.to_vec(), mime_type.to_string())),
+    )?;
+    assert_eq!(images, vec![(vec![1, 2, 3], "image/png".to_string())]);
+    Ok(())
+}
@@ -0,0 +1,187 @@
+use mod3d_gltf::{Error, Gltf, GltfBuffer, ValidationReport};
+
+//fi validation_json
+/// A Gltf Json value with a skinned, textured triangle, given the
+/// buffer data (three u16 indices then positions and normals)
+#[cfg(feature = "serde_json")]
+fn validation_json(data: &[u8]) -> Result<serde_json::Value, Error> {
+    let buffer = GltfBuffer::of_base64(data);
+    let json = format!(
+        r##"
+{{
+    "asset" : {{ "version" : "2.0" }},
+    "scene" : 0,
+    "scenes" : [ {{ "nodes" : [ 0, 2 ] }} ],
+    "nodes" : [ {{ "mesh" : 0, "children" : [ 1 ] }}, {{ }}, {{ }} ],
+    "skins" : [ {{ "joints" : [ 0, 1 ] }} ],
+    "meshes" : [ {{ "primitives" : [ {{
+        "attributes" : {{ "POSITION" : 1, "NORMAL" : 2 }},
+        "indices" : 0,
+        "material" : 0
+    }} ] }} ],
+    "materials" : [ {{ "pbrMetallicRoughness" : {{ "baseColorTexture" : {{ "index" : 0 }} }} }} ],
+    "textures" : [ {{ "source" : 0 }} ],
+    "images" : [ {{ "uri" : "image.png" }} ],
+    "buffers" : [ {{ "uri" : "{}", "byteLength" : 80 }} ],
+    "bufferViews" : [
+        {{ "buffer" : 0, "byteLength" : 6 }},
+        {{ "buffer" : 0, "byteOffset" : 8, "byteLength" : 72 }}
+    ],
+    "accessors" : [
+        {{ "bufferView" : 0, "componentType" : 5123, "count" : 3, "type" : "SCALAR" }},
+        {{ "bufferView" : 1, "componentType" : 5126, "count" : 3, "type" : "VEC3" }},
+        {{ "bufferView" : 1, "byteOffset" : 36, "componentType" : 5126, "count" : 3, "type" : "VEC3" }}
+    ]
+}}
+"##,
+        buffer.uri()
+    );
+    Ok(serde_json::from_str(&json)?)
+}
+
+//fi validation_data
+/// Buffer data for [validation_json] with the given indices
+#[cfg(feature = "serde_json")]
+fn validation_data(indices: [u16; 3]) -> Vec<u8> {
+    let mut data = vec![];
+    for i in indices {
+        data.extend_from_slice(&i.to_le_bytes());
+    }
+    data.extend_from_slice(&[0, 0]);
+    for f in [0.0_f32, 0., 0., 1., 0., 0., 0., 1., 0.] {
+        data.extend_from_slice(&f.to_le_bytes());
+    }
+    for f in [0.0_f32, 0., 1., 0., 0., 1., 0., 0., 1.] {
+        data.extend_from_slice(&f.to_le_bytes());
+    }
+    data
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn validation_report() -> Result<(), Error> {
+    let data = validation_data([0, 1, 2]);
+    let gltf = Gltf::of_json_value(validation_json(&data)?)?;
+    let mut report = gltf.validation_report();
+    assert!(report.is_empty(), "{report}");
+    gltf.validate_index_values(&mut report, &|_| Some(&data));
+    assert!(report.is_empty(), "{report}");
+
+    let bad_data = validation_data([0, 1, 3]);
+    gltf.validate_index_values(&mut report, &|_| Some(&bad_data));
+    assert_eq!(report.issues().len(), 1);
+    assert!(report.has_issue_at("/meshes/0/primitives/0/indices"));
+
+    // Without buffer data the index values cannot be checked
+    let mut report = gltf.validation_report();
+    gltf.validate_index_values(&mut report, &|_| None);
+    assert!(report.is_empty());
+
+    // Indices must be unsigned bytes, shorts or ints; reading the
+    // Json without validating it permits signed shorts
+    let zero_data = validation_data([0, 0, 0]);
+    let mut json = validation_json(&zero_data)?;
+    json["accessors"][0]["componentType"] = serde_json::json!(5122);
+    let gltf: Gltf = serde_json::from_value(json)?;
+    let mut report = ValidationReport::default();
+    gltf.validate_index_values(&mut report, &|_| Some(&zero_data));
+    assert_eq!(report.issues().len(), 1);
+    assert!(report.has_issue_at("/meshes/0/primitives/0/indices"));
+    Ok(())
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn validation_failures() -> Result<(), Error> {
+    use serde_json::{json, Value};
+    let data = validation_data([0, 1, 2]);
+    type Mutation = fn(&mut Value);
+    let cases: [(&str, Mutation); 15] = [
+        ("/bufferViews/1/byteLength", |j| {
+            j["bufferViews"][1]["byteLength"] = json!(100)
+        }),
+        ("/accessors/2", |j| {
+            j["accessors"][2]["byteOffset"] = json!(40)
+        }),
+        ("/meshes/0/primitives/0/attributes", |j| {
+            j["accessors"][2]["count"] = json!(2)
+        }),
+        ("/meshes/0/primitives/0/attributes/NORMAL", |j| {
+            j["accessors"][2]["componentType"] = json!(5121)
+        }),
+        ("/meshes/0/primitives/0/indices", |j| {
+            j["meshes"][0]["primitives"][0]["indices"] = json!(1)
+        }),
+        ("/meshes/0/primitives/0/material", |j| {
+            j["meshes"][0]["primitives"][0]["material"] = json!(1)
+        }),
+        ("/nodes/0/mesh", |j| j["nodes"][0]["mesh"] = json!(1)),
+        ("/nodes/0/children", |j| {
+            j["nodes"][1]["children"] = json!([0])
+        }),
+        ("/nodes/2/children/0", |j| {
+            j["nodes"][2]["children"] = json!([1])
+        }),
+        ("/skins/0/joints", |j| {
+            j["skins"][0]["joints"] = json!([1, 2])
+        }),
+        ("/skins/0/skeleton", |j| {
+            j["skins"][0]["skeleton"] = json!(1)
+        }),
+        ("/scenes/0/nodes/0", |j| {
+            j["scenes"][0]["nodes"] = json!([1])
+        }),
+        ("/scene", |j| j["scene"] = json!(1)),
+        (
+            "/materials/0/pbrMetallicRoughness/baseColorTexture/index",
+            |j| j["materials"][0]["pbrMetallicRoughness"]["baseColorTexture"]["index"] = json!(1),
+        ),
+        ("/textures/0/source", |j| {
+            j["textures"][0]["source"] = json!(1)
+        }),
+    ];
+    for (pointer, f) in cases {
+        let mut json_value = validation_json(&data)?;
+        f(&mut json_value);
+        let Err(Error::Validation(report)) = Gltf::of_json_value(json_value) else {
+            panic!("Expected a validation failure at {pointer}");
+        };
+        assert!(report.has_issue_at(pointer), "{pointer} not in {report}");
+    }
+
+    // Every failure is reported, not just the first
+    let mut json_value = validation_json(&data)?;
+    json_value["nodes"][0]["mesh"] = json!(1);
+    json_value["textures"][0]["source"] = json!(1);
+    let Err(Error::Validation(report)) = Gltf::of_json_value(json_value) else {
+        panic!("Expected a validation failure");
+    };
+    assert_eq!(report.issues().len(), 2, "{report}");
+    assert_eq!(
+        report.to_string(),
+        "/nodes/0/mesh: Node 0 has mesh index 1 out of range\n\
+         /textures/0/source: Texture 0 has image index 1 out of range"
+    );
+    Ok(())
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn validation_of_loaded_indices() -> Result<(), Error> {
+    for (indices, ok) in [([0, 1, 2], true), ([0, 1, 3], false)] {
+        let data = validation_data(indices);
+        let mut gltf = Gltf::of_json_value(validation_json(&data)?)?;
+        let mut od = mod3d_gltf::ObjectData::new(&gltf);
+        od.add_object(&gltf, 0.into());
+        od.derive_uses(&gltf);
+        match od.gen_byte_buffers(&mut gltf, &mod3d_gltf::buf_parse_fail, None) {
+            Ok(_) => assert!(ok, "Indices {indices:?} must fail to load"),
+            Err(Error::Validation(report)) => {
+                assert!(!ok, "Indices {indices:?} must load: {report}");
+                assert!(report.has_issue_at("/meshes/0/primitives/0/indices"));
+            }
+            Err(e) => panic!("Unexpected error {e}"),
+        }
+    }
+    Ok(())
+}
@@ -20,7 +20,8 @@ uniform sampler2D BaseTexture;
 uniform sampler2D EmissionTexture;
 uniform sampler2D MRTexture;
 uniform sampler2D OcclusionTexture;
-uniform vec4[2] Material;
+uniform vec4[5] Material;
+uniform vec4[18] UvTransforms;
 
 layout(std140) uniform World {
     WorldData world;
@@ -58,6 +59,14 @@ vec3 Fresnel(vec3 h, vec3 v, vec3 color, float metallic)
     return F0 + (1.0 - F0) * pow(1.0 - cos_theta, 5.0);
 }
 
+// Transform texture coordinates for texture 'n' (color, normal, MR,
+// occlusion, emission, clearcoat...) - as per KHR_texture_transform
+vec2 uv_transform(int n, vec2 uv)
+{
+    vec4 uv1 = vec4(uv, 1.0, 0.0);
+    return vec2(dot(UvTransforms[2*n], uv1), dot(UvTransforms[2*n+1], uv1));
+}
+
 void main()
 {
     const float PI = 3.14159265359;
@@ -72,12 +81,19 @@ void main()
     vec3 normal;
     
     Color = vec4(0.);
-    base_color = texture(BaseTexture, Material_frag);
-    emission_color = texture(EmissionTexture, Material_frag);
-    metallic_roughness = texture(MRTexture, Material_frag);
-    occlusion = texture(OcclusionTexture, Material_frag).r; // * Material[1].z;
+    base_color = texture(BaseTexture, uv_transform(0, Material_frag));
+    emission_color = texture(EmissionTexture, uv_transform(4, Material_frag));
+    metallic_roughness = texture(MRTexture, uv_transform(2, Material_frag));
+    occlusion = 1.0 + Material[1].z * (texture(OcclusionTexture, uv_transform(3, Material_frag)).r - 1.0);
     metallic = metallic_roughness.b * Material[1].x;
     roughness = metallic_roughness.g * Material[1].y;
+    if ((Material[3].x == 1.0) && (base_color.a < Material[2].w)) {
+        discard;
+    }
+    if (Material[3].w == 1.0) {
+        Color = base_color;
+        return;
+    }
 
     view_direction = normalize(-View_direction);
     normal = normalize(Normal_frag);
@@ -3,7 +3,10 @@
 // Must match ShaderMaterialBaseData in model3d-gltf
 struct ShaderMaterialBaseData {
     vec4 base_color;
-    vec4 mrxx;
+    vec4 mr_occlusion_normal; // metallic, roughness, occlusion strength, normal scale
+    vec4 emissive_cutoff;     // emissive RGB, alpha cutoff
+    vec4 alpha_etc;           // alpha mode, double-sided, texture coordinate sets, unlit
+    vec4 clearcoat_etc;       // clearcoat, clearcoat roughness, transmission, ior
 };
 
 struct Light { // 32 bytes
@@ -31,14 +34,16 @@ layout(std140) uniform World {
 };
 uniform mat4 uModelMatrix;
 uniform mat4 uMeshMatrix;
+// Must be at least MAX_INSTANCES_PER_DRAW in size; the identity if not instanced
+uniform mat4 uInstanceMatrices[16];
 uniform sampler2D BaseTexture;
 // uniform ShaderMaterialBaseData Material;
 
 void main()
 {
-    World_position = uModelMatrix * uMeshMatrix * vec4(Position, 1.);
+    World_position = uModelMatrix * uMeshMatrix * uInstanceMatrices[gl_InstanceID] * vec4(Position, 1.);
     gl_Position = world.view_matrix * World_position;
     View_direction = gl_Position.xyz;
-    Normal_frag = (uModelMatrix * uMeshMatrix * vec4(Normal, 0.)).xyz;
+    Normal_frag = (uModelMatrix * uMeshMatrix * uInstanceMatrices[gl_InstanceID] * vec4(Normal, 0.)).xyz;
     Material_frag = TexCoord;
 }
@@ -0,0 +1,63 @@
+#version 330 core
+
+// Must match ShaderMaterialBaseData in model3d-gltf
+struct ShaderMaterialBaseData {
+    vec4 base_color;
+    vec4 mr_occlusion_normal; // metallic, roughness, occlusion strength, normal scale
+    vec4 emissive_cutoff;     // emissive RGB, alpha cutoff
+    vec4 alpha_etc;           // alpha mode, double-sided, texture coordinate sets, unlit
+    vec4 clearcoat_etc;       // clearcoat, clearcoat roughness, transmission, ior
+};
+
+struct Light { // 32 bytes
+    vec4 position;
+    vec4 color;
+};
+
+struct WorldData {
+    mat4 view_matrix; // 64 bytes
+    Light lights[4];  // 128 bytes
+};
+
+
+layout (location = 0) in vec3 Position;
+in vec3 Normal;
+in vec2 TexCoord;
+in vec4 Joints;
+in vec4 Weights;
+out vec3 Normal_frag;
+out vec4 World_position;
+out vec3 View_direction;
+out vec2 Material_frag;
+
+
+layout(std140) uniform World {
+    WorldData world;
+};
+uniform mat4 uModelMatrix;
+uniform mat4 uMeshMatrix;
+// Must be at least MAX_INSTANCES_PER_DRAW in size; the identity if not instanced
+uniform mat4 uInstanceMatrices[16];
+// Must be at least MAX_BONE_MATRICES in size; with the instance matrices
+// this must fit in the 256 vec4 vertex uniforms of GLES3 and WebGL2
+uniform mat4 uBonesMatrices[40];
+// 1.0 if the primitive is skinned, 0.0 if not
+uniform float uBonesScale;
+uniform sampler2D BaseTexture;
+// uniform ShaderMaterialBaseData Material;
+
+void main()
+{
+    mat4 skin_matrix = mat4(1.);
+    if (uBonesScale > 0.) {
+        skin_matrix = (Weights.x * uBonesMatrices[int(Joints.x)] +
+                       Weights.y * uBonesMatrices[int(Joints.y)] +
+                       Weights.z * uBonesMatrices[int(Joints.z)] +
+                       Weights.w * uBonesMatrices[int(Joints.w)]);
+    }
+    World_position = uModelMatrix * uMeshMatrix * uInstanceMatrices[gl_InstanceID] * skin_matrix * vec4(Position, 1.);
+    gl_Position = world.view_matrix * World_position;
+    View_direction = gl_Position.xyz;
+    Normal_frag = (uModelMatrix * uMeshMatrix * uInstanceMatrices[gl_InstanceID] * skin_matrix * vec4(Normal, 0.)).xyz;
+    Material_frag = TexCoord;
+}
@@ -85,6 +85,13 @@ fn main() {
     s.add_node(n0);
     s.add_node(n1);
     gltf.add_scene(s);
-    let _j = serde_json::to_string_pretty(&gltf).unwrap();
-    println!("{_j}");
+
+    // Write a GLB file if a filename is given, else print the Json
+    if let Some(filename) = std::env::args().nth(1) {
+        let mut file = std::fs::File::create(&filename).unwrap();
+        mod3d_gltf::glb_save(&mut file, &gltf, None).unwrap();
+    } else {
+        let _j = serde_json::to_string_pretty(&gltf).unwrap();
+        println!("{_j}");
+    }
 }